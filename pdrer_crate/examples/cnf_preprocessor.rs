@@ -0,0 +1,213 @@
+//! CNF preprocessor that applies bounded variable elimination and structured bounded variable
+//! addition to a DIMACS file, writes the simplified DIMACS file and optionally a DRAT proof of
+//! the transformation and the stack that extends models of the simplified file to the input.
+//!
+//! A DRAT refutation of the simplified file can be appended to the proof to get a refutation of
+//! the original file, for example:
+//!
+//! ```sh
+//! cargo run --release --example cnf_preprocessor -- input.cnf --output simplified.cnf --drat preprocess.drat &&
+//! cadical simplified.cnf solver.drat ;
+//! cat preprocess.drat solver.drat > full.drat &&
+//! drat-trim input.cnf full.drat
+//! ```
+
+// ********************************************************************************************
+// imports
+// ********************************************************************************************
+use clap::Parser;
+use rust_formal_verification::formulas::{cnf::CNFPreprocessorParameters, CNF};
+use std::{
+    fmt::{self, Display, Formatter},
+    fs::File,
+    io::BufWriter,
+    path::Path,
+    process::ExitCode,
+};
+
+// ********************************************************************************************
+// Types
+// ********************************************************************************************
+
+const D: CNFPreprocessorParameters = CNFPreprocessorParameters::DEFAULT;
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum Toggle {
+    On,
+    Off,
+}
+
+impl From<bool> for Toggle {
+    fn from(b: bool) -> Self {
+        if b {
+            Toggle::On
+        } else {
+            Toggle::Off
+        }
+    }
+}
+
+impl From<Toggle> for bool {
+    fn from(t: Toggle) -> Self {
+        match t {
+            Toggle::On => true,
+            Toggle::Off => false,
+        }
+    }
+}
+
+impl Display for Toggle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Toggle::On => write!(f, "on"),
+            Toggle::Off => write!(f, "off"),
+        }
+    }
+}
+
+// ********************************************************************************************
+// Args struct
+// ********************************************************************************************
+
+/// CNF preprocessor, takes in a path to a DIMACS file (possibly gzipped) and writes a simplified
+/// DIMACS file that is satisfiable if and only if the input is.
+#[derive(Parser, Debug)]
+#[command(version = env!("GIT_HASH"), about, long_about = None)]
+struct Args {
+    /// Path to the DIMACS file
+    #[arg()]
+    input_cnf_path: String,
+
+    /// Path to write the simplified DIMACS file to
+    #[arg(short, long, default_value_t = format!("simplified.cnf"))]
+    output: String,
+
+    /// Path to write a DRAT proof of the transformation to, if empty then no proof is written.
+    #[arg(long, default_value_t = String::new())]
+    drat: String,
+
+    /// Path to write the model reconstruction stack to, if empty then it is not written.
+    /// Each line is a pivot literal followed by the rest of a clause and 0, a model of the
+    /// simplified file is extended by going over the lines from last to first and making the
+    /// pivot true whenever the clause is falsified.
+    #[arg(long, default_value_t = String::new())]
+    reconstruction: String,
+
+    /// Print progress and sizes
+    #[arg(short, long, default_value_t = Toggle::Off)]
+    verbose: Toggle,
+
+    /// Eliminate variables using bounded variable elimination
+    #[arg(long, default_value_t = D.bounded_variable_elimination.into())]
+    bve: Toggle,
+
+    /// Only eliminate variables that appear in at most this many clauses
+    #[arg(long, default_value_t = D.bve_max_occurrences)]
+    bve_max_occurrences: usize,
+
+    /// Only eliminate variables whose resolvents are at most this long
+    #[arg(long, default_value_t = D.bve_max_resolvent_length)]
+    bve_max_resolvent_length: usize,
+
+    /// Add variables for the AND pattern
+    #[arg(long, default_value_t = D.and_pattern.into())]
+    and_pattern: Toggle,
+
+    /// Add variables for the XOR pattern
+    #[arg(long, default_value_t = D.xor_pattern.into())]
+    xor_pattern: Toggle,
+
+    /// Add variables for the second XOR pattern
+    #[arg(long, default_value_t = D.xor_pattern_2.into())]
+    xor_pattern_2: Toggle,
+
+    /// The maximal number of variable additions
+    #[arg(long, default_value_t = D.bva_max_iterations)]
+    bva_max_iterations: usize,
+}
+
+// ********************************************************************************************
+// helper functions
+// ********************************************************************************************
+
+macro_rules! print_if_verbose {
+    ($verbose:expr, $($arg:tt)*) => {
+        if $verbose.into() {
+            println!($($arg)*);
+        }
+    };
+}
+
+// ********************************************************************************************
+// main
+// ********************************************************************************************
+
+fn main() -> ExitCode {
+    let start_time = std::time::Instant::now();
+    let args = Args::parse();
+
+    let cnf = match CNF::read_dimacs_from_path(Path::new(&args.input_cnf_path)) {
+        Ok(cnf) => cnf,
+        Err(e) => {
+            eprintln!("Error while reading '{}' : {}", args.input_cnf_path, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    print_if_verbose!(
+        args.verbose,
+        "Read {} clauses over {} variables.",
+        cnf.len(),
+        cnf.get_max_variable().number()
+    );
+
+    let parameters = CNFPreprocessorParameters {
+        bounded_variable_elimination: args.bve.into(),
+        bve_max_occurrences: args.bve_max_occurrences,
+        bve_max_resolvent_length: args.bve_max_resolvent_length,
+        and_pattern: args.and_pattern.into(),
+        xor_pattern: args.xor_pattern.into(),
+        xor_pattern_2: args.xor_pattern_2.into(),
+        bva_max_iterations: args.bva_max_iterations,
+    };
+
+    let result = if args.drat.is_empty() {
+        Ok(cnf.preprocess(parameters))
+    } else {
+        File::create(&args.drat)
+            .and_then(|f| cnf.preprocess_with_drat(parameters, BufWriter::new(f)))
+    };
+    let result = match result {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Error while writing the proof to '{}' : {}", args.drat, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    print_if_verbose!(
+        args.verbose,
+        "Eliminated {} variables, added {} variables, {} clauses remain.",
+        result.eliminated_variables.len(),
+        result.definitions.len(),
+        result.cnf.len()
+    );
+
+    if let Err(e) = File::create(&args.output).and_then(|f| result.cnf.write_dimacs(f)) {
+        eprintln!("Error while writing '{}' : {}", args.output, e);
+        return ExitCode::FAILURE;
+    }
+    if !args.reconstruction.is_empty() {
+        if let Err(e) =
+            File::create(&args.reconstruction).and_then(|f| result.write_reconstruction_stack(f))
+        {
+            eprintln!("Error while writing '{}' : {}", args.reconstruction, e);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    print_if_verbose!(
+        args.verbose,
+        "Elapsed time = {}",
+        start_time.elapsed().as_secs_f32()
+    );
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,268 @@
+//! Differential fuzzer for the verification pipeline. Random AIGs are simplified and proven by PDR
+//! under several parameter sets, the results are checked and compared to a bounded model checker.
+//! The first failing model is delta debugged and written as an AIGER file, for example:
+//!
+//! ```
+//! cargo run --release --example fuzz_pdr -- --iterations 1000 --latches 8 --reproducer bug.aig &&
+//! cargo run --release --example pdr_engine_for_hwmcc -- bug.aig -v on
+//! ```
+//!
+//! The command that replays the failure with the same configuration and PDR seed is printed
+//! together with the failure.
+
+// ********************************************************************************************
+// imports
+// ********************************************************************************************
+use clap::Parser;
+use rust_formal_verification::{
+    engines::{fuzzing::FuzzingConfiguration, Fuzzer, FuzzingParameters},
+    models::AndInverterGraph,
+};
+use std::{
+    fmt::{self, Display, Formatter},
+    fs,
+    process::ExitCode,
+    time::Duration,
+};
+
+// ********************************************************************************************
+// Types
+// ********************************************************************************************
+
+const D: FuzzingParameters = FuzzingParameters::DEFAULT;
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum Toggle {
+    On,
+    Off,
+}
+
+impl From<bool> for Toggle {
+    fn from(b: bool) -> Self {
+        if b {
+            Toggle::On
+        } else {
+            Toggle::Off
+        }
+    }
+}
+
+impl From<Toggle> for bool {
+    fn from(t: Toggle) -> Self {
+        match t {
+            Toggle::On => true,
+            Toggle::Off => false,
+        }
+    }
+}
+
+impl Display for Toggle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Toggle::On => write!(f, "on"),
+            Toggle::Off => write!(f, "off"),
+        }
+    }
+}
+
+// ********************************************************************************************
+// Args struct
+// ********************************************************************************************
+
+/// Fuzzer that checks PDR against a bounded model checker on random AIGs.
+#[derive(Parser, Debug)]
+#[command(version = env!("GIT_HASH"), about, long_about = None)]
+struct Args {
+    /// Path to an AIGER file to check instead of generating random models, useful for
+    /// replaying a reproducer
+    #[arg(long, default_value_t = String::new())]
+    replay: String,
+
+    /// The PDR seed to replay the model with, as reported by a failure. Every configuration is
+    /// run with this seed instead of seeds drawn from `--seed`. Requires `--replay`
+    #[arg(long)]
+    pdr_seed: Option<u64>,
+
+    /// Path to write the minimized failing model to
+    #[arg(long, default_value_t = format!("reproducer.aig"))]
+    reproducer: String,
+
+    /// Seed of the random number generator
+    #[arg(long, default_value_t = D.seed)]
+    seed: u64,
+
+    /// The number of random models to check
+    #[arg(long, default_value_t = D.iterations)]
+    iterations: usize,
+
+    /// The number of inputs of each random model
+    #[arg(long, default_value_t = D.number_of_inputs)]
+    inputs: usize,
+
+    /// The number of latches of each random model
+    #[arg(long, default_value_t = D.number_of_latches)]
+    latches: usize,
+
+    /// The number of and gates of each random model
+    #[arg(long, default_value_t = D.number_of_and_gates)]
+    and_gates: usize,
+
+    /// The number of bad wires of each random model
+    #[arg(long, default_value_t = D.number_of_bad_wires)]
+    bad: usize,
+
+    /// The number of invariant constraints of each random model
+    #[arg(long, default_value_t = D.number_of_constraint_wires)]
+    constraints: usize,
+
+    /// Choose the inputs of the and gates close to the gate, which yields deeper circuits
+    #[arg(long, default_value_t = D.use_geometric_distribution.into())]
+    geometric: Toggle,
+
+    /// Simplify the circuit using the default pipeline before proving it
+    #[arg(long, default_value_t = D.simplify.into())]
+    simplify: Toggle,
+
+    /// The depth up to which the bounded model checker looks for counterexamples
+    #[arg(long, default_value_t = D.bmc_depth)]
+    bmc_depth: usize,
+
+    /// The time out of each PDR run in seconds
+    #[arg(long, default_value_t = D.timeout.as_secs())]
+    time_out_seconds: u64,
+
+    /// Delta debug the failing model before writing it
+    #[arg(long, default_value_t = D.minimize.into())]
+    minimize: Toggle,
+
+    /// Names of the PDR configurations to use separated by ',', if empty then all of them are
+    /// used (plain, f_inf, ctg, er, er_ctg)
+    #[arg(long, default_value_t = String::new())]
+    configurations: String,
+
+    /// Print a line for every PDR run
+    #[arg(short, long, default_value_t = Toggle::Off)]
+    verbose: Toggle,
+}
+
+// ********************************************************************************************
+// helper functions
+// ********************************************************************************************
+
+macro_rules! print_if_verbose {
+    ($verbose:expr, $($arg:tt)*) => {
+        if $verbose.into() {
+            println!($($arg)*);
+        }
+    };
+}
+
+fn get_configurations(names: &str) -> Result<Vec<FuzzingConfiguration>, String> {
+    if names.trim().is_empty() {
+        return Ok(FuzzingConfiguration::ALL.to_vec());
+    }
+    names
+        .split(',')
+        .map(|name| {
+            FuzzingConfiguration::ALL
+                .iter()
+                .find(|c| c.name == name.trim())
+                .copied()
+                .ok_or_else(|| format!("Unknown configuration '{}'", name))
+        })
+        .collect()
+}
+
+// ********************************************************************************************
+// main
+// ********************************************************************************************
+
+fn main() -> ExitCode {
+    let start_time = std::time::Instant::now();
+    let args = Args::parse();
+
+    let configurations = match get_configurations(&args.configurations) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let parameters = FuzzingParameters {
+        seed: args.seed,
+        iterations: args.iterations,
+        number_of_inputs: args.inputs,
+        number_of_latches: args.latches,
+        number_of_and_gates: args.and_gates,
+        number_of_bad_wires: args.bad,
+        number_of_constraint_wires: args.constraints,
+        use_geometric_distribution: args.geometric.into(),
+        simplify: args.simplify.into(),
+        bmc_depth: args.bmc_depth,
+        timeout: Duration::from_secs(args.time_out_seconds),
+        minimize: args.minimize.into(),
+        verbose: args.verbose.into(),
+    };
+    let mut fuzzer = Fuzzer::new(parameters);
+    fuzzer.set_configurations(configurations);
+
+    // the panics are reported as failures, do not print them while delta debugging
+    if !bool::from(args.verbose) {
+        std::panic::set_hook(Box::new(|_| {}));
+    }
+
+    if args.pdr_seed.is_some() && args.replay.is_empty() {
+        eprintln!("--pdr-seed can only be used together with --replay");
+        return ExitCode::FAILURE;
+    }
+    let result = if args.replay.is_empty() {
+        fuzzer.run()
+    } else {
+        match (AndInverterGraph::from_aig_path(&args.replay), args.pdr_seed) {
+            (Ok(aig), None) => fuzzer.check_aig(&aig, 0),
+            (Ok(aig), Some(seed)) => fuzzer
+                .get_configurations()
+                .iter()
+                .try_for_each(|c| fuzzer.check_aig_with_seed(&aig, c, seed)),
+            (Err(e), _) => {
+                eprintln!("Error while reading aig file '{}' : {}", args.replay, e);
+                return ExitCode::FAILURE;
+            }
+        }
+    };
+
+    let r = match result {
+        Ok(()) => {
+            println!("No failures found.");
+            ExitCode::SUCCESS
+        }
+        Err(failure) => {
+            println!("{}", failure);
+            if let Err(e) = fs::write(&args.reproducer, failure.reproducer.get_aig()) {
+                eprintln!("Error while writing '{}' : {}", args.reproducer, e);
+            } else {
+                println!("Reproducer written to '{}'.", args.reproducer);
+                println!(
+                    "Replay with: cargo run --release --example fuzz_pdr -- --replay {} \
+                     --configurations {} --pdr-seed {} --simplify {} --bmc-depth {} \
+                     --time-out-seconds {}",
+                    args.reproducer,
+                    failure.configuration.name,
+                    failure.pdr_seed,
+                    args.simplify,
+                    args.bmc_depth,
+                    args.time_out_seconds
+                );
+            }
+            ExitCode::FAILURE
+        }
+    };
+
+    print_if_verbose!(
+        args.verbose,
+        "Elapsed time = {}",
+        start_time.elapsed().as_secs_f32()
+    );
+    r
+}
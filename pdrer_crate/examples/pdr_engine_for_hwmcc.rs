@@ -36,12 +36,21 @@
 use clap::Parser;
 use rust_formal_verification::{
     engines::{
-        pdr::PropertyDirectedReachabilityProofError, PropertyDirectedReachability,
-        PropertyDirectedReachabilityParameters,
+        pdr::{
+            checkpoint::PropertyDirectedReachabilityCheckpoint, ProofObligationScheduling,
+            PropertyDirectedReachabilityDirection, PropertyDirectedReachabilityProofError,
+        },
+        BidirectionalPropertyDirectedReachability, ExplicitStateReachability,
+        ExplicitStateReachabilityParameters, PropertyDirectedReachability,
+        PropertyDirectedReachabilityParameters, RandomSimulation, RandomSimulationParameters,
     },
-    formulas::Variable,
+    formulas::{Clause, Literal, Variable},
     models::{
-        finite_state_transition_system::{FiniteStateTransitionSystemError, ProofResult},
+        circuit::simplifiers::CircuitSimplificationPipeline,
+        finite_state_transition_system::{
+            FiniteStateTransitionSystemError, ProofResult, TransitionRelationEncoding,
+        },
+        truth_table::TRUTH_TABLE_MAX_INPUTS,
         AndInverterGraph, Circuit, Counterexample, FiniteStateTransitionSystem, Proof, Signal,
         SignalTracker,
     },
@@ -50,6 +59,7 @@ use rust_formal_verification::{
 use std::{
     cell::RefCell,
     fmt::{self, Display, Formatter},
+    path::Path,
     process::ExitCode,
     rc::Rc,
 };
@@ -95,6 +105,82 @@ impl Display for Toggle {
     }
 }
 
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum Scheduling {
+    InsertionOrder,
+    SmallestCube,
+    HighestWeight,
+    Random,
+}
+
+impl From<ProofObligationScheduling> for Scheduling {
+    fn from(s: ProofObligationScheduling) -> Self {
+        match s {
+            ProofObligationScheduling::InsertionOrder => Scheduling::InsertionOrder,
+            ProofObligationScheduling::SmallestCube => Scheduling::SmallestCube,
+            ProofObligationScheduling::HighestWeight => Scheduling::HighestWeight,
+            ProofObligationScheduling::Random => Scheduling::Random,
+        }
+    }
+}
+
+impl From<Scheduling> for ProofObligationScheduling {
+    fn from(s: Scheduling) -> Self {
+        match s {
+            Scheduling::InsertionOrder => ProofObligationScheduling::InsertionOrder,
+            Scheduling::SmallestCube => ProofObligationScheduling::SmallestCube,
+            Scheduling::HighestWeight => ProofObligationScheduling::HighestWeight,
+            Scheduling::Random => ProofObligationScheduling::Random,
+        }
+    }
+}
+
+impl Display for Scheduling {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        ProofObligationScheduling::from(*self).fmt(f)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum Direction {
+    Forward,
+    Backward,
+    Bidirectional,
+}
+
+impl From<Direction> for PropertyDirectedReachabilityDirection {
+    fn from(d: Direction) -> Self {
+        match d {
+            Direction::Forward => PropertyDirectedReachabilityDirection::Forward,
+            Direction::Backward => PropertyDirectedReachabilityDirection::Backward,
+            Direction::Bidirectional => PropertyDirectedReachabilityDirection::Bidirectional,
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        PropertyDirectedReachabilityDirection::from(*self).fmt(f)
+    }
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum Encoding {
+    Tseitin,
+    Pg,
+    Lut,
+}
+
+impl Display for Encoding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Encoding::Tseitin => write!(f, "tseitin"),
+            Encoding::Pg => write!(f, "pg"),
+            Encoding::Lut => write!(f, "lut"),
+        }
+    }
+}
+
 // ********************************************************************************************
 // Args struct
 // ********************************************************************************************
@@ -133,6 +219,99 @@ struct Args {
     #[arg(long, default_value_t = format!("certificate.aig"))]
     certificate: String,
 
+    /// Path to a combinational AIG that computes the invariant over the latches, if empty then
+    /// no monitor is produced.
+    #[arg(long, default_value_t = String::new())]
+    monitor_aig: String,
+
+    /// Path to a Verilog module that computes the invariant over the latches, if empty then no
+    /// monitor is produced.
+    #[arg(long, default_value_t = String::new())]
+    monitor_verilog: String,
+
+    /// Index of a single property to check (bad wire, or output if there are no bad wires),
+    /// the circuit is reduced to the cone of influence of this property before checking it.
+    #[arg(long)]
+    property: Option<usize>,
+
+    /// The simplification passes to run before the proof engine, separated by ';'
+    /// (for example "strash;remove_unused;tech_map:k=4,l=10;condense")
+    #[arg(long, default_value_t = CircuitSimplificationPipeline::DEFAULT.to_string())]
+    simplify: String,
+
+    /// The encoding of the gates of the circuit in the CNFs, tseitin describes every gate fully,
+    /// pg (Plaisted-Greenbaum) only describes the polarities each gate is used in and lut
+    /// describes the circuit as look up tables chosen using technology mapping
+    #[arg(long, default_value_t = Encoding::Tseitin)]
+    encoding: Encoding,
+
+    /// The maximal number of inputs of each look up table when using the lut encoding
+    #[arg(
+        long,
+        default_value_t = 4,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new()
+            .range(2..=TRUTH_TABLE_MAX_INPUTS as u64)
+    )]
+    encoding_lut_k: usize,
+
+    /// The number of cuts to consider for each gate when using the lut encoding
+    #[arg(
+        long,
+        default_value_t = 8,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    encoding_lut_l: usize,
+
+    /// The maximal number of times to repeat the simplification passes, the repetition stops
+    /// early once the circuit stops shrinking
+    #[arg(long, default_value_t = 1)]
+    simplify_iterations: usize,
+
+    /// Path to write the simplified circuit to as an AIGER file, if empty then the simplified
+    /// circuit will not be written.
+    #[arg(long, default_value_t = String::new())]
+    simplified_aig: String,
+
+    /// Path to write the simplified circuit to as a structural Verilog module, if empty then the
+    /// simplified circuit will not be written.
+    #[arg(long, default_value_t = String::new())]
+    simplified_verilog: String,
+
+    /// Path to write a checkpoint of the PDR engine to when it stops without a result (time out or
+    /// max depth), if empty then no checkpoint will be written.
+    #[arg(long, default_value_t = String::new())]
+    checkpoint: String,
+
+    /// Also write the checkpoint every this many seconds while the PDR engine runs, so that a job
+    /// that is killed can be resumed, 0 means never. Does nothing if `--checkpoint` is empty.
+    #[arg(long, default_value_t = 0)]
+    checkpoint_interval: u64,
+
+    /// Path of a checkpoint to resume the PDR engine from, the checkpoint must have been created
+    /// with the same model and simplification arguments. If empty then the engine starts from scratch.
+    #[arg(long, default_value_t = String::new())]
+    resume: String,
+
+    /// Path of a file with lemmas to seed the PDR engine with, for example the invariant of a
+    /// previous version of the design written using `--lemmas-out`. If empty then no lemmas are seeded.
+    #[arg(long, default_value_t = String::new())]
+    lemmas_in: String,
+
+    /// Path to write the invariant to when the property is safe, as lemmas over latch names.
+    /// If empty then the lemmas will not be written.
+    #[arg(long, default_value_t = String::new())]
+    lemmas_out: String,
+
+    /// Path to write the statistics of the PDR run to as JSON when the engine stops.
+    /// If empty then the statistics will not be written.
+    #[arg(long, default_value_t = String::new())]
+    stats_json: String,
+
+    /// Print the statistics of the PDR run as a single line JSON object every this many seconds,
+    /// 0 means never.
+    #[arg(long, default_value_t = 0)]
+    stats_json_interval: u64,
+
     /// Seed to seed the random number generator with
     #[arg(short, long, default_value_t = D.seed)]
     seed: u64,
@@ -145,6 +324,11 @@ struct Args {
     #[arg(long, default_value_t = D.max_depth)]
     max_depth: usize,
 
+    /// Memory limit in MB, when the process uses more memory the simplification passes stop and
+    /// the PDR engine stops with an unknown result.
+    #[arg(long)]
+    memory_limit: Option<usize>,
+
     /// Ground the Xs in the counter example, if true then the counter example will not contain x.
     #[arg(short, long, default_value_t = false.into())]
     ground_ternary: Toggle,
@@ -153,6 +337,16 @@ struct Args {
     #[arg(short, long, default_value_t = false.into())]
     check_result: Toggle,
 
+    /// Use explicit-state reachability instead of PDR when the circuit has at most 25 latches
+    /// and at most 12 inputs, it returns the shortest counterexample
+    #[arg(long, default_value_t = Toggle::Off)]
+    explicit: Toggle,
+
+    /// The number of rounds of random simulation to run before proving, every round simulates
+    /// 64 random input sequences of 32 cycles, 0 disables random simulation
+    #[arg(long, default_value_t = 0)]
+    random_simulation_rounds: usize,
+
     /// Toggle using extended resolution or not.
     #[arg(long, default_value_t = D.er.into())]
     er: Toggle,
@@ -169,6 +363,23 @@ struct Args {
     #[arg(long, default_value_t = D.er_impl.into())]
     er_impl: Toggle,
 
+    /// Toggle defining extension variables as if-then-else of 3 literals.
+    #[arg(long, default_value_t = D.er_ite.into())]
+    er_ite: Toggle,
+
+    /// Toggle defining extension variables as the majority of 3 literals.
+    #[arg(long, default_value_t = D.er_maj3.into())]
+    er_maj3: Toggle,
+
+    /// Toggle defining extension variables for internal signals that depend only on latches.
+    #[arg(long, default_value_t = D.er_circuit_signals.into())]
+    er_circuit_signals: Toggle,
+
+    /// Maximum number of gates in the cone of an internal signal that is defined
+    /// (relevant only when --er-circuit-signals is on)
+    #[arg(long, default_value_t = D.er_circuit_signals_max_cone_size)]
+    er_circuit_signals_max_cone_size: usize,
+
     /// Number of clauses learned between adding extension literals
     /// (relevant only when --extension-variables is on)
     #[arg(long, default_value_t = D.er_delta.into())]
@@ -186,6 +397,30 @@ struct Args {
     #[arg(long, default_value_t = D.generalize_using_ctg_max_ctgs.into())]
     ctg_count: usize,
 
+    /// The order in which proof obligations of the same frame are handled.
+    #[arg(long, default_value_t = D.proof_obligation_scheduling.into())]
+    po_order: Scheduling,
+
+    /// Run PDR from the initial states, from the bad states on the reversed circuit, or
+    /// alternate between the two. Resuming, checkpoints and lemmas are only supported forward.
+    #[arg(long, default_value_t = Direction::Forward)]
+    direction: Direction,
+
+    /// Toggle keeping proof obligations that were blocked at the depth for when the depth is
+    /// increased, this can find deeper counterexamples sooner.
+    #[arg(long, default_value_t = D.re_push_proof_obligations_beyond_depth.into())]
+    po_re_push: Toggle,
+
+    /// Toggle minimizing the invariant before it is returned, smaller invariants are faster
+    /// to check by certifaiger.
+    #[arg(long, default_value_t = D.minimize_proof.into())]
+    minimize_proof: Toggle,
+
+    /// Toggle also removing literals from the clauses of the invariant when minimizing it
+    /// (relevant only when --minimize-proof is on)
+    #[arg(long, default_value_t = D.minimize_proof_shrink_literals.into())]
+    minimize_proof_shrink_literals: Toggle,
+
     /// Toggle using largest inductive sub-clause analysis
     #[arg(long, default_value_t = D.perform_lic_analysis.into())]
     lic: Toggle,
@@ -248,6 +483,7 @@ fn print_details_about_model(
         "Number of constraints = {}",
         fin_state.get_invariant_constraints_on_internals().len()
     );
+    println!("{}", fin_state.get_encoding_stats());
     let cnf = fin_state.construct_cnf();
     println!("Number of clauses in CNF = {}", cnf.len());
     println!("Number of variables in CNF = {}", cnf.get_variables().len());
@@ -285,7 +521,13 @@ fn declare_sat<F: Fn(Signal) -> Variable>(
         "Unsafe, Counter example found of depth {}.",
         e.inputs.len()
     );
-    let w = e.get_aigsim(t, aig, args.ground_ternary.into(), fin_state);
+    let w = e.get_aigsim_for_property(
+        t,
+        aig,
+        args.ground_ternary.into(),
+        args.property.unwrap_or(0),
+        fin_state,
+    );
     if !args.counterexample.is_empty() {
         fs::write(&args.counterexample, w).expect("Unable to write counterexample file.");
     } else {
@@ -323,7 +565,10 @@ fn declare_un_sat<F: Fn(Signal) -> Variable>(
             );
         }
     }
-    let witness_aig = p.get_certifaiger_witness(t, aig, s2v);
+    let witness_aig = match args.property {
+        Some(i) => p.get_certifaiger_witness_for_property(t, aig, i, &s2v),
+        None => p.get_certifaiger_witness(t, aig, &s2v),
+    };
     let w = witness_aig.get_aig();
     if !args.certificate.is_empty() {
         fs::write(&args.certificate, w).expect("Unable to write certificate file.");
@@ -333,9 +578,101 @@ fn declare_un_sat<F: Fn(Signal) -> Variable>(
             "Certificate path is empty, skipping writing certificate."
         );
     }
+    if !args.monitor_aig.is_empty() {
+        match p.get_monitor_aig(t, aig, &s2v) {
+            Ok(m) => {
+                fs::write(&args.monitor_aig, m.get_aig()).expect("Unable to write monitor file.")
+            }
+            Err(e) => print_if_verbose!(args.verbose, "Unable to create monitor: {}", e),
+        }
+    }
+    if !args.monitor_verilog.is_empty() {
+        match p.get_monitor_verilog(t, aig, &s2v, "invariant_monitor") {
+            Ok(m) => fs::write(&args.monitor_verilog, m).expect("Unable to write monitor file."),
+            Err(e) => print_if_verbose!(args.verbose, "Unable to create monitor: {}", e),
+        }
+    }
     ExitCode::from(args.safe_exit_code)
 }
 
+/// Returns the name of each latch in the original AIG with the variable it became after
+/// simplification. A latch is named by its symbol, or by "l<index>" when it has no symbol or
+/// the symbol contains white space. Latches that were removed by the simplification are skipped.
+fn get_latch_names(
+    aig: &AndInverterGraph,
+    t: &SignalTracker,
+    fin_state: &FiniteStateTransitionSystem,
+) -> Vec<(String, Variable)> {
+    let symbols: Vec<(u32, String)> = aig
+        .get_symbols()
+        .into_iter()
+        .filter(|(symbol_type, _, symbol)| {
+            *symbol_type == 'l' && !symbol.contains(char::is_whitespace)
+        })
+        .map(|(_, i, symbol)| (i, symbol))
+        .collect();
+    let mut names = vec![];
+    for (i, s) in aig.get_latch_signals().into_iter().enumerate() {
+        let name = match symbols.iter().find(|(x, _)| *x as usize == i) {
+            Some((_, symbol)) => symbol.to_owned(),
+            None => format!("l{i}"),
+        };
+        if let Some(s) = t.get(s) {
+            let v = fin_state.convert_signal_to_variable(s);
+            if fin_state.is_state_variable(v) {
+                names.push((name, v));
+            }
+        }
+    }
+    names
+}
+
+/// Reads lemmas where each line is a clause and each literal is a latch name, possibly negated
+/// with '!'. Clauses that refer to unknown latches are skipped.
+fn read_lemmas(text: &str, names: &[(String, Variable)]) -> Vec<Clause> {
+    let mut lemmas = vec![];
+    for line in text.lines().filter(|l| !l.trim().is_empty()) {
+        let literals: Option<Vec<Literal>> = line
+            .split_whitespace()
+            .map(|w| {
+                let (name, is_negated) = match w.strip_prefix('!') {
+                    Some(n) => (n, true),
+                    None => (w, false),
+                };
+                names
+                    .iter()
+                    .find(|(x, _)| x == name)
+                    .map(|(_, v)| v.literal(is_negated))
+            })
+            .collect();
+        if let Some(literals) = literals {
+            lemmas.push(Clause::from_sequence(literals));
+        }
+    }
+    lemmas
+}
+
+/// Writes the clauses of the invariant that only contain latches in the format of `read_lemmas`.
+fn write_lemmas(p: &Proof, names: &[(String, Variable)]) -> String {
+    let mut text = String::new();
+    for c in p.invariant.iter() {
+        let words: Option<Vec<String>> = c
+            .iter()
+            .map(|l| {
+                names
+                    .iter()
+                    .find(|(_, v)| *v == l.variable())
+                    .map(|(n, _)| format!("{}{}", if l.is_negated() { "!" } else { "" }, n))
+            })
+            .collect();
+        if let Some(words) = words {
+            text.push_str(&words.join(" "));
+            text.push('\n');
+        }
+    }
+    text
+}
+
 fn final_print(args: &Args, start_time: &std::time::Instant) {
     print_if_verbose!(
         args.verbose,
@@ -363,11 +700,71 @@ fn main() -> ExitCode {
         }
     };
 
+    let mut pipeline = match CircuitSimplificationPipeline::parse(&args.simplify) {
+        Ok(p) => p,
+        Err(e) => {
+            print_if_verbose!(
+                args.verbose,
+                "Error while parsing simplification pipeline '{}' : {}",
+                args.simplify,
+                e
+            );
+            return ExitCode::from(args.error_exit_code);
+        }
+    };
+
+    if let Some(m) = args.memory_limit {
+        pipeline.set_memory_limit_mb(m);
+    }
+
     let mut circuit = Circuit::from_aig(&aig);
-    let t = circuit.simplify_circuit_before_using_proof_engine(args.verbose.into());
+    let mut t = SignalTracker::new();
+    if let Some(i) = args.property {
+        let properties = if aig.get_bad_wires().is_empty() {
+            aig.get_output_wires()
+        } else {
+            aig.get_bad_wires()
+        };
+        if i >= properties.len() {
+            print_if_verbose!(
+                args.verbose,
+                "Property index {} is out of range, the AIG has {} properties.",
+                i,
+                properties.len()
+            );
+            return ExitCode::from(args.error_exit_code);
+        }
+        t.push(circuit.reduce_to_cone_of_property(properties[i]));
+    }
+    t.append(circuit.simplify_circuit_using_pipeline(
+        &pipeline,
+        args.simplify_iterations,
+        args.verbose.into(),
+    ));
+    if !args.simplified_aig.is_empty() {
+        let simplified = circuit.to_aig_with_symbols(&aig, &t);
+        fs::write(&args.simplified_aig, simplified.get_aig())
+            .expect("Unable to write simplified aig file.");
+    }
+    if !args.simplified_verilog.is_empty() {
+        fs::write(&args.simplified_verilog, circuit.get_verilog("simplified"))
+            .expect("Unable to write simplified verilog file.");
+    }
 
     let assume_output_is_bad = circuit.get_bad_wires().is_empty();
-    let fin_state = match FiniteStateTransitionSystem::new(&circuit, assume_output_is_bad) {
+    let encoding = match args.encoding {
+        Encoding::Tseitin => TransitionRelationEncoding::Tseitin,
+        Encoding::Pg => TransitionRelationEncoding::PlaistedGreenbaum,
+        Encoding::Lut => TransitionRelationEncoding::Lut {
+            k: args.encoding_lut_k,
+            l: args.encoding_lut_l,
+        },
+    };
+    let mut fin_state = match FiniteStateTransitionSystem::new_with_encoding(
+        &circuit,
+        assume_output_is_bad,
+        encoding,
+    ) {
         Ok(f) => f,
         Err(e) => {
             match e {
@@ -388,6 +785,11 @@ fn main() -> ExitCode {
                 }
                 FiniteStateTransitionSystemError::BadWireIsConstantOne => todo!(),
                 FiniteStateTransitionSystemError::BadWiresIncludeWireAndItsNegation => todo!(),
+                FiniteStateTransitionSystemError::UnsupportedLutSize => {
+                    // the arguments are validated when they are parsed
+                    eprintln!("Error while building the CNF of the circuit: {}", e);
+                    return ExitCode::from(args.error_exit_code);
+                }
                 FiniteStateTransitionSystemError::MaxWireTooHigh => {}
             }
             print_if_verbose!(
@@ -403,17 +805,80 @@ fn main() -> ExitCode {
         print_details_about_model(&fin_state, &start_time);
     }
 
+    if args.random_simulation_rounds > 0 {
+        let mut parameters = RandomSimulationParameters::new();
+        parameters.rounds = args.random_simulation_rounds;
+        parameters.verbose = args.verbose.into();
+        let mut engine = RandomSimulation::new(&circuit, assume_output_is_bad, parameters);
+        if let Some(c) = engine.find_counterexample() {
+            print_if_verbose!(args.verbose, "Random simulation found a counterexample.");
+            let pr: ProofResult = Err(c);
+            if args.check_result.into() {
+                let check_result = fin_state.check_proof_result::<CaDiCalSolver>(pr.clone());
+                if let Err(e) = check_result {
+                    print_if_verbose!(args.verbose, "Error while checking the proof result: {}", e);
+                    return ExitCode::from(args.error_exit_code);
+                }
+                print_if_verbose!(args.verbose, "Result checked successfully!");
+            }
+            let r = declare_sat(&args, &aig, &t, pr.as_ref().unwrap_err(), |s| {
+                fin_state.convert_signal_to_variable(s)
+            });
+            final_print(&args, &start_time);
+            return r;
+        }
+    }
+
+    if args.explicit.into() {
+        if let Ok(mut engine) = ExplicitStateReachability::new(
+            &circuit,
+            assume_output_is_bad,
+            ExplicitStateReachabilityParameters::new(),
+        ) {
+            print_if_verbose!(args.verbose, "Using explicit-state reachability.");
+            let pr = engine.prove();
+            if args.check_result.into() {
+                let check_result = fin_state.check_proof_result::<CaDiCalSolver>(pr.clone());
+                if let Err(e) = check_result {
+                    print_if_verbose!(args.verbose, "Error while checking the proof result: {}", e);
+                    return ExitCode::from(args.error_exit_code);
+                }
+                print_if_verbose!(args.verbose, "Result checked successfully!");
+            }
+            let r = match &pr {
+                ProofResult::Ok(p) => declare_un_sat(&args, &aig, &t, p, |s| {
+                    fin_state.convert_signal_to_variable(s)
+                }),
+                ProofResult::Err(e) => declare_sat(&args, &aig, &t, e, |s| {
+                    fin_state.convert_signal_to_variable(s)
+                }),
+            };
+            final_print(&args, &start_time);
+            return r;
+        }
+    }
+
     let mut parameters = PropertyDirectedReachabilityParameters::new();
 
     parameters.er = args.er.into();
     parameters.er_fp = args.er_fp.into();
     parameters.er_generalization = args.er_gen.into();
     parameters.er_impl = args.er_impl.into();
+    parameters.er_ite = args.er_ite.into();
+    parameters.er_maj3 = args.er_maj3.into();
+    parameters.er_circuit_signals = args.er_circuit_signals.into();
+    parameters.er_circuit_signals_max_cone_size = args.er_circuit_signals_max_cone_size;
     parameters.er_delta = args.ev_delta;
 
     parameters.verbose = args.verbose.into();
     parameters.timeout = Duration::from_secs(args.time_out_seconds);
+    if args.stats_json_interval > 0 {
+        parameters.json_stats_interval = Duration::from_secs(args.stats_json_interval);
+    }
     parameters.max_depth = args.max_depth;
+    if let Some(m) = args.memory_limit {
+        parameters.memory_limit_mb = m;
+    }
     parameters.should_print_time_stats_during_run = args.time_stats.into();
     parameters.seed = args.seed;
     // parameters.should_print_bva_debug_information = true;
@@ -424,6 +889,10 @@ fn main() -> ExitCode {
     parameters.generalize_using_ctg = args.ctg.into();
     parameters.generalize_using_ctg_max_ctgs = args.ctg_count;
     parameters.generalize_using_ctg_max_depth = args.ctg_depth;
+    parameters.proof_obligation_scheduling = args.po_order.into();
+    parameters.re_push_proof_obligations_beyond_depth = args.po_re_push.into();
+    parameters.minimize_proof = args.minimize_proof.into();
+    parameters.minimize_proof_shrink_literals = args.minimize_proof_shrink_literals.into();
     parameters.decay = args.decay;
 
     parameters.insert_frame_clauses_reversed = args.rev_frame_clauses.into();
@@ -432,26 +901,132 @@ fn main() -> ExitCode {
 
     let fin_state = Rc::new(RefCell::new(fin_state));
 
+    if args.direction != Direction::Forward {
+        let mut engine =
+            match BidirectionalPropertyDirectedReachability::<CaDiCalSolver, CuddBdd>::new(
+                &circuit,
+                assume_output_is_bad,
+                fin_state.to_owned(),
+                args.direction.into(),
+                parameters,
+            ) {
+                Ok(e) => e,
+                Err(e) => {
+                    print_if_verbose!(args.verbose, "Error while creating the engine: {}", e);
+                    return ExitCode::from(args.error_exit_code);
+                }
+            };
+        let pr = match engine.prove() {
+            Ok(pr) => pr,
+            Err(e) => {
+                print_if_verbose!(
+                    args.verbose,
+                    "PropertyDirectedReachability stopped: {:?}",
+                    e
+                );
+                return ExitCode::from(args.unknown_exit_code);
+            }
+        };
+        if args.check_result.into() {
+            let check_result = fin_state
+                .borrow_mut()
+                .check_proof_result::<CaDiCalSolver>(pr.clone());
+            if let Err(e) = check_result {
+                print_if_verbose!(args.verbose, "Error while checking the proof result: {}", e);
+                return ExitCode::from(args.error_exit_code);
+            }
+            print_if_verbose!(args.verbose, "Result checked successfully!");
+        }
+        let r = match &pr {
+            ProofResult::Ok(p) => declare_un_sat(&args, &aig, &t, p, |s| {
+                fin_state.borrow().convert_signal_to_variable(s)
+            }),
+            ProofResult::Err(e) => declare_sat(&args, &aig, &t, e, |s| {
+                fin_state.borrow().convert_signal_to_variable(s)
+            }),
+        };
+        final_print(&args, &start_time);
+        return r;
+    }
+
     let mut solver = PropertyDirectedReachability::<CaDiCalSolver, CuddBdd>::new(
         fin_state.to_owned(),
         parameters,
     )
     .unwrap();
+    if !args.resume.is_empty() {
+        let restored = PropertyDirectedReachabilityCheckpoint::from_path(Path::new(&args.resume))
+            .and_then(|c| solver.restore_checkpoint(&c));
+        match restored {
+            Ok(dropped) => print_if_verbose!(
+                args.verbose,
+                "Resumed from checkpoint, {} clauses were dropped.",
+                dropped
+            ),
+            Err(e) => {
+                print_if_verbose!(args.verbose, "Error while resuming from checkpoint: {}", e);
+                return ExitCode::from(args.error_exit_code);
+            }
+        }
+    }
+    if !args.checkpoint.is_empty() && args.checkpoint_interval > 0 {
+        solver.set_periodic_checkpoint(
+            Path::new(&args.checkpoint),
+            Duration::from_secs(args.checkpoint_interval),
+        );
+    }
+    if !args.lemmas_in.is_empty() {
+        let names = get_latch_names(&aig, &t, &fin_state.borrow());
+        let seeded = fs::read_to_string(&args.lemmas_in)
+            .map_err(|e| e.to_string())
+            .map(|text| read_lemmas(&text, &names))
+            .and_then(|lemmas| solver.seed_lemmas(lemmas).map_err(|e| format!("{:?}", e)));
+        match seeded {
+            Ok((in_f_inf, in_frames)) => print_if_verbose!(
+                args.verbose,
+                "Seeded {} lemmas into F_inf and {} lemmas into other frames.",
+                in_f_inf,
+                in_frames
+            ),
+            Err(e) => {
+                print_if_verbose!(args.verbose, "Error while seeding lemmas: {}", e);
+                return ExitCode::from(args.error_exit_code);
+            }
+        }
+    }
     let pr = solver.prove();
+    if !args.stats_json.is_empty() {
+        fs::write(&args.stats_json, solver.get_run_stats().to_json())
+            .expect("Unable to write stats file.");
+    }
     let pr = match &pr {
         Ok(o) => o,
         Err(e) => {
+            if !args.checkpoint.is_empty() {
+                if let Err(e) = solver.save_checkpoint(Path::new(&args.checkpoint)) {
+                    print_if_verbose!(args.verbose, "Error while saving checkpoint: {}", e);
+                }
+            }
             match e {
-                PropertyDirectedReachabilityProofError::MaxDepthReached => {
+                PropertyDirectedReachabilityProofError::MaxDepthReached(partial) => {
                     print_if_verbose!(
                         args.verbose,
-                        "Max depth reached in PropertyDirectedReachability."
+                        "Max depth reached in PropertyDirectedReachability, {}.",
+                        partial
                     )
                 }
-                PropertyDirectedReachabilityProofError::TimeOutReached => {
+                PropertyDirectedReachabilityProofError::TimeOutReached(partial) => {
                     print_if_verbose!(
                         args.verbose,
-                        "Time out reached in PropertyDirectedReachability."
+                        "Time out reached in PropertyDirectedReachability, {}.",
+                        partial
+                    )
+                }
+                PropertyDirectedReachabilityProofError::MemoryLimitReached(partial) => {
+                    print_if_verbose!(
+                        args.verbose,
+                        "Memory limit reached in PropertyDirectedReachability, {}.",
+                        partial
                     )
                 }
             }
@@ -471,6 +1046,11 @@ fn main() -> ExitCode {
         print_if_verbose!(args.verbose, "Result checked successfully!");
     }
 
+    if let (ProofResult::Ok(p), false) = (pr, args.lemmas_out.is_empty()) {
+        let names = get_latch_names(&aig, &t, &fin_state.borrow());
+        fs::write(&args.lemmas_out, write_lemmas(p, &names)).expect("Unable to write lemmas file.");
+    }
+
     // print result
     let r = match &pr {
         ProofResult::Ok(p) => declare_un_sat(&args, &aig, &t, p, |s| {
@@ -0,0 +1,52 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use super::{
+    ExplicitStateReachability, ExplicitStateReachabilityError, ExplicitStateReachabilityParameters,
+};
+use crate::models::{BitParallelSimulator, Circuit};
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl ExplicitStateReachability {
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Creates the engine for a circuit, the property is read from the circuit the same way
+    /// `FiniteStateTransitionSystem::new` reads it, and the results use the same variables as
+    /// the transition system (the variable of a signal has the number of the signal).
+    pub fn new(
+        circuit: &Circuit,
+        assume_output_is_bad: bool,
+        parameters: ExplicitStateReachabilityParameters,
+    ) -> Result<Self, ExplicitStateReachabilityError> {
+        let latches = circuit.get_latch_signals().len();
+        if latches > parameters.max_latches.min(32) {
+            return Err(ExplicitStateReachabilityError::TooManyLatches(latches));
+        }
+        let inputs = circuit.get_input_signals().len();
+        if inputs > parameters.max_inputs.min(32) {
+            return Err(ExplicitStateReachabilityError::TooManyInputs(inputs));
+        }
+
+        let mut properties: Vec<_> = circuit.get_bad_wires().iter().copied().collect();
+        if assume_output_is_bad {
+            properties.extend(circuit.get_output_wires().iter().copied());
+        }
+
+        Ok(Self {
+            simulator: BitParallelSimulator::new(circuit),
+            properties,
+            constraints: circuit
+                .get_invariant_constraint_wires()
+                .iter()
+                .copied()
+                .collect(),
+            parameters,
+        })
+    }
+}
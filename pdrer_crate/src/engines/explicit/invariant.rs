@@ -0,0 +1,84 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use super::ExplicitStateReachability;
+use crate::formulas::{Clause, Literal, Variable, CNF};
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl ExplicitStateReachability {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    /// The bits of the states `[start, start + length)`, where `length` is smaller than 64 and
+    /// `start` is a multiple of it.
+    fn get_short_range(visited: &[u64], start: usize, length: usize) -> u64 {
+        (visited[start / 64] >> (start % 64)) & ((1 << length) - 1)
+    }
+
+    fn count_visited_in_range(visited: &[u64], start: usize, length: usize) -> usize {
+        if length < 64 {
+            Self::get_short_range(visited, start, length).count_ones() as usize
+        } else {
+            visited[start / 64..(start + length) / 64]
+                .iter()
+                .map(|w| w.count_ones() as usize)
+                .sum()
+        }
+    }
+
+    fn are_ranges_equal(visited: &[u64], a: usize, b: usize, length: usize) -> bool {
+        if length < 64 {
+            Self::get_short_range(visited, a, length) == Self::get_short_range(visited, b, length)
+        } else {
+            visited[a / 64..(a + length) / 64] == visited[b / 64..(b + length) / 64]
+        }
+    }
+
+    /// Adds clauses that block the unvisited states among `[start, start + 2^n)`, these states
+    /// agree on the latches above `n` and the literals of those latches are in `path`.
+    /// Latches that the visited set does not depend on are skipped, so the clauses are the paths
+    /// to the empty leaves of a reduced decision tree.
+    fn add_clauses_blocking_unvisited_states(
+        &self,
+        visited: &[u64],
+        start: usize,
+        n: usize,
+        path: &mut Vec<Literal>,
+        clauses: &mut Vec<Clause>,
+    ) {
+        let length = 1 << n;
+        let count = Self::count_visited_in_range(visited, start, length);
+        if count == length {
+            return;
+        }
+        if count == 0 {
+            clauses.push(Clause::from_sequence(path.iter().map(|l| !*l).collect()));
+            return;
+        }
+
+        let half = length / 2;
+        if Self::are_ranges_equal(visited, start, start + half, half) {
+            self.add_clauses_blocking_unvisited_states(visited, start, n - 1, path, clauses);
+            return;
+        }
+        let latch = Variable::new(self.simulator.get_latch_signals()[n - 1].number());
+        for (s, is_negated) in [(start, true), (start + half, false)] {
+            path.push(latch.literal(is_negated));
+            self.add_clauses_blocking_unvisited_states(visited, s, n - 1, path, clauses);
+            path.pop();
+        }
+    }
+
+    /// The CNF over the latches that is satisfied exactly by the visited states.
+    pub(super) fn get_invariant(&self, visited: &[u64]) -> CNF {
+        let n = self.simulator.get_latch_signals().len();
+        let mut clauses = vec![];
+        self.add_clauses_blocking_unvisited_states(visited, 0, n, &mut vec![], &mut clauses);
+        CNF::from_sequence(clauses)
+    }
+}
@@ -0,0 +1,95 @@
+//! Explicit-state reachability for small designs.
+//!
+//! The reachable states are enumerated in breadth first order, 64 pairs of a state and an input
+//! are simulated at once using the bit-parallel simulator. The results are exact: a proof holds
+//! an invariant that describes exactly the reachable states, and a counterexample is as short as
+//! possible.
+
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::fmt;
+
+use crate::models::{BitParallelSimulator, Wire};
+
+// ************************************************************************************************
+// struct
+// ************************************************************************************************
+
+#[derive(Debug, Clone, Copy)]
+pub struct ExplicitStateReachabilityParameters {
+    /// Designs with more latches than this are rejected, the set of visited states takes
+    /// `2^latches` bits.
+    pub max_latches: usize,
+    /// Designs with more inputs than this are rejected, every reachable state is simulated with
+    /// all `2^inputs` input values.
+    pub max_inputs: usize,
+    /// If true, then the size of every layer of the search is printed.
+    pub verbose: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExplicitStateReachabilityError {
+    TooManyLatches(usize),
+    TooManyInputs(usize),
+}
+
+pub struct ExplicitStateReachability {
+    simulator: BitParallelSimulator,
+    /// The wires whose disjunction is the bad event.
+    properties: Vec<Wire>,
+    constraints: Vec<Wire>,
+    parameters: ExplicitStateReachabilityParameters,
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl ExplicitStateReachabilityParameters {
+    pub const DEFAULT: Self = Self {
+        max_latches: 25,
+        max_inputs: 12,
+        verbose: false,
+    };
+
+    pub fn new() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Default for ExplicitStateReachabilityParameters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ************************************************************************************************
+// printing
+// ************************************************************************************************
+
+impl fmt::Display for ExplicitStateReachabilityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyLatches(n) => write!(
+                f,
+                "The design has {} latches, which is too many for explicit-state reachability",
+                n
+            ),
+            Self::TooManyInputs(n) => write!(
+                f,
+                "The design has {} inputs, which is too many for explicit-state reachability",
+                n
+            ),
+        }
+    }
+}
+
+// ************************************************************************************************
+// rust submodule declaration, they get searched in their respective file  names
+// ************************************************************************************************
+
+pub mod construction;
+pub mod invariant;
+pub mod prove;
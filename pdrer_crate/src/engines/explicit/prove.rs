@@ -0,0 +1,273 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use fxhash::FxHashMap;
+
+use super::ExplicitStateReachability;
+use crate::formulas::{Cube, Variable};
+use crate::models::bit_parallel_simulator::SimulationWord;
+use crate::models::finite_state_transition_system::ProofResult;
+use crate::models::{Counterexample, Proof, Signal, TernaryValue};
+
+// ************************************************************************************************
+// types
+// ************************************************************************************************
+
+/// A state is the number whose `j`-th bit is the value of the `j`-th latch, an input is the
+/// number whose `j`-th bit is the value of the `j`-th input.
+type State = usize;
+type Input = usize;
+
+const LANES: usize = SimulationWord::BITS as usize;
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl ExplicitStateReachability {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn is_visited(visited: &[u64], state: State) -> bool {
+        (visited[state / 64] >> (state % 64)) & 1 == 1
+    }
+
+    fn mark_visited(visited: &mut [u64], state: State) {
+        visited[state / 64] |= 1 << (state % 64);
+    }
+
+    fn get_initial_states(&self) -> Vec<State> {
+        let mut fixed = 0;
+        let mut free = vec![];
+        for (j, v) in self.simulator.get_latch_initial_values().iter().enumerate() {
+            match v {
+                TernaryValue::True => fixed |= 1 << j,
+                TernaryValue::False => {}
+                TernaryValue::X => free.push(j),
+            }
+        }
+        (0..(1_usize << free.len()))
+            .map(|m| {
+                let mut s = fixed;
+                for (k, j) in free.iter().enumerate() {
+                    s |= ((m >> k) & 1) << j;
+                }
+                s
+            })
+            .collect()
+    }
+
+    fn get_cube(signals: &[Signal], value: usize) -> Cube {
+        Cube::from_sequence(
+            signals
+                .iter()
+                .enumerate()
+                .map(|(j, s)| Variable::new(s.number()).literal((value >> j) & 1 == 0))
+                .collect(),
+        )
+    }
+
+    /// Simulates a batch of at most 64 pairs, the pair at index `i` is simulated in bit `i`.
+    /// Returns the pairs that satisfy the constraints, and the pairs among them that are bad.
+    fn simulate_batch(&mut self, batch: &[(State, Input)]) -> (SimulationWord, SimulationWord) {
+        for j in 0..self.simulator.get_latch_signals().len() {
+            let word = batch.iter().enumerate().fold(0, |w, (lane, (s, _))| {
+                w | ((((s >> j) & 1) as SimulationWord) << lane)
+            });
+            let signal = self.simulator.get_latch_signals()[j];
+            self.simulator.set_value(signal, word);
+        }
+        for j in 0..self.simulator.get_input_signals().len() {
+            let word = batch.iter().enumerate().fold(0, |w, (lane, (_, i))| {
+                w | ((((i >> j) & 1) as SimulationWord) << lane)
+            });
+            let signal = self.simulator.get_input_signals()[j];
+            self.simulator.set_value(signal, word);
+        }
+        self.simulator.simulate();
+
+        let active = if batch.len() == LANES {
+            SimulationWord::MAX
+        } else {
+            (1 << batch.len()) - 1
+        };
+        let allowed = self
+            .constraints
+            .iter()
+            .fold(active, |r, w| r & self.simulator.get_wire_value(*w));
+        let bad = self
+            .properties
+            .iter()
+            .fold(0, |r, w| r | self.simulator.get_wire_value(*w));
+        (allowed, allowed & bad)
+    }
+
+    /// Simulates the batch and adds the successors that were not visited yet to the next layer.
+    /// Returns a bad pair if there is one, otherwise if some pair satisfies the constraints.
+    fn process_batch(
+        &mut self,
+        batch: &[(State, Input)],
+        visited: &mut [u64],
+        parents: &mut FxHashMap<State, (State, Input)>,
+        next_layer: &mut Vec<State>,
+    ) -> Result<bool, (State, Input)> {
+        let (allowed, bad) = self.simulate_batch(batch);
+        if bad != 0 {
+            return Err(batch[bad.trailing_zeros() as usize]);
+        }
+
+        let next = self.simulator.get_next_latch_values();
+        for (lane, pair) in batch.iter().enumerate() {
+            if (allowed >> lane) & 1 == 0 {
+                continue;
+            }
+            let successor = next
+                .iter()
+                .enumerate()
+                .fold(0, |s, (j, w)| s | ((((w >> lane) & 1) as State) << j));
+            if !Self::is_visited(visited, successor) {
+                Self::mark_visited(visited, successor);
+                parents.insert(successor, *pair);
+                next_layer.push(successor);
+            }
+        }
+        Ok(allowed != 0)
+    }
+
+    fn get_counterexample(
+        &self,
+        parents: &FxHashMap<State, (State, Input)>,
+        (state, input): (State, Input),
+    ) -> Counterexample {
+        let mut path = vec![(state, input)];
+        while let Some(pair) = parents.get(&path.last().unwrap().0) {
+            path.push(*pair);
+        }
+        path.reverse();
+
+        let latches = self.simulator.get_latch_signals();
+        let inputs = self.simulator.get_input_signals();
+        Counterexample {
+            initial_cube: Self::get_cube(latches, path[0].0),
+            inputs: path
+                .iter()
+                .map(|(_, i)| Self::get_cube(inputs, *i))
+                .collect(),
+        }
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Enumerates the reachable states in breadth first order. Returns a shortest counterexample
+    /// if the property can be violated, and otherwise a proof whose invariant holds in exactly
+    /// the reachable states.
+    pub fn prove(&mut self) -> ProofResult {
+        let number_of_latches = self.simulator.get_latch_signals().len();
+        let number_of_input_values: Input = 1 << self.simulator.get_input_signals().len();
+        let mut visited = vec![0_u64; ((1_usize << number_of_latches) / 64).max(1)];
+        let mut parents = FxHashMap::default();
+        let mut some_initial_state_satisfies_constraints = false;
+
+        let mut layer = self.get_initial_states();
+        for s in layer.iter() {
+            Self::mark_visited(&mut visited, *s);
+        }
+
+        let mut depth = 0;
+        while !layer.is_empty() {
+            if self.parameters.verbose {
+                println!("Depth = {}, states = {}", depth, layer.len());
+            }
+            let mut next_layer = vec![];
+            let mut batch = Vec::with_capacity(LANES);
+            let pairs = layer
+                .iter()
+                .flat_map(|s| (0..number_of_input_values).map(move |i| (*s, i)));
+            for pair in pairs {
+                batch.push(pair);
+                if batch.len() < LANES {
+                    continue;
+                }
+                match self.process_batch(&batch, &mut visited, &mut parents, &mut next_layer) {
+                    Ok(allowed) => some_initial_state_satisfies_constraints |= allowed,
+                    Err(bad) => return Err(self.get_counterexample(&parents, bad)),
+                }
+                batch.clear();
+            }
+            if !batch.is_empty() {
+                match self.process_batch(&batch, &mut visited, &mut parents, &mut next_layer) {
+                    Ok(allowed) => some_initial_state_satisfies_constraints |= allowed,
+                    Err(bad) => return Err(self.get_counterexample(&parents, bad)),
+                }
+            }
+
+            if depth == 0 && !some_initial_state_satisfies_constraints {
+                break;
+            }
+            layer = next_layer;
+            depth += 1;
+        }
+
+        Ok(Proof {
+            all_initial_states_violate_constraints: !some_initial_state_satisfies_constraints,
+            invariant: self.get_invariant(&visited),
+            definitions: vec![],
+        })
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::engines::fuzzing::AigBoundedModelChecker;
+    use crate::engines::ExplicitStateReachabilityParameters;
+    use crate::models::{AndInverterGraph, Circuit, FiniteStateTransitionSystem};
+    use crate::solvers::sat::incremental::CaDiCalSolver;
+
+    #[test]
+    fn test_results_are_exact_and_valid() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for i in 0..200 {
+            let aig = if i % 2 == 0 {
+                AndInverterGraph::uniform_random(&mut rng, 3, 6, 30, 0, 1, i % 3)
+            } else {
+                AndInverterGraph::geometric_random(&mut rng, 2, 8, 40, 0, 2, i % 3)
+            };
+            let circuit = Circuit::from_aig(&aig);
+            let mut engine = ExplicitStateReachability::new(
+                &circuit,
+                false,
+                ExplicitStateReachabilityParameters::new(),
+            )
+            .unwrap();
+            let result = engine.prove();
+
+            // the counterexample is as short as possible, and no counterexample is missed
+            let mut bmc = AigBoundedModelChecker::new(&aig);
+            match &result {
+                Ok(_) => assert_eq!(bmc.find_shortest_counterexample(30), None),
+                Err(c) => {
+                    let depth = c.inputs.len() - 1;
+                    assert_eq!(bmc.find_shortest_counterexample(depth), Some(depth));
+                }
+            }
+
+            if let Ok(mut fin_state) = FiniteStateTransitionSystem::new(&circuit, false) {
+                fin_state
+                    .check_proof_result::<CaDiCalSolver>(result)
+                    .unwrap();
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use crate::formulas::{Literal, Variable};
+use crate::models::and_inverter_graph::AndInverterGraph;
+use crate::models::{TernaryValue, Wire};
+use crate::solvers::sat::incremental::{CaDiCalSolver, SatResult};
+
+// ************************************************************************************************
+// struct
+// ************************************************************************************************
+
+/// Bounded model checker that unrolls the AIG directly into a SAT solver.
+///
+/// This intentionally shares nothing with the circuit, the transition system or PDR, so that it
+/// can serve as an oracle for them. Signal `s` in cycle `k` is represented by the variable
+/// `k * (M + 1) + s + 1` where `M` is the maximal signal of the AIG.
+pub struct AigBoundedModelChecker {
+    solver: CaDiCalSolver,
+    number_of_signals: u32,
+    latches: Vec<(Wire, TernaryValue)>,
+    and_gates: Vec<(Wire, Wire)>,
+    first_and_gate: u32,
+    first_latch: u32,
+    properties: Vec<Wire>,
+    constraints: Vec<Wire>,
+    /// The number of cycles that were added to the solver.
+    unrolled: usize,
+    /// The depths that were checked so far, and the shortest counterexample if one was found.
+    checked_depth: Option<usize>,
+    shortest_counterexample: Option<usize>,
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl AigBoundedModelChecker {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn literal(&self, wire: Wire, cycle: usize) -> Literal {
+        let number = cycle as u32 * self.number_of_signals + wire.signal().number() + 1;
+        Variable::new(number).literal(wire.is_negated())
+    }
+
+    fn unroll_one_cycle(&mut self) {
+        let k = self.unrolled;
+        let mut clauses = vec![vec![!self.literal(Wire::CONSTANT_ZERO, k)]];
+
+        for (i, (input, initial)) in self.latches.iter().enumerate() {
+            let latch = self.literal(Wire::new((self.first_latch + i as u32) << 1), k);
+            if k == 0 {
+                match initial {
+                    TernaryValue::True => clauses.push(vec![latch]),
+                    TernaryValue::False => clauses.push(vec![!latch]),
+                    TernaryValue::X => {}
+                }
+            } else {
+                let previous = self.literal(*input, k - 1);
+                clauses.push(vec![!latch, previous]);
+                clauses.push(vec![latch, !previous]);
+            }
+        }
+
+        for (i, (in0, in1)) in self.and_gates.iter().enumerate() {
+            let out = self.literal(Wire::new((self.first_and_gate + i as u32) << 1), k);
+            let a = self.literal(*in0, k);
+            let b = self.literal(*in1, k);
+            clauses.push(vec![!out, a]);
+            clauses.push(vec![!out, b]);
+            clauses.push(vec![out, !a, !b]);
+        }
+
+        for c in self.constraints.iter() {
+            clauses.push(vec![self.literal(*c, k)]);
+        }
+
+        for clause in clauses {
+            self.solver.add_clause(clause);
+        }
+        self.unrolled += 1;
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Creates the checker, the properties are the bad wires of the AIG, or its outputs if it
+    /// has no bad wires.
+    pub fn new(aig: &AndInverterGraph) -> Self {
+        let latches: Vec<(Wire, TernaryValue)> = aig
+            .get_latch_information()
+            .into_iter()
+            .map(|l| (l.input, l.initial))
+            .collect();
+        let and_gates: Vec<(Wire, Wire)> = aig
+            .get_all_and_gates()
+            .into_iter()
+            .map(|g| (g.in0, g.in1))
+            .collect();
+        let first_latch = aig.get_input_signals().len() as u32 + 1;
+        let first_and_gate = first_latch + latches.len() as u32;
+        let properties = if aig.get_bad_wires().is_empty() {
+            aig.get_output_wires()
+        } else {
+            aig.get_bad_wires()
+        };
+
+        Self {
+            solver: CaDiCalSolver::new(0),
+            number_of_signals: first_and_gate + and_gates.len() as u32,
+            latches,
+            and_gates,
+            first_and_gate,
+            first_latch,
+            properties,
+            constraints: aig.get_constraints_wires(),
+            unrolled: 0,
+            checked_depth: None,
+            shortest_counterexample: None,
+        }
+    }
+
+    /// Returns the depth of the shortest counterexample whose depth is at most `depth`, the depth
+    /// of a counterexample is the number of transitions it takes.
+    /// Depths that were already checked in previous calls are not checked again.
+    pub fn find_shortest_counterexample(&mut self, depth: usize) -> Option<usize> {
+        if let Some(d) = self.shortest_counterexample {
+            return if d <= depth { Some(d) } else { None };
+        }
+        let start = self.checked_depth.map_or(0, |d| d + 1);
+        for k in start..=depth {
+            while self.unrolled <= k {
+                self.unroll_one_cycle();
+            }
+            let bad: Vec<Literal> = self
+                .properties
+                .iter()
+                .map(|p| self.literal(*p, k))
+                .collect();
+            self.checked_depth = Some(k);
+            if bad.is_empty() {
+                continue;
+            }
+            if self.solver.solve([], bad) == SatResult::Sat {
+                self.shortest_counterexample = Some(k);
+                return Some(k);
+            }
+        }
+        None
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Signal;
+
+    #[test]
+    fn test_shift_register_depth() {
+        // three latches that start at 0 and shift in a constant 1, the last one is bad
+        let aig = AndInverterGraph::new(
+            Signal::new(4),
+            1,
+            &[
+                (Wire::CONSTANT_ONE, TernaryValue::False),
+                (Signal::new(2).wire(false), TernaryValue::False),
+                (Signal::new(3).wire(false), TernaryValue::False),
+            ],
+            vec![],
+            vec![Signal::new(4).wire(false)],
+            vec![],
+            &[],
+            String::new(),
+        )
+        .unwrap();
+        let mut oracle = AigBoundedModelChecker::new(&aig);
+        assert_eq!(oracle.find_shortest_counterexample(2), None);
+        assert_eq!(oracle.find_shortest_counterexample(10), Some(3));
+        assert_eq!(oracle.find_shortest_counterexample(1), None);
+
+        // constraining the input to be 0 does not matter, constraining the first latch does
+        let constrained = AndInverterGraph::new(
+            Signal::new(4),
+            1,
+            &[
+                (Wire::CONSTANT_ONE, TernaryValue::False),
+                (Signal::new(2).wire(false), TernaryValue::False),
+                (Signal::new(3).wire(false), TernaryValue::False),
+            ],
+            vec![],
+            vec![Signal::new(4).wire(false)],
+            vec![Signal::new(1).wire(true), Signal::new(2).wire(true)],
+            &[],
+            String::new(),
+        )
+        .unwrap();
+        let mut oracle = AigBoundedModelChecker::new(&constrained);
+        assert_eq!(oracle.find_shortest_counterexample(10), None);
+    }
+}
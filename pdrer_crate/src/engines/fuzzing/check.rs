@@ -0,0 +1,244 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Duration;
+use std::{cell::RefCell, rc::Rc};
+
+use super::{Fuzzer, FuzzingConfiguration, FuzzingFailureKind, FuzzingOracle};
+use crate::engines::pdr::PropertyDirectedReachabilityProofError;
+use crate::engines::{PropertyDirectedReachability, PropertyDirectedReachabilityParameters};
+use crate::models::circuit::simplifiers::CircuitSimplificationPipeline;
+use crate::models::finite_state_transition_system::{
+    FiniteStateTransitionSystemError, ProofResult,
+};
+use crate::models::{AndInverterGraph, Circuit, FiniteStateTransitionSystem};
+use crate::solvers::{dd::OxiddBdd, sat::incremental::CaDiCalSolver};
+
+// ************************************************************************************************
+// enum
+// ************************************************************************************************
+
+/// What the pipeline concluded about a model, after its result was checked.
+enum PipelineOutcome {
+    Safe,
+    /// A counterexample of this depth exists.
+    Unsafe(usize),
+    /// The pipeline stopped early, possibly knowing that there is no counterexample of at most
+    /// this depth.
+    Unknown(Option<usize>),
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl FuzzingConfiguration {
+    /// The parameter sets that are used by default.
+    pub const ALL: [Self; 5] = [
+        Self {
+            name: "plain",
+            er: false,
+            generalize_using_ctg: false,
+            use_infinite_frame: false,
+        },
+        Self {
+            name: "f_inf",
+            er: false,
+            generalize_using_ctg: false,
+            use_infinite_frame: true,
+        },
+        Self {
+            name: "ctg",
+            er: false,
+            generalize_using_ctg: true,
+            use_infinite_frame: true,
+        },
+        Self {
+            name: "er",
+            er: true,
+            generalize_using_ctg: false,
+            use_infinite_frame: true,
+        },
+        Self {
+            name: "er_ctg",
+            er: true,
+            generalize_using_ctg: true,
+            use_infinite_frame: true,
+        },
+    ];
+
+    pub fn get_parameters(
+        &self,
+        seed: u64,
+        timeout: Duration,
+    ) -> PropertyDirectedReachabilityParameters {
+        let mut parameters = PropertyDirectedReachabilityParameters::new();
+        parameters.seed = seed;
+        parameters.timeout = timeout;
+        parameters.er = self.er;
+        parameters.generalize_using_ctg = self.generalize_using_ctg;
+        parameters.use_infinite_frame = self.use_infinite_frame;
+        parameters
+    }
+}
+
+impl Fuzzer {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn get_panic_message(payload: Box<dyn Any + Send>) -> String {
+        if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.to_owned()
+        } else {
+            "unknown panic payload".to_string()
+        }
+    }
+
+    /// Runs the pipeline the same way the hwmcc example does, and checks the result it returns.
+    fn prove(
+        &self,
+        aig: &AndInverterGraph,
+        configuration: &FuzzingConfiguration,
+        seed: u64,
+    ) -> Result<PipelineOutcome, String> {
+        let mut circuit = Circuit::from_aig(aig);
+        if self.parameters.simplify {
+            let pipeline =
+                CircuitSimplificationPipeline::parse(CircuitSimplificationPipeline::DEFAULT)
+                    .unwrap();
+            circuit.simplify_circuit_using_pipeline(&pipeline, 1, false);
+        }
+
+        let assume_output_is_bad = circuit.get_bad_wires().is_empty();
+        let fin_state = match FiniteStateTransitionSystem::new(&circuit, assume_output_is_bad) {
+            Ok(f) => f,
+            Err(
+                FiniteStateTransitionSystemError::EmptyCircuit
+                | FiniteStateTransitionSystemError::ConstraintWireIsConstantZero
+                | FiniteStateTransitionSystemError::ConstraintWiresIncludeWireAndItsNegation,
+            ) => return Ok(PipelineOutcome::Safe),
+            // the property is violated in the first cycle, as long as the constraints allow it
+            // which is left for the oracle to confirm.
+            Err(
+                FiniteStateTransitionSystemError::BadWireIsConstantOne
+                | FiniteStateTransitionSystemError::BadWiresIncludeWireAndItsNegation,
+            ) => return Ok(PipelineOutcome::Unsafe(0)),
+            Err(_) => return Ok(PipelineOutcome::Unknown(None)),
+        };
+        let fin_state = Rc::new(RefCell::new(fin_state));
+
+        let parameters = configuration.get_parameters(seed, self.parameters.timeout);
+        let mut pdr = PropertyDirectedReachability::<CaDiCalSolver, OxiddBdd>::new(
+            fin_state.to_owned(),
+            parameters,
+        )
+        .map_err(|e| format!("{:?}", e))?;
+        let result: ProofResult = match pdr.prove() {
+            Ok(r) => r,
+            Err(
+                PropertyDirectedReachabilityProofError::MaxDepthReached(partial)
+                | PropertyDirectedReachabilityProofError::TimeOutReached(partial)
+                | PropertyDirectedReachabilityProofError::MemoryLimitReached(partial),
+            ) => return Ok(PipelineOutcome::Unknown(partial.get_bounded_safety())),
+        };
+
+        let outcome = match &result {
+            Ok(_) => PipelineOutcome::Safe,
+            Err(counterexample) => PipelineOutcome::Unsafe(counterexample.inputs.len() - 1),
+        };
+        fin_state
+            .borrow_mut()
+            .check_proof_result::<CaDiCalSolver>(result)?;
+        Ok(outcome)
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Proves the model using a single configuration and compares the result to the oracle.
+    /// Running out of time is not considered a failure.
+    pub fn check_aig_with_configuration(
+        &self,
+        aig: &AndInverterGraph,
+        configuration: &FuzzingConfiguration,
+        seed: u64,
+        oracle: &mut FuzzingOracle,
+    ) -> Result<(), FuzzingFailureKind> {
+        let outcome =
+            panic::catch_unwind(AssertUnwindSafe(|| self.prove(aig, configuration, seed)))
+                .map_err(|payload| FuzzingFailureKind::Panic(Self::get_panic_message(payload)))?
+                .map_err(FuzzingFailureKind::InvalidResult)?;
+
+        let bound = if oracle.is_exact() {
+            usize::MAX
+        } else {
+            self.parameters.bmc_depth
+        };
+        match outcome {
+            PipelineOutcome::Safe => match oracle.find_shortest_counterexample(bound) {
+                Some(depth) => Err(FuzzingFailureKind::MissedCounterexample(depth)),
+                None => Ok(()),
+            },
+            PipelineOutcome::Unsafe(depth) => match oracle.find_shortest_counterexample(depth) {
+                Some(_) => Ok(()),
+                None => Err(FuzzingFailureKind::SpuriousCounterexample(depth)),
+            },
+            PipelineOutcome::Unknown(Some(depth)) => {
+                match oracle.find_shortest_counterexample(depth.min(bound)) {
+                    Some(_) => Err(FuzzingFailureKind::WrongBoundedSafety(depth)),
+                    None => Ok(()),
+                }
+            }
+            PipelineOutcome::Unknown(None) => Ok(()),
+        }
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engines::fuzzing::FuzzingParameters;
+    use crate::models::{Signal, TernaryValue, Wire};
+
+    #[test]
+    fn test_constant_bad_wire_is_compared_to_the_oracle() {
+        // 1 input and a latch that starts at 0 and takes the input, the bad wire is constant
+        let aig = |constraints: Vec<Wire>| {
+            AndInverterGraph::new(
+                Signal::new(2),
+                1,
+                &[(Signal::new(1).wire(false), TernaryValue::False)],
+                vec![],
+                vec![Wire::CONSTANT_ONE],
+                constraints,
+                &[],
+                String::new(),
+            )
+            .unwrap()
+        };
+        let fuzzer = Fuzzer::new(FuzzingParameters::new());
+        let configuration = FuzzingConfiguration::ALL[0];
+        let check = |aig: &AndInverterGraph| {
+            let mut oracle = FuzzingOracle::new(aig);
+            fuzzer.check_aig_with_configuration(aig, &configuration, 0, &mut oracle)
+        };
+
+        assert_eq!(check(&aig(vec![])), Ok(()));
+        // constraining the latch to be 1 removes the counterexample of depth 0
+        assert_eq!(
+            check(&aig(vec![Signal::new(2).wire(false)])),
+            Err(FuzzingFailureKind::SpuriousCounterexample(0))
+        );
+    }
+}
@@ -0,0 +1,218 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::mem;
+
+use super::{Fuzzer, FuzzingConfiguration, FuzzingFailureKind, FuzzingOracle};
+use crate::models::{AndInverterGraph, Signal, TernaryValue, Wire};
+
+// ************************************************************************************************
+// struct
+// ************************************************************************************************
+
+/// The parts of an AIG in a form that is easy to shrink, signals are numbered as in the AIGER
+/// format: the constant, then the inputs, then the latches and then the and gates.
+#[derive(Clone)]
+struct ReducibleAig {
+    number_of_inputs: u32,
+    latches: Vec<(Wire, TernaryValue)>,
+    and_gates: Vec<(Wire, Wire)>,
+    outputs: Vec<Wire>,
+    bad: Vec<Wire>,
+    constraints: Vec<Wire>,
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl ReducibleAig {
+    fn from_aig(aig: &AndInverterGraph) -> Self {
+        Self {
+            number_of_inputs: aig.get_input_signals().len() as u32,
+            latches: aig
+                .get_latch_information()
+                .into_iter()
+                .map(|l| (l.input, l.initial))
+                .collect(),
+            and_gates: aig
+                .get_all_and_gates()
+                .into_iter()
+                .map(|g| (g.in0, g.in1))
+                .collect(),
+            outputs: aig.get_output_wires(),
+            bad: aig.get_bad_wires(),
+            constraints: aig.get_constraints_wires(),
+        }
+    }
+
+    fn to_aig(&self) -> AndInverterGraph {
+        let max_signal = Signal::new(
+            self.number_of_inputs + self.latches.len() as u32 + self.and_gates.len() as u32,
+        );
+        AndInverterGraph::new(
+            max_signal,
+            self.number_of_inputs,
+            &self.latches,
+            self.outputs.to_owned(),
+            self.bad.to_owned(),
+            self.constraints.to_owned(),
+            &self.and_gates,
+            String::new(),
+        )
+        .unwrap()
+    }
+
+    fn map_wires<F: Fn(Wire) -> Wire>(&mut self, f: F) {
+        for (input, _) in self.latches.iter_mut() {
+            *input = f(*input);
+        }
+        for (in0, in1) in self.and_gates.iter_mut() {
+            *in0 = f(*in0);
+            *in1 = f(*in1);
+        }
+        for w in self
+            .outputs
+            .iter_mut()
+            .chain(self.bad.iter_mut())
+            .chain(self.constraints.iter_mut())
+        {
+            *w = f(*w);
+        }
+    }
+
+    /// Removes a signal, every use of it is replaced by `replacement` which must be a smaller
+    /// signal, and the signals above it are renumbered.
+    fn remove_signal(&self, signal: u32, replacement: Wire) -> Self {
+        debug_assert!(replacement.signal().number() < signal);
+        let mut r = self.clone();
+        let first_latch = self.number_of_inputs + 1;
+        let first_and_gate = first_latch + self.latches.len() as u32;
+        if signal < first_latch {
+            r.number_of_inputs -= 1;
+        } else if signal < first_and_gate {
+            r.latches.remove((signal - first_latch) as usize);
+        } else {
+            r.and_gates.remove((signal - first_and_gate) as usize);
+        }
+        r.map_wires(|w| {
+            let n = w.signal().number();
+            if n == signal {
+                if w.is_negated() {
+                    !replacement
+                } else {
+                    replacement
+                }
+            } else if n > signal {
+                Signal::new(n - 1).wire(w.is_negated())
+            } else {
+                w
+            }
+        });
+        r
+    }
+
+    /// All the models that are one step smaller than this one.
+    fn get_smaller_candidates(&self) -> Vec<Self> {
+        let mut candidates = vec![];
+
+        // properties and constraints
+        // the outputs are the properties only when there are no bad wires
+        if !self.bad.is_empty() || self.outputs.len() > 1 {
+            for i in 0..self.outputs.len() {
+                let mut r = self.clone();
+                r.outputs.remove(i);
+                candidates.push(r);
+            }
+        }
+        if self.bad.len() > 1 {
+            for i in 0..self.bad.len() {
+                let mut r = self.clone();
+                r.bad.remove(i);
+                candidates.push(r);
+            }
+        }
+        for i in 0..self.constraints.len() {
+            let mut r = self.clone();
+            r.constraints.remove(i);
+            candidates.push(r);
+        }
+
+        // and gates, from the output side
+        let first_latch = self.number_of_inputs + 1;
+        let first_and_gate = first_latch + self.latches.len() as u32;
+        for (i, (in0, in1)) in self.and_gates.iter().enumerate().rev() {
+            let signal = first_and_gate + i as u32;
+            for replacement in [Wire::CONSTANT_ZERO, Wire::CONSTANT_ONE, *in0, *in1] {
+                candidates.push(self.remove_signal(signal, replacement));
+            }
+        }
+
+        // latches become their initial value, or get a fixed initial value
+        for (i, (_, initial)) in self.latches.iter().enumerate().rev() {
+            let signal = first_latch + i as u32;
+            match initial {
+                TernaryValue::False => {
+                    candidates.push(self.remove_signal(signal, Wire::CONSTANT_ZERO))
+                }
+                TernaryValue::True => {
+                    candidates.push(self.remove_signal(signal, Wire::CONSTANT_ONE))
+                }
+                TernaryValue::X => {
+                    candidates.push(self.remove_signal(signal, Wire::CONSTANT_ZERO));
+                    candidates.push(self.remove_signal(signal, Wire::CONSTANT_ONE));
+                    for value in [TernaryValue::False, TernaryValue::True] {
+                        let mut r = self.clone();
+                        r.latches[i].1 = value;
+                        candidates.push(r);
+                    }
+                }
+            }
+        }
+
+        // inputs
+        for signal in (1..first_latch).rev() {
+            candidates.push(self.remove_signal(signal, Wire::CONSTANT_ZERO));
+        }
+
+        candidates
+    }
+}
+
+impl Fuzzer {
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Greedily shrinks a model while it keeps failing in the same way (the same kind of failure
+    /// using the same configuration and seed). The result is 1-minimal with respect to removing
+    /// a single gate, latch, input, property or constraint.
+    pub fn minimize(
+        &self,
+        aig: &AndInverterGraph,
+        configuration: &FuzzingConfiguration,
+        seed: u64,
+        kind: &FuzzingFailureKind,
+    ) -> AndInverterGraph {
+        let fails = |candidate: &AndInverterGraph| {
+            let mut oracle = FuzzingOracle::new(candidate);
+            match self.check_aig_with_configuration(candidate, configuration, seed, &mut oracle) {
+                Ok(()) => false,
+                Err(k) => mem::discriminant(&k) == mem::discriminant(kind),
+            }
+        };
+
+        let mut current = ReducibleAig::from_aig(aig);
+        'shrink: loop {
+            for candidate in current.get_smaller_candidates() {
+                if fails(&candidate.to_aig()) {
+                    current = candidate;
+                    continue 'shrink;
+                }
+            }
+            break;
+        }
+        current.to_aig()
+    }
+}
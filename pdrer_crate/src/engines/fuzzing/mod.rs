@@ -0,0 +1,202 @@
+//! Differential testing of the verification pipeline using random AIGs.
+//!
+//! Random sequential AIGs are simplified, turned into transition systems and proven by PDR under
+//! several parameter sets. Every result is checked using `check_proof_result` and compared to an
+//! oracle (explicit-state reachability for small models and an independent bounded model
+//! checker otherwise), failing models are delta debugged into small reproducers.
+
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::fmt;
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+
+use crate::models::AndInverterGraph;
+
+// ************************************************************************************************
+// struct
+// ************************************************************************************************
+
+/// Parameters of a fuzzing campaign.
+#[derive(Debug, Clone, Copy)]
+pub struct FuzzingParameters {
+    /// Seed of the random number generator that generates the models and the PDR seeds.
+    pub seed: u64,
+    /// The number of random models to check.
+    pub iterations: usize,
+
+    /// The sizes of the random models.
+    pub number_of_inputs: usize,
+    pub number_of_latches: usize,
+    pub number_of_and_gates: usize,
+    pub number_of_bad_wires: usize,
+    pub number_of_constraint_wires: usize,
+    /// If true the inputs of the and gates are chosen close to the gate (using a geometric
+    /// distribution), this yields deeper circuits than the uniform distribution.
+    pub use_geometric_distribution: bool,
+
+    /// If true the circuit is simplified using the default pipeline before the proof engine runs.
+    pub simplify: bool,
+    /// The depth up to which the oracle searches for counterexamples when PDR claims the property
+    /// holds, unless the model is small enough for explicit-state reachability.
+    pub bmc_depth: usize,
+    /// The maximum time each PDR run is allowed to take, runs that time out are not counted as
+    /// failures.
+    pub timeout: Duration,
+    /// If true then failing models are delta debugged before they are reported.
+    pub minimize: bool,
+    /// If true then a line is printed for every model that is checked.
+    pub verbose: bool,
+}
+
+/// A PDR parameter set that every random model is proven with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzingConfiguration {
+    pub name: &'static str,
+    pub er: bool,
+    pub generalize_using_ctg: bool,
+    pub use_infinite_frame: bool,
+}
+
+/// The way in which the pipeline misbehaved on some model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FuzzingFailureKind {
+    /// Some part of the pipeline panicked, holds the panic message.
+    Panic(String),
+    /// The result of PDR was rejected by `check_proof_result`, holds the reason.
+    InvalidResult(String),
+    /// PDR proved the property, but the oracle found a counterexample of this depth.
+    MissedCounterexample(usize),
+    /// PDR returned a counterexample of this depth, but the oracle found no counterexample that
+    /// is at most this deep.
+    SpuriousCounterexample(usize),
+    /// PDR stopped early claiming that no counterexample of at most this depth exists, but the
+    /// oracle found one.
+    WrongBoundedSafety(usize),
+}
+
+/// A model on which the pipeline misbehaved.
+#[derive(Debug, Clone)]
+pub struct FuzzingFailure {
+    /// The iteration in which the model was generated.
+    pub iteration: usize,
+    pub configuration: FuzzingConfiguration,
+    /// The seed that PDR was run with.
+    pub pdr_seed: u64,
+    pub kind: FuzzingFailureKind,
+    /// The random model that was generated.
+    pub aig: AndInverterGraph,
+    /// A smaller model that fails in the same way, this is `aig` when minimization is disabled.
+    pub reproducer: AndInverterGraph,
+}
+
+pub struct Fuzzer {
+    parameters: FuzzingParameters,
+    configurations: Vec<FuzzingConfiguration>,
+    rng: StdRng,
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl FuzzingParameters {
+    pub const DEFAULT: Self = Self {
+        seed: 0,
+        iterations: 100,
+        number_of_inputs: 3,
+        number_of_latches: 6,
+        number_of_and_gates: 30,
+        number_of_bad_wires: 1,
+        number_of_constraint_wires: 0,
+        use_geometric_distribution: true,
+        simplify: true,
+        bmc_depth: 20,
+        timeout: Duration::from_secs(10),
+        minimize: true,
+        verbose: false,
+    };
+
+    pub fn new() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Default for FuzzingParameters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ************************************************************************************************
+// printing
+// ************************************************************************************************
+
+impl fmt::Display for FuzzingConfiguration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (er = {}, ctg = {}, f_inf = {})",
+            self.name, self.er, self.generalize_using_ctg, self.use_infinite_frame
+        )
+    }
+}
+
+impl fmt::Display for FuzzingFailureKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Panic(message) => write!(f, "The pipeline panicked: {}", message),
+            Self::InvalidResult(reason) => write!(f, "The result is invalid: {}", reason),
+            Self::MissedCounterexample(depth) => write!(
+                f,
+                "The property was proven, but a counterexample of depth {} exists",
+                depth
+            ),
+            Self::SpuriousCounterexample(depth) => write!(
+                f,
+                "A counterexample of depth {} was returned, but no such counterexample exists",
+                depth
+            ),
+            Self::WrongBoundedSafety(depth) => write!(
+                f,
+                "Safety up to depth {} was claimed, but a counterexample within it exists",
+                depth
+            ),
+        }
+    }
+}
+
+impl fmt::Display for FuzzingFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Iteration {}, configuration {}, pdr seed {}: {}. Reproducer has {} latches and {} and gates.",
+            self.iteration,
+            self.configuration,
+            self.pdr_seed,
+            self.kind,
+            self.reproducer.get_latch_information().len(),
+            self.reproducer.get_all_and_gates().len()
+        )
+    }
+}
+
+// ************************************************************************************************
+// rust submodule declaration, they get searched in their respective file  names
+// ************************************************************************************************
+
+pub mod bmc;
+pub mod check;
+pub mod minimize;
+pub mod oracle;
+pub mod run;
+
+// ************************************************************************************************
+// re-exports of structs in these modules to simplify paths for other imports
+// ************************************************************************************************
+
+pub use bmc::AigBoundedModelChecker;
+pub use oracle::FuzzingOracle;
@@ -0,0 +1,60 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use super::AigBoundedModelChecker;
+use crate::engines::{ExplicitStateReachability, ExplicitStateReachabilityParameters};
+use crate::models::{AndInverterGraph, Circuit};
+
+// ************************************************************************************************
+// struct
+// ************************************************************************************************
+
+/// Finds the shortest counterexample of a model. When the model is small enough this is exact
+/// and uses explicit-state reachability, otherwise bounded model checking is used.
+pub struct FuzzingOracle {
+    bmc: AigBoundedModelChecker,
+    /// The depth of the shortest counterexample (`None` if the property holds), known only if
+    /// the model is small enough.
+    exact: Option<Option<usize>>,
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl FuzzingOracle {
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    pub fn new(aig: &AndInverterGraph) -> Self {
+        let circuit = Circuit::from_aig(aig);
+        let assume_output_is_bad = circuit.get_bad_wires().is_empty();
+        let exact = ExplicitStateReachability::new(
+            &circuit,
+            assume_output_is_bad,
+            ExplicitStateReachabilityParameters::new(),
+        )
+        .ok()
+        .map(|mut e| e.prove().err().map(|c| c.inputs.len() - 1));
+
+        Self {
+            bmc: AigBoundedModelChecker::new(aig),
+            exact,
+        }
+    }
+
+    /// True if the oracle knows the shortest counterexample of every depth.
+    pub fn is_exact(&self) -> bool {
+        self.exact.is_some()
+    }
+
+    /// Returns the depth of the shortest counterexample whose depth is at most `depth`.
+    pub fn find_shortest_counterexample(&mut self, depth: usize) -> Option<usize> {
+        match self.exact {
+            Some(shortest) => shortest.filter(|d| *d <= depth),
+            None => self.bmc.find_shortest_counterexample(depth),
+        }
+    }
+}
@@ -0,0 +1,167 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::time::Instant;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::{
+    Fuzzer, FuzzingConfiguration, FuzzingFailure, FuzzingFailureKind, FuzzingOracle,
+    FuzzingParameters,
+};
+use crate::models::AndInverterGraph;
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl Fuzzer {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn generate_aig(&mut self) -> AndInverterGraph {
+        let p = &self.parameters;
+        if p.use_geometric_distribution {
+            AndInverterGraph::geometric_random(
+                &mut self.rng,
+                p.number_of_inputs,
+                p.number_of_latches,
+                p.number_of_and_gates,
+                0,
+                p.number_of_bad_wires,
+                p.number_of_constraint_wires,
+            )
+        } else {
+            AndInverterGraph::uniform_random(
+                &mut self.rng,
+                p.number_of_inputs,
+                p.number_of_latches,
+                p.number_of_and_gates,
+                0,
+                p.number_of_bad_wires,
+                p.number_of_constraint_wires,
+            )
+        }
+    }
+
+    fn get_failure(
+        &self,
+        aig: &AndInverterGraph,
+        iteration: usize,
+        configuration: FuzzingConfiguration,
+        seed: u64,
+        kind: FuzzingFailureKind,
+    ) -> Box<FuzzingFailure> {
+        let reproducer = if self.parameters.minimize {
+            self.minimize(aig, &configuration, seed, &kind)
+        } else {
+            aig.to_owned()
+        };
+        Box::new(FuzzingFailure {
+            iteration,
+            configuration,
+            pdr_seed: seed,
+            kind,
+            aig: aig.to_owned(),
+            reproducer,
+        })
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    pub fn new(parameters: FuzzingParameters) -> Self {
+        Self {
+            parameters,
+            configurations: FuzzingConfiguration::ALL.to_vec(),
+            rng: StdRng::seed_from_u64(parameters.seed),
+        }
+    }
+
+    /// Replaces the PDR configurations every model is checked with.
+    pub fn set_configurations(&mut self, configurations: Vec<FuzzingConfiguration>) {
+        self.configurations = configurations;
+    }
+
+    pub fn get_configurations(&self) -> &[FuzzingConfiguration] {
+        &self.configurations
+    }
+
+    /// Checks a single model with every configuration, stopping at the first failure.
+    pub fn check_aig(
+        &mut self,
+        aig: &AndInverterGraph,
+        iteration: usize,
+    ) -> Result<(), Box<FuzzingFailure>> {
+        let mut oracle = FuzzingOracle::new(aig);
+        for configuration in self.configurations.clone() {
+            let seed: u64 = self.rng.gen();
+            let start = Instant::now();
+            let result = self.check_aig_with_configuration(aig, &configuration, seed, &mut oracle);
+            if self.parameters.verbose {
+                println!(
+                    "Iteration {}, configuration {} finished in {:.3} sec.",
+                    iteration,
+                    configuration.name,
+                    start.elapsed().as_secs_f32()
+                );
+            }
+
+            if let Err(kind) = result {
+                return Err(self.get_failure(aig, iteration, configuration, seed, kind));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks a single model with a single configuration and the given PDR seed, this replays a
+    /// failure using the `pdr_seed` and the configuration it reported.
+    pub fn check_aig_with_seed(
+        &self,
+        aig: &AndInverterGraph,
+        configuration: &FuzzingConfiguration,
+        seed: u64,
+    ) -> Result<(), Box<FuzzingFailure>> {
+        let mut oracle = FuzzingOracle::new(aig);
+        self.check_aig_with_configuration(aig, configuration, seed, &mut oracle)
+            .map_err(|kind| self.get_failure(aig, 0, *configuration, seed, kind))
+    }
+
+    /// Generates and checks random models, stopping at the first failure.
+    pub fn run(&mut self) -> Result<(), Box<FuzzingFailure>> {
+        for iteration in 0..self.parameters.iterations {
+            let aig = self.generate_aig();
+            self.check_aig(&aig, iteration)?;
+        }
+        Ok(())
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_random_models_pass() {
+        let mut parameters = FuzzingParameters::new();
+        parameters.iterations = 10;
+        parameters.number_of_latches = 4;
+        parameters.number_of_and_gates = 20;
+        parameters.number_of_constraint_wires = 1;
+        parameters.timeout = Duration::from_secs(60);
+        let mut fuzzer = Fuzzer::new(parameters);
+        if let Err(failure) = fuzzer.run() {
+            panic!("{}\n{}", failure, failure.reproducer.get_aag_string());
+        }
+    }
+}
@@ -4,11 +4,18 @@
 // rust submodule declaration, they get searched in their respective file  names
 // ************************************************************************************************
 
+pub mod explicit;
+pub mod fuzzing;
 pub mod pdr;
+pub mod random_simulation;
 
 // ************************************************************************************************
 // re-exports of structs in these modules to simplify paths for other imports
 // ************************************************************************************************
 
+pub use explicit::{ExplicitStateReachability, ExplicitStateReachabilityParameters};
+pub use fuzzing::{Fuzzer, FuzzingParameters};
+pub use pdr::BidirectionalPropertyDirectedReachability;
 pub use pdr::PropertyDirectedReachability;
 pub use pdr::PropertyDirectedReachabilityParameters;
+pub use random_simulation::{RandomSimulation, RandomSimulationParameters};
@@ -0,0 +1,84 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use super::{
+    BidirectionalPropertyDirectedReachability, BidirectionalPropertyDirectedReachabilityError,
+    PropertyDirectedReachabilityDirection, ReversedCircuit,
+};
+use crate::engines::pdr::{
+    PropertyDirectedReachability, PropertyDirectedReachabilityParameters,
+    PropertyDirectedReachabilitySolver,
+};
+use crate::models::{Circuit, FiniteStateTransitionSystem};
+use crate::solvers::dd::DecisionDiagramManager;
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager>
+    BidirectionalPropertyDirectedReachability<T, D>
+{
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Creates the engines for the direction, `fin_state` must be the transition system of the
+    /// circuit. The transition system of the reversed circuit uses the same encoding, and both
+    /// engines use the same parameters.
+    /// The bidirectional direction is rejected if `D` does not support several managers on the
+    /// same thread.
+    pub fn new(
+        circuit: &Circuit,
+        assume_output_is_bad: bool,
+        fin_state: Rc<RefCell<FiniteStateTransitionSystem>>,
+        direction: PropertyDirectedReachabilityDirection,
+        parameters: PropertyDirectedReachabilityParameters,
+    ) -> Result<Self, BidirectionalPropertyDirectedReachabilityError> {
+        if direction == PropertyDirectedReachabilityDirection::Bidirectional
+            && !D::SUPPORTS_SEVERAL_MANAGERS_PER_THREAD
+        {
+            return Err(
+                BidirectionalPropertyDirectedReachabilityError::UnsupportedDecisionDiagramManager,
+            );
+        }
+        let reversed = ReversedCircuit::new(circuit, assume_output_is_bad);
+
+        let forward = if direction == PropertyDirectedReachabilityDirection::Backward {
+            None
+        } else {
+            let engine = PropertyDirectedReachability::new(fin_state.clone(), parameters)
+                .map_err(BidirectionalPropertyDirectedReachabilityError::Engine)?;
+            Some(engine)
+        };
+
+        let backward = if direction == PropertyDirectedReachabilityDirection::Forward {
+            None
+        } else {
+            let reversed_fin_state = FiniteStateTransitionSystem::new_with_encoding(
+                reversed.get_circuit(),
+                false,
+                fin_state.borrow().get_encoding(),
+            )
+            .map_err(BidirectionalPropertyDirectedReachabilityError::ReversedTransitionSystem)?;
+            let engine = PropertyDirectedReachability::new(
+                Rc::new(RefCell::new(reversed_fin_state)),
+                parameters,
+            )
+            .map_err(BidirectionalPropertyDirectedReachabilityError::Engine)?;
+            Some(engine)
+        };
+
+        Ok(Self {
+            direction,
+            forward,
+            backward,
+            reversed,
+            fin_state,
+        })
+    }
+}
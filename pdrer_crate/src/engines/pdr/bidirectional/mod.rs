@@ -0,0 +1,125 @@
+//! Running PDR backwards, from the bad states towards the initial states, and alternating
+//! between the two directions.
+//!
+//! The backward engine is an ordinary PDR engine that runs on a reversed circuit, whose initial
+//! state leads to the bad states of the original circuit and whose bad states are the initial
+//! states of the original circuit. Its proofs and counterexamples are mapped back to the
+//! original transition system, so they can be checked and exported like the forward ones.
+
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+use super::{
+    PropertyDirectedReachability, PropertyDirectedReachabilityError,
+    PropertyDirectedReachabilitySolver,
+};
+use crate::formulas::Variable;
+use crate::models::finite_state_transition_system::FiniteStateTransitionSystemError;
+use crate::models::{Circuit, FiniteStateTransitionSystem};
+use crate::solvers::dd::DecisionDiagramManager;
+
+// ************************************************************************************************
+// struct
+// ************************************************************************************************
+
+/// The direction in which the transition relation is explored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyDirectedReachabilityDirection {
+    /// From the initial states towards the bad states.
+    Forward,
+    /// From the bad states towards the initial states.
+    Backward,
+    /// Alternates between the directions, every engine runs until its depth increases.
+    Bidirectional,
+}
+
+#[derive(Debug)]
+pub enum BidirectionalPropertyDirectedReachabilityError {
+    /// The transition system of the reversed circuit could not be created.
+    ReversedTransitionSystem(FiniteStateTransitionSystemError),
+    Engine(PropertyDirectedReachabilityError),
+    /// The bidirectional mode was requested with a decision diagram manager that does not
+    /// support several managers per thread.
+    UnsupportedDecisionDiagramManager,
+}
+
+/// The reversed circuit, and the signals that are needed for mapping results back.
+///
+/// Every latch `x` of the original circuit becomes an input that holds the predecessor state,
+/// and is copied into a new latch `y` that holds the current state. A new latch `first` is only
+/// set in the initial state, in which the predecessor must be a bad state. The new input `stop`
+/// ends the trace by setting the latch `stopped`, this is needed because a state is bad only
+/// if the constraints can be satisfied in it, while the initial states of the original circuit
+/// need not have a predecessor.
+#[derive(Debug, Clone)]
+pub struct ReversedCircuit {
+    circuit: Circuit,
+    /// The variables of the latches of the original transition system, and the variables of
+    /// the corresponding latches `y` and inputs `x` of the reversed one.
+    latches: Vec<(Variable, Variable, Variable)>,
+    /// The variables of the inputs of the original transition system and of the reversed one.
+    inputs: Vec<(Variable, Variable)>,
+    first: Variable,
+    stopped: Variable,
+}
+
+/// Runs PDR in the requested direction, the results are over the original transition system.
+///
+/// In the bidirectional mode both engines own a decision diagram manager and use them in turns
+/// on the same thread, so `D` must support that (see
+/// `DecisionDiagramManager::SUPPORTS_SEVERAL_MANAGERS_PER_THREAD`). `CuddBdd` does, while the
+/// oxidd managers share thread local state and may corrupt each other, so they are rejected.
+pub struct BidirectionalPropertyDirectedReachability<
+    T: PropertyDirectedReachabilitySolver,
+    D: DecisionDiagramManager,
+> {
+    direction: PropertyDirectedReachabilityDirection,
+    forward: Option<PropertyDirectedReachability<T, D>>,
+    backward: Option<PropertyDirectedReachability<T, D>>,
+    reversed: ReversedCircuit,
+    fin_state: Rc<RefCell<FiniteStateTransitionSystem>>,
+}
+
+// ************************************************************************************************
+// printing
+// ************************************************************************************************
+
+impl fmt::Display for PropertyDirectedReachabilityDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Forward => write!(f, "forward"),
+            Self::Backward => write!(f, "backward"),
+            Self::Bidirectional => write!(f, "bidirectional"),
+        }
+    }
+}
+
+impl fmt::Display for BidirectionalPropertyDirectedReachabilityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ReversedTransitionSystem(e) => write!(
+                f,
+                "The transition system of the reversed circuit could not be created: {:?}",
+                e
+            ),
+            Self::Engine(e) => write!(f, "The engine could not be created: {:?}", e),
+            Self::UnsupportedDecisionDiagramManager => write!(
+                f,
+                "The decision diagram manager does not support the bidirectional direction."
+            ),
+        }
+    }
+}
+
+// ************************************************************************************************
+// rust submodule declaration, they get searched in their respective file  names
+// ************************************************************************************************
+
+pub mod construction;
+pub mod prove;
+pub mod reversal;
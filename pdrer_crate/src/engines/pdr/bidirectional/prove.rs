@@ -0,0 +1,253 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use super::BidirectionalPropertyDirectedReachability;
+use super::PropertyDirectedReachabilityDirection;
+use crate::engines::pdr::{
+    PropertyDirectedReachabilityPartialResult, PropertyDirectedReachabilityProofError,
+    PropertyDirectedReachabilitySolver,
+};
+use crate::models::finite_state_transition_system::ProofResult;
+use crate::solvers::dd::DecisionDiagramManager;
+use crate::solvers::sat::incremental::IncrementalSatSolver;
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl<T: PropertyDirectedReachabilitySolver + IncrementalSatSolver, D: DecisionDiagramManager>
+    BidirectionalPropertyDirectedReachability<T, D>
+{
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn get_forward_result(&self, result: ProofResult) -> ProofResult {
+        match result {
+            Ok(p) => Ok(self
+                .reversed
+                .get_forward_proof(&p, &self.fin_state.borrow())),
+            Err(c) => Err(self.reversed.get_forward_counterexample(&c)),
+        }
+    }
+
+    /// The clauses of the backward engine are not over the original transition system, so the
+    /// partial result holds the clauses of the forward engine if there is one. A reversed
+    /// counterexample is one step longer than the original one.
+    fn get_forward_partial_result(
+        &self,
+        partial: PropertyDirectedReachabilityPartialResult,
+    ) -> PropertyDirectedReachabilityPartialResult {
+        let mut result = match &self.forward {
+            Some(forward) => forward.get_partial_result(false),
+            None => PropertyDirectedReachabilityPartialResult::from_bounded_safety(None),
+        };
+        result.add_bounded_safety(Self::get_forward_bounded_safety(&partial));
+        result
+    }
+
+    /// The bounded safety of a partial result of the backward engine, over the original
+    /// transition system.
+    fn get_forward_bounded_safety(
+        partial: &PropertyDirectedReachabilityPartialResult,
+    ) -> Option<usize> {
+        partial.get_bounded_safety().and_then(|k| k.checked_sub(1))
+    }
+
+    fn map_partial_result<F>(
+        error: PropertyDirectedReachabilityProofError,
+        f: F,
+    ) -> PropertyDirectedReachabilityProofError
+    where
+        F: FnOnce(
+            PropertyDirectedReachabilityPartialResult,
+        ) -> PropertyDirectedReachabilityPartialResult,
+    {
+        match error {
+            PropertyDirectedReachabilityProofError::MaxDepthReached(p) => {
+                PropertyDirectedReachabilityProofError::MaxDepthReached(f(p))
+            }
+            PropertyDirectedReachabilityProofError::TimeOutReached(p) => {
+                PropertyDirectedReachabilityProofError::TimeOutReached(f(p))
+            }
+            PropertyDirectedReachabilityProofError::MemoryLimitReached(p) => {
+                PropertyDirectedReachabilityProofError::MemoryLimitReached(f(p))
+            }
+        }
+    }
+
+    /// The forward engine stopped, the bound that the backward engine reached so far still holds.
+    fn get_error_of_forward_engine(
+        &self,
+        error: PropertyDirectedReachabilityProofError,
+    ) -> PropertyDirectedReachabilityProofError {
+        let bounded_safety = self
+            .backward
+            .as_ref()
+            .and_then(|b| Self::get_forward_bounded_safety(&b.get_partial_result(false)));
+        Self::map_partial_result(error, |mut p| {
+            p.add_bounded_safety(bounded_safety);
+            p
+        })
+    }
+
+    fn get_error_of_backward_engine(
+        &self,
+        error: PropertyDirectedReachabilityProofError,
+    ) -> PropertyDirectedReachabilityProofError {
+        Self::map_partial_result(error, |p| self.get_forward_partial_result(p))
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    pub fn get_direction(&self) -> PropertyDirectedReachabilityDirection {
+        self.direction
+    }
+
+    /// Performs the proof, the engines take turns and every engine runs until its depth
+    /// increases. The results are over the original transition system.
+    pub fn prove(&mut self) -> Result<ProofResult, PropertyDirectedReachabilityProofError> {
+        let mut depth = 0;
+        loop {
+            if let Some(forward) = self.forward.as_mut() {
+                match forward.prove_up_to_depth(depth) {
+                    Ok(Some(r)) => return Ok(r),
+                    Ok(None) => {}
+                    Err(e) => return Err(self.get_error_of_forward_engine(e)),
+                }
+            }
+            if let Some(backward) = self.backward.as_mut() {
+                match backward.prove_up_to_depth(depth) {
+                    Ok(Some(r)) => return Ok(self.get_forward_result(r)),
+                    Ok(None) => {}
+                    Err(e) => return Err(self.get_error_of_backward_engine(e)),
+                }
+            }
+            depth += 1;
+        }
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::engines::pdr::bidirectional::BidirectionalPropertyDirectedReachabilityError;
+    use crate::engines::pdr::PropertyDirectedReachabilityParameters;
+    use crate::engines::{ExplicitStateReachability, ExplicitStateReachabilityParameters};
+    use crate::models::{AndInverterGraph, Circuit, FiniteStateTransitionSystem};
+    use crate::solvers::dd::{CuddBdd, OxiddBdd};
+    use crate::solvers::sat::incremental::CaDiCalSolver;
+
+    #[test]
+    fn test_backward_results_are_valid() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for i in 0..100 {
+            let aig = AndInverterGraph::geometric_random(&mut rng, 3, 6, 30, 0, 1, i % 3);
+            let circuit = Circuit::from_aig(&aig);
+            let fin_state = match FiniteStateTransitionSystem::new(&circuit, false) {
+                Ok(f) => Rc::new(RefCell::new(f)),
+                Err(_) => continue,
+            };
+            let is_safe = ExplicitStateReachability::new(
+                &circuit,
+                false,
+                ExplicitStateReachabilityParameters::new(),
+            )
+            .unwrap()
+            .prove()
+            .is_ok();
+
+            // a single engine, the oxidd managers cannot be used in turns
+            let mut parameters = PropertyDirectedReachabilityParameters::new();
+            parameters.seed = i as u64;
+            let mut engine =
+                BidirectionalPropertyDirectedReachability::<CaDiCalSolver, OxiddBdd>::new(
+                    &circuit,
+                    false,
+                    fin_state.clone(),
+                    PropertyDirectedReachabilityDirection::Backward,
+                    parameters,
+                )
+                .unwrap();
+            let result = engine.prove().unwrap();
+            assert_eq!(result.is_ok(), is_safe, "{}", i);
+            fin_state
+                .borrow_mut()
+                .check_proof_result::<CaDiCalSolver>(result)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_bidirectional_results_are_valid() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for i in 0..100 {
+            let aig = AndInverterGraph::geometric_random(&mut rng, 3, 6, 30, 0, 1, i % 3);
+            let circuit = Circuit::from_aig(&aig);
+            let fin_state = match FiniteStateTransitionSystem::new(&circuit, false) {
+                Ok(f) => Rc::new(RefCell::new(f)),
+                Err(_) => continue,
+            };
+            let is_safe = ExplicitStateReachability::new(
+                &circuit,
+                false,
+                ExplicitStateReachabilityParameters::new(),
+            )
+            .unwrap()
+            .prove()
+            .is_ok();
+
+            let mut parameters = PropertyDirectedReachabilityParameters::new();
+            parameters.seed = i as u64;
+            let mut engine =
+                BidirectionalPropertyDirectedReachability::<CaDiCalSolver, CuddBdd>::new(
+                    &circuit,
+                    false,
+                    fin_state.clone(),
+                    PropertyDirectedReachabilityDirection::Bidirectional,
+                    parameters,
+                )
+                .unwrap();
+            let result = engine.prove().unwrap();
+            assert_eq!(result.is_ok(), is_safe, "{}", i);
+            fin_state
+                .borrow_mut()
+                .check_proof_result::<CaDiCalSolver>(result)
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_bidirectional_is_rejected_for_oxidd() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let aig = AndInverterGraph::geometric_random(&mut rng, 3, 6, 30, 0, 1, 0);
+        let circuit = Circuit::from_aig(&aig);
+        let fin_state = Rc::new(RefCell::new(
+            FiniteStateTransitionSystem::new(&circuit, false).unwrap(),
+        ));
+        let engine = BidirectionalPropertyDirectedReachability::<CaDiCalSolver, OxiddBdd>::new(
+            &circuit,
+            false,
+            fin_state,
+            PropertyDirectedReachabilityDirection::Bidirectional,
+            PropertyDirectedReachabilityParameters::new(),
+        );
+        assert!(matches!(
+            engine,
+            Err(BidirectionalPropertyDirectedReachabilityError::UnsupportedDecisionDiagramManager)
+        ));
+    }
+}
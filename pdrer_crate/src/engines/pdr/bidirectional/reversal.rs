@@ -0,0 +1,401 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use fxhash::FxHashMap;
+
+use super::ReversedCircuit;
+use crate::formulas::{Clause, Cube, Literal, Variable, CNF};
+use crate::models::circuit::node_types::CircuitNodeType;
+use crate::models::definition::DefinitionFunction;
+use crate::models::truth_table::TRUTH_TABLE_MAX_INPUTS;
+use crate::models::{
+    Circuit, CircuitBuilder, Counterexample, Definition, FiniteStateTransitionSystem, Proof,
+    Signal, SortedVecOfLiterals, TernaryValue, UniqueSortedHashMap, UniqueSortedVec, Wire,
+};
+
+// ************************************************************************************************
+// types
+// ************************************************************************************************
+
+/// A literal of the reversed transition system after it was mapped to the original one, the
+/// latches `first` and `stopped` are 0 in the states that matter and become constants.
+#[derive(Debug, Clone, Copy)]
+enum MappedLiteral {
+    Literal(Literal),
+    Constant(bool),
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl ReversedCircuit {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn add_and_gate(builder: &mut CircuitBuilder, inputs: Vec<Wire>) -> Signal {
+        let signal = builder.get_unused_signal();
+        builder
+            .add_and_gate(signal, UniqueSortedVec::from_sequence(inputs))
+            .unwrap();
+        signal
+    }
+
+    fn variable(mapping: &UniqueSortedHashMap<Signal, Signal>, signal: Signal) -> Variable {
+        Variable::new(mapping.get(&signal).unwrap().number())
+    }
+
+    fn map_literal(
+        l: Literal,
+        state_mapping: &FxHashMap<Variable, MappedLiteral>,
+        extension_mapping: &FxHashMap<Variable, MappedLiteral>,
+    ) -> MappedLiteral {
+        let v = l.variable();
+        let mapped = state_mapping
+            .get(&v)
+            .or_else(|| extension_mapping.get(&v))
+            .copied()
+            .unwrap_or_else(|| panic!("Variable {} is not a state or extension variable.", v));
+        match mapped {
+            MappedLiteral::Literal(m) => MappedLiteral::Literal(m.negate_if_true(l.is_negated())),
+            MappedLiteral::Constant(c) => MappedLiteral::Constant(c ^ l.is_negated()),
+        }
+    }
+
+    /// Substitutes the constant inputs of a definition, returns the function over the remaining
+    /// inputs, or the constant the definition is equal to.
+    fn simplify_definition(
+        function: DefinitionFunction,
+        inputs: &[MappedLiteral],
+    ) -> Result<(DefinitionFunction, Vec<Literal>, bool), bool> {
+        let remaining: Vec<Literal> = inputs
+            .iter()
+            .filter_map(|i| match i {
+                MappedLiteral::Literal(l) => Some(*l),
+                MappedLiteral::Constant(_) => None,
+            })
+            .collect();
+        if remaining.len() == inputs.len() {
+            let (function, inputs) = function.sort_inputs(remaining);
+            return Ok((function, inputs, false));
+        }
+        let constants: Vec<bool> = inputs
+            .iter()
+            .filter_map(|i| match i {
+                MappedLiteral::Literal(_) => None,
+                MappedLiteral::Constant(c) => Some(*c),
+            })
+            .collect();
+
+        match function {
+            DefinitionFunction::And => {
+                if constants.iter().any(|c| !c) {
+                    Err(false)
+                } else if remaining.is_empty() {
+                    Err(true)
+                } else {
+                    Ok((function, remaining, false))
+                }
+            }
+            DefinitionFunction::Xor => {
+                let parity = constants.iter().filter(|c| **c).count() % 2 == 1;
+                if remaining.is_empty() {
+                    Err(parity)
+                } else {
+                    Ok((function, remaining, parity))
+                }
+            }
+            DefinitionFunction::Ite(_)
+            | DefinitionFunction::Maj3
+            | DefinitionFunction::TruthTable(_) => {
+                debug_assert!(inputs.len() <= TRUTH_TABLE_MAX_INPUTS);
+                // the truth table over the remaining inputs
+                let mut values = vec![false; inputs.len()];
+                let mut table = 0;
+                for row in 0..(1 << remaining.len()) {
+                    let mut j = 0;
+                    for (v, i) in values.iter_mut().zip(inputs.iter()) {
+                        *v = match i {
+                            MappedLiteral::Constant(c) => *c,
+                            MappedLiteral::Literal(_) => {
+                                j += 1;
+                                (row >> (j - 1)) & 1 == 1
+                            }
+                        };
+                    }
+                    if function.evaluate(&values) {
+                        table |= 1 << row;
+                    }
+                }
+                let rows = 1 << remaining.len();
+                if table == 0 {
+                    Err(false)
+                } else if table == (1_u32 << rows) - 1 {
+                    Err(true)
+                } else {
+                    let (function, inputs) =
+                        DefinitionFunction::TruthTable(table as _).sort_inputs(remaining);
+                    Ok((function, inputs, false))
+                }
+            }
+        }
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Creates the reversed circuit, the property is read from the circuit the same way
+    /// `FiniteStateTransitionSystem::new` reads it. The reversed circuit has no outputs, its
+    /// property is in its bad wire.
+    pub fn new(circuit: &Circuit, assume_output_is_bad: bool) -> Self {
+        let mut builder = CircuitBuilder::from_circuit(circuit);
+        builder.add_ground();
+
+        // the original latches become inputs, and the state is held in new latches
+        let original_inputs: Vec<Signal> = circuit.get_input_signals().iter().copied().collect();
+        let original_latches: Vec<(Signal, Wire, TernaryValue)> = circuit
+            .get_latch_signals()
+            .iter()
+            .map(|s| match &circuit.get_node(s).unwrap().node_type {
+                CircuitNodeType::Latch(l) => (*s, l.input, l.initial),
+                _ => unreachable!(),
+            })
+            .collect();
+        let mut state = vec![];
+        for (x, _, _) in original_latches.iter() {
+            builder.add_input(*x);
+            let y = builder.get_unused_signal();
+            builder.add_latch(y, x.wire(false), TernaryValue::False);
+            state.push(y);
+        }
+        let first = builder.get_unused_signal();
+        builder.add_latch(first, Wire::CONSTANT_ZERO, TernaryValue::True);
+        let stopped = builder.get_unused_signal();
+        builder.add_latch(stopped, Wire::CONSTANT_ZERO, TernaryValue::False);
+        let stop = builder.get_unused_signal();
+        builder.add_input(stop);
+        let stopped_next =
+            Self::add_and_gate(&mut builder, vec![stop.wire(true), stopped.wire(true)]);
+        builder.add_latch(stopped, stopped_next.wire(true), TernaryValue::False);
+
+        // the constraints of the original circuit hold in the predecessor, unless the trace stops
+        for c in circuit.get_invariant_constraint_wires().iter() {
+            builder.un_mark_as_invariant_constraint(*c);
+            let g = Self::add_and_gate(&mut builder, vec![stop.wire(true), !*c]);
+            builder.mark_as_invariant_constraint(g.wire(true));
+        }
+
+        // in the first cycle the predecessor is bad
+        let mut properties: Vec<Wire> = circuit.get_bad_wires().iter().copied().collect();
+        if assume_output_is_bad {
+            properties.extend(circuit.get_output_wires().iter().copied());
+        }
+        for w in circuit.get_bad_wires().iter() {
+            builder.un_mark_as_bad(*w);
+        }
+        for w in circuit.get_output_wires().iter() {
+            builder.un_mark_as_output(*w);
+        }
+        let mut inputs = vec![first.wire(false), stop.wire(true)];
+        inputs.extend(properties.iter().map(|w| !*w));
+        let g = Self::add_and_gate(&mut builder, inputs);
+        builder.mark_as_invariant_constraint(g.wire(true));
+
+        // afterwards the predecessor leads to the current state
+        for ((_, next, _), y) in original_latches.iter().zip(state.iter()) {
+            let both_1 = Self::add_and_gate(&mut builder, vec![*next, y.wire(false)]);
+            let both_0 = Self::add_and_gate(&mut builder, vec![!*next, y.wire(true)]);
+            let different =
+                Self::add_and_gate(&mut builder, vec![both_1.wire(true), both_0.wire(true)]);
+            let g = Self::add_and_gate(
+                &mut builder,
+                vec![first.wire(true), stop.wire(true), different.wire(false)],
+            );
+            builder.mark_as_invariant_constraint(g.wire(true));
+        }
+
+        // the bad states are the initial states of the original circuit
+        let mut inputs = vec![first.wire(true), stopped.wire(true)];
+        for ((_, _, initial), y) in original_latches.iter().zip(state.iter()) {
+            match initial {
+                TernaryValue::True => inputs.push(y.wire(false)),
+                TernaryValue::False => inputs.push(y.wire(true)),
+                TernaryValue::X => {}
+            }
+        }
+        let bad = Self::add_and_gate(&mut builder, inputs);
+        builder.mark_as_bad(bad.wire(false));
+
+        let (reversed, mapping) = builder.build().unwrap();
+        Self {
+            circuit: reversed,
+            latches: original_latches
+                .iter()
+                .zip(state.iter())
+                .map(|((x, _, _), y)| {
+                    (
+                        Variable::new(x.number()),
+                        Self::variable(&mapping, *y),
+                        Self::variable(&mapping, *x),
+                    )
+                })
+                .collect(),
+            inputs: original_inputs
+                .iter()
+                .map(|i| (Variable::new(i.number()), Self::variable(&mapping, *i)))
+                .collect(),
+            first: Self::variable(&mapping, first),
+            stopped: Self::variable(&mapping, stopped),
+        }
+    }
+
+    pub fn get_circuit(&self) -> &Circuit {
+        &self.circuit
+    }
+
+    /// Maps a counterexample of the reversed circuit to the original circuit.
+    /// The reversed counterexample goes from the bad states to an initial state, in step `k` its
+    /// inputs hold the state and the inputs of the original counterexample `k` steps before the
+    /// end. Its last step reaches the initial state and holds nothing.
+    pub fn get_forward_counterexample(&self, counterexample: &Counterexample) -> Counterexample {
+        let depth = counterexample.inputs.len() - 1;
+        debug_assert!(depth > 0);
+        let map_cube = |cube: &Cube, pairs: &mut dyn Iterator<Item = (Variable, Variable)>| {
+            Cube::from_sequence(
+                pairs
+                    .filter_map(|(original, reversed)| {
+                        if cube.contains(&reversed.literal(false)) {
+                            Some(original.literal(false))
+                        } else if cube.contains(&reversed.literal(true)) {
+                            Some(original.literal(true))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect(),
+            )
+        };
+
+        let initial_cube = map_cube(
+            &counterexample.inputs[depth - 1],
+            &mut self.latches.iter().map(|(o, _, x)| (*o, *x)),
+        );
+        let inputs = counterexample.inputs[..depth]
+            .iter()
+            .rev()
+            .map(|cube| map_cube(cube, &mut self.inputs.iter().copied()))
+            .collect();
+        Counterexample {
+            initial_cube,
+            inputs,
+        }
+    }
+
+    /// Maps a proof of the reversed circuit to the original transition system.
+    /// The invariant `R` of the reversed circuit over-approximates the states from which a bad
+    /// state can be reached, so the states outside of it (when `first` and `stopped` are 0)
+    /// form an invariant of the original circuit. The negation of every clause of `R` is
+    /// defined by an extension variable, and the invariant is the single clause holding them.
+    pub fn get_forward_proof(
+        &self,
+        proof: &Proof,
+        fin_state: &FiniteStateTransitionSystem,
+    ) -> Proof {
+        if proof.all_initial_states_violate_constraints {
+            // the bad states of the original circuit all violate the constraints
+            return Proof {
+                all_initial_states_violate_constraints: false,
+                invariant: CNF::new(),
+                definitions: vec![],
+            };
+        }
+
+        let mut state_mapping: FxHashMap<Variable, MappedLiteral> = self
+            .latches
+            .iter()
+            .map(|(o, y, _)| (*y, MappedLiteral::Literal(o.literal(false))))
+            .collect();
+        state_mapping.insert(self.first, MappedLiteral::Constant(false));
+        state_mapping.insert(self.stopped, MappedLiteral::Constant(false));
+
+        // the tag of an extension variable must not be another extension variable, so the
+        // variables are taken from every other block of `max_variable` variables
+        let block_size = fin_state.get_max_variable().number().max(1);
+        let mut next_variable = fin_state.get_max_variable();
+        fin_state.add_tags_to_variable(&mut next_variable, 1);
+        let mut new_variable = || {
+            let mut n = next_variable.number() + 1;
+            if ((n - 1) / block_size) % 2 != 0 {
+                n += block_size;
+            }
+            next_variable = Variable::new(n);
+            next_variable
+        };
+
+        let mut definitions = vec![];
+        let mut extension_mapping = FxHashMap::default();
+        let mut reversed_definitions = proof.definitions.to_owned();
+        reversed_definitions.sort_by_key(|d| d.variable);
+        for d in reversed_definitions {
+            let inputs: Vec<MappedLiteral> = d
+                .inputs
+                .iter()
+                .map(|l| Self::map_literal(*l, &state_mapping, &extension_mapping))
+                .collect();
+            let mapped = match Self::simplify_definition(d.function, &inputs) {
+                Ok((function, inputs, is_negated)) => {
+                    let variable = new_variable();
+                    definitions.push(Definition {
+                        variable,
+                        function,
+                        inputs: SortedVecOfLiterals::from_sequence(inputs),
+                    });
+                    MappedLiteral::Literal(variable.literal(is_negated))
+                }
+                Err(c) => MappedLiteral::Constant(c),
+            };
+            extension_mapping.insert(d.variable, mapped);
+        }
+
+        let mut invariant = vec![];
+        'clauses: for clause in proof.invariant.iter() {
+            let mut negation = vec![];
+            for l in clause.iter() {
+                match Self::map_literal(*l, &state_mapping, &extension_mapping) {
+                    MappedLiteral::Literal(m) => negation.push(!m),
+                    MappedLiteral::Constant(true) => continue 'clauses,
+                    MappedLiteral::Constant(false) => {}
+                }
+            }
+            match negation.len() {
+                // the clause is false, no state can reach a bad state
+                0 => {
+                    return Proof {
+                        all_initial_states_violate_constraints: false,
+                        invariant: CNF::new(),
+                        definitions: vec![],
+                    }
+                }
+                1 => invariant.push(negation[0]),
+                _ => {
+                    let variable = new_variable();
+                    definitions.push(Definition {
+                        variable,
+                        function: DefinitionFunction::And,
+                        inputs: SortedVecOfLiterals::from_sequence(negation),
+                    });
+                    invariant.push(variable.literal(false));
+                }
+            }
+        }
+
+        Proof {
+            all_initial_states_violate_constraints: false,
+            invariant: CNF::from_sequence(vec![Clause::from_sequence(invariant)]),
+            definitions,
+        }
+    }
+}
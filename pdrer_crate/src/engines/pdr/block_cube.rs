@@ -2,6 +2,7 @@
 // use
 // ************************************************************************************************
 
+use super::pdr_stats::PDRStats;
 use super::proof_obligations::ProofObligation;
 use super::{
     PropertyDirectedReachability, PropertyDirectedReachabilityProofError,
@@ -13,12 +14,13 @@ use crate::function;
 use crate::models::time_stats::function_timer::FunctionTimer;
 use crate::models::Counterexample;
 use crate::solvers::dd::DecisionDiagramManager;
+use crate::solvers::sat::incremental::IncrementalSatSolver;
 
 // ************************************************************************************************
 // impl
 // ************************************************************************************************
 
-impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager>
+impl<T: PropertyDirectedReachabilitySolver + IncrementalSatSolver, D: DecisionDiagramManager>
     PropertyDirectedReachability<T, D>
 {
     pub fn add_clause_to_frame_at_least(&mut self, clause: Clause, k: usize) -> usize {
@@ -153,7 +155,15 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager>
             }
         }
 
-        if max_k < self.frames.depth() {
+        // the obligation is kept for when the depth is increased
+        let mut re_push_beyond_depth = false;
+        if self.s.parameters.re_push_proof_obligations_beyond_depth && max_k == self.frames.depth()
+        {
+            self.frames.add_frames_up_to(max_k + 1);
+            re_push_beyond_depth = !self.frames.is_cube_blocked_in_frame(&po.cube, max_k + 1);
+        }
+
+        if max_k < self.frames.depth() || re_push_beyond_depth {
             let mut po = po;
             po.frame = max_k + 1;
             po.hash_when_added = self.frames.at(po.frame).get_hash();
@@ -200,15 +210,25 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager>
             None,
         )?;
 
+        self.block_proof_obligations()
+    }
+
+    /// Handles the proof obligations in the queue until all of them are blocked or only
+    /// obligations beyond the depth are left.
+    pub(super) fn block_proof_obligations(
+        &mut self,
+    ) -> Result<Result<(), PropertyDirectedReachabilityProofError>, Counterexample> {
+        let _timer = FunctionTimer::start(function!(), self.s.time_stats.clone());
+
         while !self.proof_obligations.is_empty() {
-            if self.s.parameters.start_time.unwrap().elapsed() > self.s.parameters.timeout {
-                return Ok(Err(PropertyDirectedReachabilityProofError::TimeOutReached));
+            if let Err(e) = self.check_resource_limits() {
+                return Ok(Err(e));
             }
 
             self.s
                 .pdr_stats
                 .borrow_mut()
-                .increment_generic_count("Total Proof Obligations");
+                .increment_generic_count(PDRStats::PROOF_OBLIGATIONS);
 
             self.frames.call_condense();
 
@@ -0,0 +1,534 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{PropertyDirectedReachability, PropertyDirectedReachabilitySolver};
+use crate::{
+    formulas::{Clause, Literal, Variable},
+    function,
+    models::{
+        definition::DefinitionFunction, time_stats::function_timer::FunctionTimer, Definition,
+        SortedVecOfLiterals,
+    },
+    solvers::dd::DecisionDiagramManager,
+};
+
+// ************************************************************************************************
+// struct
+// ************************************************************************************************
+
+/// The state of a PDR run that is needed in order to resume it later, possibly on another machine.
+///
+/// The checkpoint is saved as text, one item per line:
+/// ```text
+/// pdr_checkpoint 1
+/// depth <depth>
+/// frames <number of frames including INIT and F_INF>
+/// seed <seed of the random number generator>
+/// weights <weight of variable 0> <weight of variable 1> ...
+/// definition <variable> <AND|XOR|MAJ3|ITE<i>|TT<hex>> <literal> <literal> ...
+/// clause <frame|inf> <literal> <literal> ...
+/// ```
+/// Literals are written in DIMACS format, definitions are written in the order they were created.
+/// The inputs of a definition are written sorted, and the functions refer to them by position:
+/// - `ITE<i>` (for example `ITE0`) is an if-then-else of 3 inputs where input `i` is the
+///   condition, the first of the other two inputs is the result when the condition holds and the
+///   second is the result otherwise.
+/// - `MAJ3` is the majority of 3 inputs.
+/// - `TT<hex>` (for example `TT6` for the XOR of 2 inputs) is the truth table in lowercase
+///   hexadecimal without leading zeros, bit `r` is the result when every input `j` is assigned
+///   bit `j` of `r`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyDirectedReachabilityCheckpoint {
+    depth: usize,
+    seed: u64,
+    weights: Vec<f64>,
+    definitions: Vec<Definition>,
+    /// The clauses of every frame delta, the first is INIT and the last is F_INF.
+    deltas: Vec<Vec<Clause>>,
+}
+
+/// Where and how often a running engine saves checkpoints.
+#[derive(Debug, Clone)]
+pub(super) struct PeriodicCheckpoint {
+    path: PathBuf,
+    interval: Duration,
+    last_save: Instant,
+}
+
+#[derive(Debug)]
+pub enum PropertyDirectedReachabilityCheckpointError {
+    Io(std::io::Error),
+    MissingHeader,
+    InconsistentDepth,
+    InvalidLine(usize),
+    FrameOutOfRange(usize),
+    EngineAlreadyStarted,
+    WeightCountMismatch(usize, usize),
+    InvalidDefinition(usize),
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl PropertyDirectedReachabilityCheckpoint {
+    const HEADER: &'static str = "pdr_checkpoint 1";
+
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn parse_number<N: std::str::FromStr>(
+        word: Option<&str>,
+        line: usize,
+    ) -> Result<N, PropertyDirectedReachabilityCheckpointError> {
+        word.and_then(|w| w.parse().ok()).ok_or(
+            PropertyDirectedReachabilityCheckpointError::InvalidLine(line),
+        )
+    }
+
+    fn parse_literals<'a, I>(
+        words: I,
+        line: usize,
+    ) -> Result<Vec<Literal>, PropertyDirectedReachabilityCheckpointError>
+    where
+        I: Iterator<Item = &'a str>,
+    {
+        let mut literals = vec![];
+        for w in words {
+            let l: i32 = Self::parse_number(Some(w), line)?;
+            if l == 0 || l == i32::MIN {
+                return Err(PropertyDirectedReachabilityCheckpointError::InvalidLine(
+                    line,
+                ));
+            }
+            literals.push(Literal::from_dimacs_number(l));
+        }
+        Ok(literals)
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn get_definitions(&self) -> &[Definition] {
+        &self.definitions
+    }
+
+    pub fn get_deltas(&self) -> &[Vec<Clause>] {
+        &self.deltas
+    }
+
+    /// Parses a checkpoint from the text format described in the documentation of this struct.
+    pub fn parse(text: &str) -> Result<Self, PropertyDirectedReachabilityCheckpointError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.trim()))
+            .filter(|(_, l)| !l.is_empty());
+        match lines.next() {
+            Some((_, l)) if l == Self::HEADER => {}
+            _ => return Err(PropertyDirectedReachabilityCheckpointError::MissingHeader),
+        }
+
+        let mut r = Self {
+            depth: 0,
+            seed: 0,
+            weights: vec![],
+            definitions: vec![],
+            deltas: vec![vec![], vec![]],
+        };
+        for (line, l) in lines {
+            let mut words = l.split_whitespace();
+            match words.next() {
+                Some("depth") => r.depth = Self::parse_number(words.next(), line)?,
+                Some("frames") => {
+                    // the number of frames decides where F_INF is, so it must come before all the clauses
+                    let n: usize = Self::parse_number(words.next(), line)?;
+                    if n < 2 || r.deltas.iter().any(|d| !d.is_empty()) {
+                        return Err(PropertyDirectedReachabilityCheckpointError::InvalidLine(
+                            line,
+                        ));
+                    }
+                    r.deltas.resize(n, vec![]);
+                }
+                Some("seed") => r.seed = Self::parse_number(words.next(), line)?,
+                Some("weights") => {
+                    r.weights = words
+                        .map(|w| Self::parse_number(Some(w), line))
+                        .collect::<Result<Vec<f64>, _>>()?;
+                }
+                Some("definition") => {
+                    let variable: u32 = Self::parse_number(words.next(), line)?;
+                    let function = match words.next().map(DefinitionFunction::from_str) {
+                        Some(Ok(function)) => function,
+                        _ => {
+                            return Err(PropertyDirectedReachabilityCheckpointError::InvalidLine(
+                                line,
+                            ))
+                        }
+                    };
+                    let inputs = Self::parse_literals(words, line)?;
+                    r.definitions.push(Definition {
+                        variable: Variable::new(variable),
+                        function,
+                        inputs: SortedVecOfLiterals::from_sequence(inputs),
+                    });
+                }
+                Some("clause") => {
+                    let k = match words.next() {
+                        Some("inf") => r.deltas.len() - 1,
+                        w => {
+                            let k: usize = Self::parse_number(w, line)?;
+                            if k == 0 || k >= r.deltas.len() - 1 {
+                                return Err(
+                                    PropertyDirectedReachabilityCheckpointError::FrameOutOfRange(
+                                        line,
+                                    ),
+                                );
+                            }
+                            k
+                        }
+                    };
+                    let literals = Self::parse_literals(words, line)?;
+                    r.deltas[k].push(Clause::from_sequence(literals));
+                }
+                _ => {
+                    return Err(PropertyDirectedReachabilityCheckpointError::InvalidLine(
+                        line,
+                    ))
+                }
+            }
+        }
+        if r.deltas.len() < r.depth + 2 {
+            return Err(PropertyDirectedReachabilityCheckpointError::InconsistentDepth);
+        }
+
+        Ok(r)
+    }
+
+    /// Reads a checkpoint from a file.
+    pub fn from_path(path: &Path) -> Result<Self, PropertyDirectedReachabilityCheckpointError> {
+        let text =
+            fs::read_to_string(path).map_err(PropertyDirectedReachabilityCheckpointError::Io)?;
+        Self::parse(&text)
+    }
+
+    /// Writes the checkpoint to a file, if the file exists it will be overwritten.
+    /// The checkpoint is first written to a temporary file next to it, so that an interrupted
+    /// write does not destroy the previous checkpoint.
+    pub fn write(&self, path: &Path) -> Result<(), PropertyDirectedReachabilityCheckpointError> {
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+        fs::write(&temporary, self.to_string())
+            .and_then(|_| fs::rename(&temporary, path))
+            .map_err(PropertyDirectedReachabilityCheckpointError::Io)
+    }
+}
+
+impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager>
+    PropertyDirectedReachability<T, D>
+{
+    /// Captures the state of the run: the frames, the definitions, the depth and the variable weights.
+    /// The internal state of the random number generator cannot be extracted, so instead a new seed
+    /// is drawn from it and the generator is re-seeded with it. This way a run that is resumed from
+    /// the checkpoint continues with the same random choices as the run that created it.
+    /// Proof obligations are not saved, they are re-discovered after resuming.
+    pub fn get_checkpoint(&mut self) -> PropertyDirectedReachabilityCheckpoint {
+        let _timer = FunctionTimer::start(function!(), self.s.time_stats.clone());
+
+        let seed: u64 = self.s.rng.borrow_mut().gen();
+        *self.s.rng.borrow_mut() = StdRng::seed_from_u64(seed);
+
+        PropertyDirectedReachabilityCheckpoint {
+            depth: self.frames.depth(),
+            seed,
+            weights: self.s.weights.borrow().get_weights().to_vec(),
+            definitions: self.frames.get_definitions().to_owned(),
+            deltas: self.frames.get_frame_deltas(),
+        }
+    }
+
+    /// Saves the state of the run to a file, see `get_checkpoint`.
+    pub fn save_checkpoint(
+        &mut self,
+        path: &Path,
+    ) -> Result<(), PropertyDirectedReachabilityCheckpointError> {
+        self.get_checkpoint().write(path)
+    }
+
+    /// Makes the engine save a checkpoint to the path every `interval` while it runs, so that a
+    /// run that is killed from the outside can be resumed from its last checkpoint.
+    /// Checkpoints are saved between iterations, when no proof obligation is being blocked.
+    pub fn set_periodic_checkpoint(&mut self, path: &Path, interval: Duration) {
+        self.periodic_checkpoint = Some(PeriodicCheckpoint {
+            path: path.to_owned(),
+            interval,
+            last_save: Instant::now(),
+        });
+    }
+
+    pub(super) fn save_checkpoint_if_interval_passed(&mut self) {
+        let path = match &self.periodic_checkpoint {
+            Some(p) if p.last_save.elapsed() >= p.interval => p.path.to_owned(),
+            _ => return,
+        };
+        if let Err(e) = self.save_checkpoint(&path) {
+            if self.s.parameters.verbose {
+                println!("Error while saving checkpoint: {}", e);
+            }
+        }
+        if let Some(p) = self.periodic_checkpoint.as_mut() {
+            p.last_save = Instant::now();
+        }
+    }
+
+    /// Restores a checkpoint into an engine that has not started running yet.
+    /// The checkpoint is not trusted, every clause is re-validated against the transition system
+    /// before it is inserted, clauses that fail validation are dropped. When clauses are dropped
+    /// the restored depth might be lower than the one in the checkpoint.
+    /// Returns the number of clauses that were dropped.
+    pub fn restore_checkpoint(
+        &mut self,
+        checkpoint: &PropertyDirectedReachabilityCheckpoint,
+    ) -> Result<usize, PropertyDirectedReachabilityCheckpointError> {
+        let _timer = FunctionTimer::start(function!(), self.s.time_stats.clone());
+
+        if !self.frames.is_fresh() {
+            return Err(PropertyDirectedReachabilityCheckpointError::EngineAlreadyStarted);
+        }
+        let expected = self.s.weights.borrow().get_weights().len();
+        if checkpoint.weights.len() != expected {
+            return Err(
+                PropertyDirectedReachabilityCheckpointError::WeightCountMismatch(
+                    expected,
+                    checkpoint.weights.len(),
+                ),
+            );
+        }
+
+        self.frames
+            .restore_definitions(&checkpoint.definitions)
+            .map_err(PropertyDirectedReachabilityCheckpointError::InvalidDefinition)?;
+        let dropped = self.frames.restore_clauses(&checkpoint.deltas);
+
+        // frames below the depth must not intersect the bad states, this might not hold anymore
+        // if clauses were dropped so the depth is only restored up to the first such frame.
+        while self.frames.depth() < checkpoint.depth {
+            if self.frames.get_bad_cube(self.frames.depth()).is_some() {
+                break;
+            }
+            self.frames.increase_depth();
+        }
+
+        self.s.weights.borrow_mut().set_weights(&checkpoint.weights);
+        *self.s.rng.borrow_mut() = StdRng::seed_from_u64(checkpoint.seed);
+
+        self.print_progress_if_verbose("RESTORED");
+        Ok(dropped)
+    }
+}
+
+// ************************************************************************************************
+// printing
+// ************************************************************************************************
+
+impl fmt::Display for PropertyDirectedReachabilityCheckpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let literals_to_string = |literals: &mut dyn Iterator<Item = &Literal>| {
+            literals
+                .map(|l| l.get_dimacs_number().to_string())
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+
+        writeln!(f, "{}", Self::HEADER)?;
+        writeln!(f, "depth {}", self.depth)?;
+        writeln!(f, "frames {}", self.deltas.len())?;
+        writeln!(f, "seed {}", self.seed)?;
+        let weights: Vec<String> = self.weights.iter().map(|w| w.to_string()).collect();
+        writeln!(f, "weights {}", weights.join(" "))?;
+        for d in self.definitions.iter() {
+            writeln!(
+                f,
+                "definition {} {} {}",
+                d.variable.number(),
+                d.function,
+                literals_to_string(&mut d.inputs.iter())
+            )?;
+        }
+        let inf = self.deltas.len() - 1;
+        for (k, delta) in self.deltas.iter().enumerate() {
+            for c in delta.iter() {
+                let frame = if k == inf {
+                    "inf".to_string()
+                } else {
+                    k.to_string()
+                };
+                writeln!(f, "clause {} {}", frame, literals_to_string(&mut c.iter()))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PropertyDirectedReachabilityCheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Could not access the checkpoint file: {}", e),
+            Self::MissingHeader => write!(f, "The checkpoint does not start with a valid header."),
+            Self::InconsistentDepth => {
+                write!(
+                    f,
+                    "The checkpoint has fewer frames than its depth requires."
+                )
+            }
+            Self::InvalidLine(l) => write!(f, "Line {} of the checkpoint is invalid.", l),
+            Self::FrameOutOfRange(l) => {
+                write!(
+                    f,
+                    "Line {} of the checkpoint refers to a frame that does not exist.",
+                    l
+                )
+            }
+            Self::EngineAlreadyStarted => {
+                write!(
+                    f,
+                    "A checkpoint can only be restored before the engine starts."
+                )
+            }
+            Self::WeightCountMismatch(expected, found) => write!(
+                f,
+                "The checkpoint has {} variable weights but the model has {}.",
+                found, expected
+            ),
+            Self::InvalidDefinition(i) => write!(
+                f,
+                "Definition {} of the checkpoint cannot be re-created on this model.",
+                i
+            ),
+        }
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[test]
+fn test_checkpoint_and_restore() {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        engines::pdr::PropertyDirectedReachabilityParameters,
+        models::{AndInverterGraph, Circuit, FiniteStateTransitionSystem, Signal, TernaryValue},
+        solvers::{dd::OxiddBdd, sat::incremental::CaDiCalSolver},
+    };
+
+    // 2 latches that are flipped together by an input, the property is that they are always equal.
+    let aig = AndInverterGraph::new(
+        Signal::new(10),
+        1,
+        &[
+            (Signal::new(6).wire(true), TernaryValue::False),
+            (Signal::new(9).wire(true), TernaryValue::False),
+        ],
+        vec![],
+        vec![Signal::new(10).wire(false)],
+        vec![],
+        &[
+            (Signal::new(1).wire(false), Signal::new(2).wire(true)),
+            (Signal::new(1).wire(true), Signal::new(2).wire(false)),
+            (Signal::new(4).wire(true), Signal::new(5).wire(true)),
+            (Signal::new(1).wire(false), Signal::new(3).wire(true)),
+            (Signal::new(1).wire(true), Signal::new(3).wire(false)),
+            (Signal::new(7).wire(true), Signal::new(8).wire(true)),
+            (Signal::new(2).wire(false), Signal::new(3).wire(true)),
+        ],
+        String::new(),
+    )
+    .unwrap();
+    let circuit = Circuit::from_aig(&aig);
+    let fin_state = FiniteStateTransitionSystem::new(&circuit, false).unwrap();
+    let fin_state = Rc::new(RefCell::new(fin_state));
+    let new_engine = || {
+        PropertyDirectedReachability::<CaDiCalSolver, OxiddBdd>::new(
+            fin_state.to_owned(),
+            PropertyDirectedReachabilityParameters::new(),
+        )
+        .unwrap()
+    };
+    let sorted_deltas = |deltas: &[Vec<Clause>]| -> Vec<Vec<Clause>> {
+        deltas
+            .iter()
+            .map(|d| {
+                let mut d = d.to_owned();
+                d.sort();
+                d
+            })
+            .collect()
+    };
+
+    let mut pdr = new_engine();
+    assert!(pdr.prove().unwrap().is_ok());
+    let checkpoint = pdr.get_checkpoint();
+    assert!(checkpoint.get_deltas().iter().any(|d| !d.is_empty()));
+    let parsed = PropertyDirectedReachabilityCheckpoint::parse(&checkpoint.to_string()).unwrap();
+    assert_eq!(parsed, checkpoint);
+    assert!(matches!(
+        pdr.restore_checkpoint(&parsed),
+        Err(PropertyDirectedReachabilityCheckpointError::EngineAlreadyStarted)
+    ));
+
+    // all clauses are valid so nothing should be dropped
+    let mut restored = new_engine();
+    assert_eq!(restored.restore_checkpoint(&parsed).unwrap(), 0);
+    assert_eq!(restored.depth(), checkpoint.get_depth());
+    assert_eq!(
+        sorted_deltas(&restored.frames.get_frame_deltas()),
+        sorted_deltas(checkpoint.get_deltas())
+    );
+    assert!(restored.prove().unwrap().is_ok());
+
+    // a clause that does not hold in the initial state is dropped
+    let latch = Variable::new(circuit.get_latch_signals().peek()[0].number());
+    let text = format!(
+        "{}clause inf {}\n",
+        checkpoint,
+        latch.literal(false).get_dimacs_number()
+    );
+    let corrupted = PropertyDirectedReachabilityCheckpoint::parse(&text).unwrap();
+    let mut restored = new_engine();
+    assert_eq!(restored.restore_checkpoint(&corrupted).unwrap(), 1);
+    assert!(restored.prove().unwrap().is_ok());
+
+    // checkpoints that are saved while the engine runs can be resumed from
+    let path = std::env::temp_dir().join(format!("pdr_checkpoint_{}", std::process::id()));
+    let mut pdr = new_engine();
+    pdr.set_periodic_checkpoint(&path, Duration::ZERO);
+    assert!(pdr.prove().unwrap().is_ok());
+    let saved = PropertyDirectedReachabilityCheckpoint::from_path(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    let mut restored = new_engine();
+    assert_eq!(restored.restore_checkpoint(&saved).unwrap(), 0);
+    assert!(restored.prove().unwrap().is_ok());
+
+    assert!(matches!(
+        PropertyDirectedReachabilityCheckpoint::parse("depth 1\n"),
+        Err(PropertyDirectedReachabilityCheckpointError::MissingHeader)
+    ));
+}
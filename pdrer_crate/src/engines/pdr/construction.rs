@@ -7,12 +7,17 @@ use std::rc::Rc;
 
 use super::frames::Frames;
 use super::pdr_stats::PDRStats;
+use super::proof_obligations::scheduler::{
+    HighestWeightScheduler, InsertionOrderScheduler, ProofObligationScheduler, RandomScheduler,
+    SmallestCubeScheduler,
+};
 use super::proof_obligations::ProofObligations;
 use super::shared_objects::SharedObjects;
 use super::{
-    PropertyDirectedReachability, PropertyDirectedReachabilityError,
+    ProofObligationScheduling, PropertyDirectedReachability, PropertyDirectedReachabilityError,
     PropertyDirectedReachabilityParameters, PropertyDirectedReachabilitySolver, Weights,
 };
+use crate::formulas::Clause;
 use crate::models::{FiniteStateTransitionSystem, TimeStats};
 use crate::solvers::dd::DecisionDiagramManager;
 use rand::{rngs::StdRng, SeedableRng};
@@ -28,6 +33,17 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager>
     // helper functions
     // ********************************************************************************************
 
+    fn new_proof_obligation_scheduler(s: &SharedObjects) -> Box<dyn ProofObligationScheduler> {
+        match s.parameters.proof_obligation_scheduling {
+            ProofObligationScheduling::InsertionOrder => Box::new(InsertionOrderScheduler::new()),
+            ProofObligationScheduling::SmallestCube => Box::new(SmallestCubeScheduler::new()),
+            ProofObligationScheduling::HighestWeight => {
+                Box::new(HighestWeightScheduler::new(s.weights.clone()))
+            }
+            ProofObligationScheduling::Random => Box::new(RandomScheduler::new(s.rng.clone())),
+        }
+    }
+
     // pub(super) fn static_print(
     //     params: &PropertyDirectedReachabilityParameters,
     //     operation: &str,
@@ -63,11 +79,30 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager>
         };
 
         let frames = Frames::new(s.clone());
+        let proof_obligations =
+            ProofObligations::new_with_scheduler(Self::new_proof_obligation_scheduler(&s));
 
         Ok(Self {
             frames,
-            proof_obligations: ProofObligations::new(),
+            proof_obligations,
             s,
+            is_started: false,
+            periodic_checkpoint: None,
         })
     }
+
+    /// Seeds the engine with candidate clauses over the state variables, for example the invariant
+    /// of a proof of a previous version of the model. This must be called before `prove`.
+    /// Candidates that are inductive are inserted into F_INF, others are inserted into the
+    /// highest frame where they hold and the rest are dropped.
+    /// Returns the number of clauses that were inserted into F_INF and into other frames.
+    pub fn seed_lemmas(
+        &mut self,
+        candidates: Vec<Clause>,
+    ) -> Result<(usize, usize), PropertyDirectedReachabilityError> {
+        if !self.frames.is_fresh() {
+            return Err(PropertyDirectedReachabilityError::EngineAlreadyStarted);
+        }
+        Ok(self.frames.seed_clauses(candidates))
+    }
 }
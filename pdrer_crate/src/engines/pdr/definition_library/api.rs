@@ -73,6 +73,13 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager> Definitio
                         }
                     }
                 }
+                DefinitionFunction::Ite(_)
+                | DefinitionFunction::Maj3
+                | DefinitionFunction::TruthTable(_) => {
+                    if &d.inputs == inputs {
+                        return Some((i, false));
+                    }
+                }
             }
         }
         None
@@ -101,12 +108,7 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager> Definitio
         debug_assert!(!self.var_to_bdd.contains_key(&d.variable));
         debug_assert!(!d.inputs.is_empty());
 
-        let mut d_bdd = match &d.function {
-            DefinitionFunction::And => self.manager_ref.top(),
-            DefinitionFunction::Xor => self.manager_ref.bot(),
-        }
-        .ok()?;
-
+        let mut inputs = vec![];
         for x in d.inputs.iter() {
             let a = self.var_to_bdd.get(&x.variable())?;
             let a = if x.is_negated() {
@@ -114,13 +116,48 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager> Definitio
             } else {
                 a.to_owned()
             };
-
-            d_bdd = match d.function {
-                DefinitionFunction::And => self.manager_ref.apply_and(&d_bdd, &a).ok()?,
-                DefinitionFunction::Xor => self.manager_ref.apply_xor(&d_bdd, &a).ok()?,
-            };
+            inputs.push(a);
         }
 
+        let d_bdd = match d.function {
+            DefinitionFunction::And => {
+                let mut d_bdd = self.manager_ref.top().ok()?;
+                for a in inputs.iter() {
+                    d_bdd = self.manager_ref.apply_and(&d_bdd, a).ok()?;
+                }
+                d_bdd
+            }
+            DefinitionFunction::Xor => {
+                let mut d_bdd = self.manager_ref.bot().ok()?;
+                for a in inputs.iter() {
+                    d_bdd = self.manager_ref.apply_xor(&d_bdd, a).ok()?;
+                }
+                d_bdd
+            }
+            DefinitionFunction::Ite(_)
+            | DefinitionFunction::Maj3
+            | DefinitionFunction::TruthTable(_) => {
+                // shannon expansion of the truth table, one input at a time starting from the first
+                let table = d.function.get_truth_table(inputs.len());
+                let mut level = vec![];
+                for row in 0..(1 << inputs.len()) {
+                    level.push(if (table >> row) & 1 == 1 {
+                        self.manager_ref.top().ok()?
+                    } else {
+                        self.manager_ref.bot().ok()?
+                    });
+                }
+                for a in inputs.iter() {
+                    let mut next_level = vec![];
+                    for pair in level.chunks(2) {
+                        next_level.push(self.manager_ref.apply_ite(a, &pair[1], &pair[0]).ok()?);
+                    }
+                    level = next_level;
+                }
+                level.pop().unwrap()
+            }
+        };
+
         self.var_to_bdd.insert(d.variable, d_bdd);
         Some(())
     }
@@ -196,7 +233,8 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager> Definitio
             state_vars.len() / 10
         } else {
             1000
-        };
+        }
+        .min(s.parameters.memory_limit_mb);
         let mut manager_ref = D::new(state_vars.len(), 1, max_memory_in_mb);
         let mut var_to_bdd: FxHashMap<Variable, D::DecisionDiagram> = FxHashMap::default();
 
@@ -50,6 +50,14 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager> Frames<T,
         self.depth
     }
 
+    /// Returns true if the frames were not used yet, meaning that the depth is 0 and no clause
+    /// or definition was added.
+    pub fn is_fresh(&self) -> bool {
+        self.depth == 0
+            && self.get_definitions().is_empty()
+            && self.frames.iter().all(|f| f.get_delta().is_empty())
+    }
+
     pub fn len(&self) -> usize {
         self.frames.len()
     }
@@ -86,6 +94,13 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager> Frames<T,
         }
     }
 
+    /// Adds frames beyond the depth until frame k exists and is not F_INF.
+    pub fn add_frames_up_to(&mut self, k: usize) {
+        while self.frames.len() - 1 <= k {
+            self.new_frame();
+        }
+    }
+
     /// This function gets the inf frame, which is the last frame in the list of frames.
     /// This frame is guaranteed to be inductive.
     pub fn get_f_inf(&self) -> &Frame<D> {
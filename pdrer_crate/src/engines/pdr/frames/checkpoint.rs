@@ -0,0 +1,155 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use super::Frames;
+use crate::{
+    engines::pdr::PropertyDirectedReachabilitySolver,
+    formulas::{Clause, Variable},
+    function,
+    models::{time_stats::function_timer::FunctionTimer, Definition},
+    solvers::dd::DecisionDiagramManager,
+};
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager> Frames<T, D> {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    /// Checks that a variable is either a state variable or an already defined extension variable.
+    fn is_variable_known(&self, v: Variable) -> bool {
+        if self.definition_library.is_extension_variable(v) {
+            self.definition_library.iter().any(|d| d.variable == v)
+        } else {
+            self.s.fin_state.borrow().is_state_variable(v)
+        }
+    }
+
+    pub(super) fn is_clause_well_formed(&self, clause: &Clause) -> bool {
+        let variables: Vec<Variable> = clause.iter().map(|l| l.variable()).collect();
+        !clause.is_empty()
+            && variables.windows(2).all(|w| w[0] != w[1])
+            && variables.iter().all(|v| self.is_variable_known(*v))
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Returns the clauses of every frame delta, the first is INIT and the last is F_INF.
+    pub fn get_frame_deltas(&self) -> Vec<Vec<Clause>> {
+        self.get_deltas()
+    }
+
+    /// Re-creates definitions that were saved from a previous run, the definitions must be
+    /// provided in the order they were created so that each one gets the same variable again.
+    /// Must be called before any frame is added.
+    /// On failure returns the index of the first definition that could not be re-created.
+    pub fn restore_definitions(&mut self, definitions: &[Definition]) -> Result<(), usize> {
+        let _timer = FunctionTimer::start(function!(), self.s.time_stats.clone());
+        debug_assert_eq!(self.frames.len(), 2);
+
+        let lib_size_before = self.definition_library.len();
+        for (i, d) in definitions.iter().enumerate() {
+            let is_valid = d.inputs.len() >= 2
+                && d.function.is_number_of_inputs_supported(d.inputs.len())
+                && d.inputs
+                    .iter()
+                    .all(|l| self.is_variable_known(l.variable()))
+                && !self.is_variable_known(d.variable);
+            if !is_valid {
+                return Err(i);
+            }
+            match self
+                .definition_library
+                .add_definition(d.function, d.inputs.to_owned())
+            {
+                Ok((x, false)) if self.definition_library.at(x).variable == d.variable => {}
+                _ => return Err(i),
+            }
+        }
+
+        for i in lib_size_before..self.definition_library.len() {
+            let d = &self.definition_library.get_definitions()[i];
+            self.solvers.add_new_definition(d);
+        }
+        self.solvers.rest_solvers(self.get_deltas());
+
+        Ok(())
+    }
+
+    /// Inserts clauses that were saved from a previous run into the frames, the deltas must have
+    /// the same layout as the ones returned by `get_frame_deltas`, frames are added if needed.
+    /// Every clause is checked to be satisfied by all initial states and to be guaranteed after
+    /// a transition from the frame before it. Since clauses depend on each other, this is done
+    /// until a fix-point is reached, each round a clause that fails the check is moved one frame
+    /// lower (clauses from F_INF are moved to the highest frame) or dropped if it is in the first frame.
+    /// Returns the number of clauses that were dropped.
+    pub fn restore_clauses(&mut self, deltas: &[Vec<Clause>]) -> usize {
+        let _timer = FunctionTimer::start(function!(), self.s.time_stats.clone());
+        debug_assert!(self.frames.iter().all(|f| f.is_empty()));
+
+        // the frames are added here, the depth is increased by the caller
+        while self.frames.len() < deltas.len() {
+            self.new_frame();
+        }
+        debug_assert_eq!(deltas.len(), self.frames.len());
+
+        let inf = self.frames.len() - 1;
+        let mut dropped = 0;
+        let mut candidates = vec![vec![]; deltas.len()];
+        for (k, delta) in deltas.iter().enumerate().skip(1) {
+            for clause in delta.iter() {
+                let clause = self
+                    .definition_library
+                    .make_clause_canonical(clause.to_owned());
+                if self.is_clause_well_formed(&clause)
+                    && self.is_clause_satisfied_by_all_initial_states(&clause)
+                {
+                    candidates[k].push(clause);
+                } else {
+                    dropped += 1;
+                }
+            }
+        }
+
+        loop {
+            self.solvers.rest_solvers(candidates.to_owned());
+            let mut failed = vec![];
+            for (k, delta) in candidates.iter_mut().enumerate().skip(1) {
+                let before = if k == inf { k } else { k - 1 };
+                let mut i = 0;
+                while i < delta.len() {
+                    if self
+                        .solvers
+                        .is_clause_guaranteed_after_transition_if_assumed(before, &delta[i])
+                    {
+                        i += 1;
+                    } else {
+                        failed.push((k, delta.swap_remove(i)));
+                    }
+                }
+            }
+            if failed.is_empty() {
+                break;
+            }
+            for (k, clause) in failed {
+                let lower = if k == inf { inf - 1 } else { k - 1 };
+                if lower == 0 {
+                    dropped += 1;
+                } else {
+                    candidates[lower].push(clause);
+                }
+            }
+        }
+
+        self.insert_validated_clauses(candidates);
+
+        debug_assert!(self.regression_check());
+        dropped
+    }
+}
@@ -2,11 +2,16 @@
 // use
 // ************************************************************************************************
 
+use fxhash::{FxHashMap, FxHashSet};
+
 use super::Frames;
 use crate::engines::pdr::definition_library::DefinitionLibrary;
 use crate::engines::pdr::PropertyDirectedReachabilitySolver;
-// use crate::formulas::Clause;
+use crate::formulas::{Literal, Variable};
+use crate::function;
 use crate::models::definition::Definition;
+use crate::models::time_stats::function_timer::FunctionTimer;
+use crate::models::SortedVecOfLiterals;
 use crate::solvers::dd::DecisionDiagramManager;
 
 // ************************************************************************************************
@@ -46,4 +51,119 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager> Frames<T,
     pub fn get_definitions_lib(&self) -> &DefinitionLibrary<T, D> {
         &self.definition_library
     }
+
+    /// Defines an extension variable for each of the internal signals of the circuit that are
+    /// functions of the state variables alone, see
+    /// `get_definitions_of_internal_signals_over_state_variables`.
+    /// Returns the number of extension variables that were added.
+    pub fn add_definitions_of_internal_signals(&mut self, max_cone_size: usize) -> usize {
+        let _timer = FunctionTimer::start(function!(), self.s.time_stats.clone());
+        let definitions = self
+            .s
+            .fin_state
+            .borrow()
+            .get_definitions_of_internal_signals_over_state_variables(max_cone_size);
+
+        let lib_size_before = self.definition_library.len();
+        let internal_variables: FxHashSet<Variable> =
+            definitions.iter().map(|d| d.variable).collect();
+        let mut internal_to_extension: FxHashMap<Variable, Literal> = FxHashMap::default();
+        for d in definitions {
+            // inputs that are internal variables which were skipped are not over the state
+            // variables, so the whole fan-out of a skipped definition is skipped.
+            let inputs: Option<Vec<Literal>> = d
+                .inputs
+                .iter()
+                .map(|l| match internal_to_extension.get(&l.variable()) {
+                    Some(x) => Some(x.negate_if_true(l.is_negated())),
+                    None if internal_variables.contains(&l.variable()) => None,
+                    None => Some(*l),
+                })
+                .collect();
+            let inputs = match inputs {
+                Some(i) => i,
+                None => continue,
+            };
+            let (function, inputs) = d.function.sort_inputs(inputs);
+            if !SortedVecOfLiterals::are_variables_sorted_and_unique(&inputs) {
+                continue;
+            }
+            let (i, is_negated) = self
+                .definition_library
+                .add_definition(function, SortedVecOfLiterals::from_ordered_set(inputs))
+                .unwrap();
+            let x = self.definition_library.at(i).variable.literal(is_negated);
+            internal_to_extension.insert(d.variable, x);
+        }
+
+        for i in lib_size_before..self.definition_library.len() {
+            let d = &self.definition_library.get_definitions()[i];
+            self.solvers.add_new_definition(d);
+        }
+        self.solvers.rest_solvers(self.get_deltas());
+
+        self.definition_library.len() - lib_size_before
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[test]
+fn test_definitions_of_internal_signals_skip_dependent_signals() {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        engines::pdr::{PropertyDirectedReachability, PropertyDirectedReachabilityParameters},
+        models::{AndInverterGraph, Circuit, FiniteStateTransitionSystem, Signal, TernaryValue},
+        solvers::{dd::OxiddBdd, sat::incremental::CaDiCalSolver},
+    };
+
+    // 1 input, 2 latches, 4 = 2 & 3 and 5 = 2 & 3 get the same extension variable, so
+    // 6 = 4 & !5 cannot be defined and neither can 7 = 6 & 2 which is bad.
+    let aig = AndInverterGraph::new(
+        Signal::new(7),
+        1,
+        &[
+            (Signal::new(1).wire(false), TernaryValue::False),
+            (Signal::new(1).wire(true), TernaryValue::False),
+        ],
+        vec![],
+        vec![Signal::new(7).wire(false)],
+        vec![],
+        &[
+            (Signal::new(2).wire(false), Signal::new(3).wire(false)),
+            (Signal::new(2).wire(false), Signal::new(3).wire(false)),
+            (Signal::new(4).wire(false), Signal::new(5).wire(true)),
+            (Signal::new(6).wire(false), Signal::new(2).wire(false)),
+        ],
+        String::new(),
+    )
+    .unwrap();
+    let circuit = Circuit::from_aig(&aig);
+    let fin_state = FiniteStateTransitionSystem::new(&circuit, false).unwrap();
+    assert_eq!(
+        fin_state
+            .get_definitions_of_internal_signals_over_state_variables(8)
+            .len(),
+        4
+    );
+    let state_variables = fin_state.get_state_variables().to_owned();
+    let mut pdr = PropertyDirectedReachability::<CaDiCalSolver, OxiddBdd>::new(
+        Rc::new(RefCell::new(fin_state)),
+        PropertyDirectedReachabilityParameters::new(),
+    )
+    .unwrap();
+
+    assert_eq!(pdr.frames.add_definitions_of_internal_signals(8), 1);
+    let definitions = pdr.frames.get_definitions();
+    for d in definitions.iter() {
+        for l in d.inputs.iter() {
+            assert!(
+                state_variables.contains(&l.variable())
+                    || definitions.iter().any(|x| x.variable == l.variable())
+            );
+        }
+    }
 }
@@ -417,6 +417,8 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager> Frames<T,
                 and_pattern: true,
                 xor_pattern: true,
                 half_adder_pattern: false,
+                ite_pattern: self.s.parameters.er_ite,
+                maj3_pattern: self.s.parameters.er_maj3,
             },
         );
 
@@ -434,6 +436,8 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager> Frames<T,
                         BVA3Pattern::AndPattern(..) => 0,
                         BVA3Pattern::XorPattern(..) => r.len(),
                         BVA3Pattern::HalfAdderPattern(..) => r.len() * 2,
+                        BVA3Pattern::IfThenElsePattern(..) => 0,
+                        BVA3Pattern::MajorityPattern(..) => r.len(),
                     }
             })
             .unwrap();
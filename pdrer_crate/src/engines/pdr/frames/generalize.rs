@@ -120,6 +120,12 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager> Frames<T,
                         (true, false) | (false, true) => (d.variable.literal(true), r),
                     }
                 }
+                DefinitionFunction::Ite(_)
+                | DefinitionFunction::Maj3
+                | DefinitionFunction::TruthTable(_) => {
+                    // these are only introduced through propagation
+                    continue;
+                }
             };
 
             let mut clause_clone = clause.clone();
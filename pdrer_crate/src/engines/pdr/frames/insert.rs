@@ -109,6 +109,26 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager> Frames<T,
         false
     }
 
+    /// Inserts clauses that were validated together, where each clause was checked against the
+    /// frames that already contain all the given clauses, so they cannot be inserted one at a time.
+    /// Redundant clauses are skipped.
+    pub(super) fn insert_validated_clauses(&mut self, deltas: Vec<Vec<Clause>>) {
+        debug_assert_eq!(deltas.len(), self.frames.len());
+
+        // insert from the highest frame and the shortest clause so that redundant clauses are skipped
+        for (k, mut delta) in deltas.into_iter().enumerate().skip(1).rev() {
+            delta.sort_by_key(|c| c.len());
+            for clause in delta {
+                let de = self.make_delta_element(clause);
+                if !self.is_clause_redundant(&de, k) {
+                    self.frames[k].push_to_delta_and_increment_hash(de);
+                }
+            }
+        }
+        self.solvers.rest_solvers(self.get_deltas());
+        self.lowest_frame_that_was_updated_since_last_propagate = 1;
+    }
+
     // ********************************************************************************************
     // helper functions
     // ********************************************************************************************
@@ -39,6 +39,7 @@ pub struct Frames<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManag
 
 pub mod basic;
 pub mod check;
+pub mod checkpoint;
 pub mod complex;
 pub mod definitions;
 pub mod extension_variables;
@@ -48,6 +49,7 @@ pub mod insert;
 pub mod propagate;
 pub mod propagate_f_inf;
 pub mod sat_calls;
+pub mod seed;
 
 // ************************************************************************************************
 // re-exports of structs in these modules to simplify paths for other imports
@@ -40,13 +40,16 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager> Frames<T,
         let mut c1 = c.peek().peek().clone();
         let r = c1.remove(el);
 
-        let a = d.inputs.peek().peek()[0];
-        let b = d.inputs.peek().peek()[1];
-
         match d.function {
             DefinitionFunction::And => {
                 if el.is_negated() {
-                    unreachable!("The clause should have already been backwards propagated.");
+                    // only possible when the inputs of an AND include a negated AND, as may
+                    // happen with definitions of circuit signals.
+                    let mut c = c1;
+                    for x in d.inputs.iter() {
+                        c.insert(!*x);
+                    }
+                    vec![c]
                 } else {
                     d.inputs
                         .iter()
@@ -66,6 +69,8 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager> Frames<T,
                 let mut c2 = c1.clone();
 
                 debug_assert!(d.inputs.len() == 2);
+                let a = d.inputs.peek().peek()[0];
+                let b = d.inputs.peek().peek()[1];
                 if el.is_negated() {
                     c1.insert(a);
                     c1.insert(!b);
@@ -81,6 +86,23 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager> Frames<T,
                     vec![c1, c2]
                 }
             }
+            DefinitionFunction::Ite(_)
+            | DefinitionFunction::Maj3
+            | DefinitionFunction::TruthTable(_) => {
+                // (el \/ ...) <=> (c_1 \/ ...) ^ ... ^ (c_n \/ ...)
+                // where c_1 ^ ... ^ c_n <=> el
+                debug_assert!(r);
+                d.get_clauses_equivalent_to_literal(el.is_negated())
+                    .into_iter()
+                    .map(|clause| {
+                        let mut c = c1.clone();
+                        for l in clause.iter() {
+                            c.insert(*l);
+                        }
+                        c
+                    })
+                    .collect()
+            }
         }
     }
 
@@ -151,7 +173,12 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager> Frames<T,
             return None;
         }
 
-        let clause = Clause::from_ordered_set(clause.peek().to_owned());
+        let clause = self
+            .definition_library
+            .make_clause_canonical(Clause::from_ordered_set(clause.peek().to_owned()));
+        if !SortedVecOfLiterals::are_variables_sorted_and_unique(clause.peek().peek().peek()) {
+            return None;
+        }
 
         self.definition_library
             .ternary_propagation(clause.to_owned())?;
@@ -0,0 +1,177 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use super::Frames;
+use crate::{
+    engines::pdr::{solvers::solvers_api::build_transition, PropertyDirectedReachabilitySolver},
+    formulas::{Clause, CNF},
+    function,
+    models::time_stats::function_timer::FunctionTimer,
+    solvers::{dd::DecisionDiagramManager, sat::incremental::CaDiCalSolver},
+};
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager> Frames<T, D> {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn get_inductive_subset_of_candidates(&self, candidates: Vec<Clause>) -> Vec<Clause> {
+        let fin_state = self.s.fin_state.borrow();
+        let mut cnf = build_transition(
+            &fin_state,
+            self.s.parameters.assume_constraints_in_second_cycle,
+            false,
+        );
+        cnf.append(CNF::from_sequence(
+            self.get_cnf_of_frame(self.frames.len() - 1),
+        ));
+        fin_state.get_inductive_subset_relative_to_cnf::<CaDiCalSolver>(candidates, &cnf, false)
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Inserts candidate clauses from an external source (for example the invariant of a previous
+    /// version of the model) before the frames are used.
+    /// Candidates that are not satisfied by all initial states are dropped, the largest subset
+    /// of the rest that is inductive is inserted into F_INF. Every other candidate is inserted
+    /// into the highest frame where it is guaranteed after a transition from the frame before it,
+    /// frames beyond the depth are added if needed.
+    /// Returns the number of clauses that were inserted into F_INF and into other frames.
+    pub fn seed_clauses(&mut self, candidates: Vec<Clause>) -> (usize, usize) {
+        let _timer = FunctionTimer::start(function!(), self.s.time_stats.clone());
+        debug_assert!(self.is_fresh());
+
+        let mut filtered = vec![];
+        for clause in candidates {
+            let clause = self.definition_library.make_clause_canonical(clause);
+            if self.is_clause_well_formed(&clause)
+                && self.is_clause_satisfied_by_all_initial_states(&clause)
+            {
+                filtered.push(clause);
+            }
+        }
+        filtered.sort_unstable();
+        filtered.dedup();
+
+        let inductive = self.get_inductive_subset_of_candidates(filtered.to_owned());
+        let inserted_to_f_inf = inductive.len();
+        let mut deltas = vec![vec![]; self.frames.len()];
+        *deltas.last_mut().unwrap() = inductive.to_owned();
+        self.insert_validated_clauses(deltas);
+
+        // every candidate is moved one frame up while it is guaranteed after a transition,
+        // once all the candidates of a frame are moved up they are inductive together so we stop.
+        let mut sorted_inductive = inductive;
+        sorted_inductive.sort_unstable();
+        let mut level: Vec<Clause> = filtered
+            .into_iter()
+            .filter(|c| sorted_inductive.binary_search(c).is_err())
+            .collect();
+        level.sort_by_key(|c| c.len());
+        let mut inserted_to_frames = 0;
+        let mut k = 1;
+        loop {
+            let level_size = level.len();
+            level.retain(|c| self.is_clause_guaranteed_after_transition_if_assumed(c, k - 1));
+            if level.is_empty() {
+                break;
+            }
+            if k == self.frames.len() - 1 {
+                self.new_frame();
+            }
+            let mut next_level = vec![];
+            for clause in level {
+                let de = self.make_delta_element(clause.to_owned());
+                if !self.is_clause_redundant(&de, k) {
+                    self.insert_clause_to_exact_frame(de, k, false);
+                    next_level.push(clause);
+                }
+            }
+            if k == 1 {
+                inserted_to_frames = next_level.len();
+            } else if next_level.len() == level_size {
+                break;
+            }
+            level = next_level;
+            k += 1;
+        }
+
+        debug_assert!(self.regression_check());
+        (inserted_to_f_inf, inserted_to_frames)
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[test]
+fn test_seed_clauses() {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        engines::pdr::{PropertyDirectedReachability, PropertyDirectedReachabilityParameters},
+        models::{AndInverterGraph, Circuit, FiniteStateTransitionSystem, Signal, TernaryValue},
+        solvers::dd::OxiddBdd,
+    };
+
+    // a shift register of 3 latches that shifts in 1, the property is that c3 implies c2.
+    let aig = AndInverterGraph::new(
+        Signal::new(4),
+        0,
+        &[
+            (Signal::new(0).wire(true), TernaryValue::False),
+            (Signal::new(1).wire(false), TernaryValue::False),
+            (Signal::new(2).wire(false), TernaryValue::False),
+        ],
+        vec![],
+        vec![Signal::new(4).wire(false)],
+        vec![],
+        &[(Signal::new(3).wire(false), Signal::new(2).wire(true))],
+        String::new(),
+    )
+    .unwrap();
+    let circuit = Circuit::from_aig(&aig);
+    let fin_state = FiniteStateTransitionSystem::new(&circuit, false).unwrap();
+    let c: Vec<_> = (1..=3)
+        .map(|i| fin_state.convert_signal_to_variable(Signal::new(i)))
+        .collect();
+    let mut pdr = PropertyDirectedReachability::<CaDiCalSolver, OxiddBdd>::new(
+        Rc::new(RefCell::new(fin_state)),
+        PropertyDirectedReachabilityParameters::new(),
+    )
+    .unwrap();
+
+    let candidates = vec![
+        // inductive together, the duplicate is only inserted once
+        Clause::from_sequence(vec![c[2].literal(true), c[1].literal(false)]),
+        Clause::from_sequence(vec![c[1].literal(true), c[0].literal(false)]),
+        Clause::from_sequence(vec![c[2].literal(true), c[1].literal(false)]),
+        // hold for 1 and 2 cycles
+        Clause::from_sequence(vec![c[1].literal(true)]),
+        Clause::from_sequence(vec![c[2].literal(true)]),
+        // does not hold in the initial state
+        Clause::from_sequence(vec![c[0].literal(false)]),
+        // does not hold after 1 cycle
+        Clause::from_sequence(vec![c[0].literal(true)]),
+    ];
+    assert_eq!(pdr.seed_lemmas(candidates).unwrap(), (2, 2));
+    assert_eq!(pdr.frames.get_f_inf().len(), 2);
+    assert_eq!(
+        pdr.frames.at(1).get_delta_clauses_cloned(),
+        vec![Clause::from_sequence(vec![c[1].literal(true)])]
+    );
+    assert_eq!(
+        pdr.frames.at(2).get_delta_clauses_cloned(),
+        vec![Clause::from_sequence(vec![c[2].literal(true)])]
+    );
+    assert!(pdr.seed_lemmas(vec![]).is_err());
+    assert!(pdr.prove().unwrap().is_ok());
+}
@@ -17,12 +17,14 @@ pub type Weights = VariableWeights;
 #[derive(Debug)]
 pub enum PropertyDirectedReachabilityError {
     ConstraintsNotSupported,
+    EngineAlreadyStarted,
 }
 
 #[derive(Debug)]
 pub enum PropertyDirectedReachabilityProofError {
-    MaxDepthReached,
-    TimeOutReached,
+    MaxDepthReached(PropertyDirectedReachabilityPartialResult),
+    TimeOutReached(PropertyDirectedReachabilityPartialResult),
+    MemoryLimitReached(PropertyDirectedReachabilityPartialResult),
 }
 
 // ************************************************************************************************
@@ -38,19 +40,26 @@ pub struct PropertyDirectedReachability<
     pub proof_obligations: ProofObligations,
     /// objects that are shared throughout the PDR algorithm
     pub s: SharedObjects,
+    /// true once the proof started, the checks before the first iteration are done only once
+    is_started: bool,
+    /// saves checkpoints while the proof runs, if set
+    periodic_checkpoint: Option<checkpoint::PeriodicCheckpoint>,
 }
 
 // ************************************************************************************************
 // rust submodule declaration, they get searched in their respective file  names
 // ************************************************************************************************
 
+pub mod bidirectional;
 pub mod block_cube;
+pub mod checkpoint;
 pub mod construction;
 pub mod definition_library;
 pub mod delta_element;
 pub mod frame;
 pub mod frames;
 pub mod parameters;
+pub mod partial_result;
 pub mod pdr_stats;
 pub mod proof_obligations;
 pub mod prove;
@@ -64,7 +73,11 @@ pub mod variable_weights;
 // ************************************************************************************************
 
 use self::frames::Frames;
-pub use parameters::PropertyDirectedReachabilityParameters;
+pub use bidirectional::{
+    BidirectionalPropertyDirectedReachability, PropertyDirectedReachabilityDirection,
+};
+pub use parameters::{ProofObligationScheduling, PropertyDirectedReachabilityParameters};
+pub use partial_result::PropertyDirectedReachabilityPartialResult;
 use proof_obligations::ProofObligations;
 use shared_objects::SharedObjects;
 pub use solvers::PropertyDirectedReachabilitySolver;
@@ -25,6 +25,19 @@ pub enum AnalysisTiming {
     Skip,
 }
 
+/// The order in which proof obligations of the same frame are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofObligationScheduling {
+    /// In the order they were pushed.
+    InsertionOrder,
+    /// Obligations with fewer literals first.
+    SmallestCube,
+    /// Obligations whose variables have the highest weights first.
+    HighestWeight,
+    /// In a random order.
+    Random,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct PropertyDirectedReachabilityParameters {
     /// Random seed for the random number generator, this is used to generate the seeds
@@ -37,6 +50,9 @@ pub struct PropertyDirectedReachabilityParameters {
     pub timeout: Duration,
     /// The maximum depth that the algorithm will go to.
     pub max_depth: usize,
+    /// The maximum resident memory of the process in MB, when it is reached the algorithm stops.
+    /// This also bounds the memory of the decision diagram managers.
+    pub memory_limit_mb: usize,
 
     /// If true, then the algorithm will print out information about what it is doing (similar to verbose settings in other engines).
     pub verbose: bool,
@@ -54,6 +70,10 @@ pub struct PropertyDirectedReachabilityParameters {
     pub should_print_largest_inductive_cycle_progress: bool,
     /// If true, then the algorithm will print out debug information about the BVA run.
     pub should_print_bva_debug_information: bool,
+    /// If true, then the statistics of the run are printed as a single line JSON object when the run ends.
+    pub should_print_json_stats: bool,
+    /// The time between printing the statistics of the run as a single line JSON object during the run.
+    pub json_stats_interval: Duration,
 
     /// The minimum length of a clause that is needed to be able to generalize it.
     /// (This is used to prevent generalizing small clauses).
@@ -112,6 +132,15 @@ pub struct PropertyDirectedReachabilityParameters {
     pub er_fp_for_f_inf: bool,
     /// Perform the implication check correctly by not only doing syntactic subsumption but also BDDs
     pub er_impl: bool,
+    /// If true, then extension variables can also be defined as an if-then-else of 3 literals.
+    pub er_ite: bool,
+    /// If true, then extension variables can also be defined as the majority of 3 literals.
+    pub er_maj3: bool,
+    /// If true, then internal signals of the circuit that are functions of the latches alone are
+    /// defined as extension variables before the run starts.
+    pub er_circuit_signals: bool,
+    /// The maximum number of gates in the cone of an internal signal for it to be defined.
+    pub er_circuit_signals_max_cone_size: usize,
 
     // /// Controls how the algorithm will generalize with extension variables.
     // pub generalize_with_extension_variables: bool,
@@ -132,6 +161,20 @@ pub struct PropertyDirectedReachabilityParameters {
     pub generalize_using_ctg: bool,
     pub generalize_using_ctg_max_depth: usize,
     pub generalize_using_ctg_max_ctgs: usize,
+
+    /// The order in which proof obligations of the same frame are handled.
+    pub proof_obligation_scheduling: ProofObligationScheduling,
+    /// If true, then a proof obligation that was blocked at the depth is pushed again to the frame
+    /// after the depth (as done in IC3ref) instead of being dropped. Such obligations are handled
+    /// as soon as the depth is increased, which allows finding deeper counterexamples sooner.
+    pub re_push_proof_obligations_beyond_depth: bool,
+
+    /// If true, then the invariant that is found is minimized before it is returned, by removing
+    /// clauses that are not needed for it to be inductive and safe, and definitions that are unused.
+    pub minimize_proof: bool,
+    /// If true, then minimizing the invariant also removes literals from its clauses.
+    /// This does nothing if minimize_proof is false.
+    pub minimize_proof_shrink_literals: bool,
 }
 
 // ************************************************************************************************
@@ -144,6 +187,7 @@ impl PropertyDirectedReachabilityParameters {
         start_time: None,
         timeout: Duration::MAX,
         max_depth: usize::MAX,
+        memory_limit_mb: usize::MAX,
 
         verbose: false,
         should_print_time_stats_during_run: false,
@@ -153,6 +197,8 @@ impl PropertyDirectedReachabilityParameters {
         should_print_when_extension_variable_is_added: false,
         should_print_largest_inductive_cycle_progress: false,
         should_print_bva_debug_information: false,
+        should_print_json_stats: false,
+        json_stats_interval: Duration::MAX,
 
         minimum_clause_length_to_generalize: 2,
         decay: 0.9,
@@ -181,6 +227,10 @@ impl PropertyDirectedReachabilityParameters {
         er_fp: true,
         er_fp_for_f_inf: true,
         er_impl: true,
+        er_ite: false,
+        er_maj3: false,
+        er_circuit_signals: false,
+        er_circuit_signals_max_cone_size: 8,
         // generalize_with_extension_variables: true,
         // re_write_frame_when_bva_finds_simplification: true,
         // delta_in_frame_size_to_call_condense: 8,
@@ -191,6 +241,12 @@ impl PropertyDirectedReachabilityParameters {
         generalize_using_ctg: false,
         generalize_using_ctg_max_depth: 1,
         generalize_using_ctg_max_ctgs: 3,
+
+        proof_obligation_scheduling: ProofObligationScheduling::InsertionOrder,
+        re_push_proof_obligations_beyond_depth: false,
+
+        minimize_proof: false,
+        minimize_proof_shrink_literals: false,
     };
 
     pub fn new() -> Self {
@@ -238,6 +294,7 @@ impl fmt::Display for PropertyDirectedReachabilityParameters {
 
         write_filed!(self, table, self.timeout.as_secs_f32());
         write_filed!(self, table, self.max_depth);
+        write_filed!(self, table, self.memory_limit_mb);
 
         write_filed!(self, table, self.verbose);
         write_filed!(self, table, self.should_print_time_stats_during_run);
@@ -255,6 +312,8 @@ impl fmt::Display for PropertyDirectedReachabilityParameters {
             self.should_print_largest_inductive_cycle_progress
         );
         write_filed!(self, table, self.should_print_bva_debug_information);
+        write_filed!(self, table, self.should_print_json_stats);
+        write_filed!(self, table, self.json_stats_interval.as_secs_f32());
 
         write_filed!(self, table, self.minimum_clause_length_to_generalize);
         write_filed!(self, table, self.decay);
@@ -287,6 +346,10 @@ impl fmt::Display for PropertyDirectedReachabilityParameters {
         write_filed!(self, table, self.er_fp);
         write_filed!(self, table, self.er_fp_for_f_inf);
         write_filed!(self, table, self.er_impl);
+        write_filed!(self, table, self.er_ite);
+        write_filed!(self, table, self.er_maj3);
+        write_filed!(self, table, self.er_circuit_signals);
+        write_filed!(self, table, self.er_circuit_signals_max_cone_size);
         // write_filed!(self, table, self.generalize_with_extension_variables);
         // write_filed!(
         //     self,
@@ -302,6 +365,23 @@ impl fmt::Display for PropertyDirectedReachabilityParameters {
         write_filed!(self, table, self.generalize_using_ctg_max_depth);
         write_filed!(self, table, self.generalize_using_ctg_max_ctgs);
 
+        write_filed!(self, table, self.proof_obligation_scheduling);
+        write_filed!(self, table, self.re_push_proof_obligations_beyond_depth);
+
+        write_filed!(self, table, self.minimize_proof);
+        write_filed!(self, table, self.minimize_proof_shrink_literals);
+
         writeln!(f, "{}", table)
     }
 }
+
+impl fmt::Display for ProofObligationScheduling {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProofObligationScheduling::InsertionOrder => write!(f, "insertion-order"),
+            ProofObligationScheduling::SmallestCube => write!(f, "smallest-cube"),
+            ProofObligationScheduling::HighestWeight => write!(f, "highest-weight"),
+            ProofObligationScheduling::Random => write!(f, "random"),
+        }
+    }
+}
@@ -0,0 +1,202 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::fmt;
+
+use super::{PropertyDirectedReachability, PropertyDirectedReachabilitySolver};
+use crate::{
+    formulas::Clause,
+    models::Definition,
+    solvers::{dd::DecisionDiagramManager, sat::incremental::IncrementalSatSolver},
+};
+
+// ************************************************************************************************
+// struct
+// ************************************************************************************************
+
+/// What is known when a PDR run stops before the proof is closed.
+///
+/// The clauses are over the state variables and the extension variables, the extension variables
+/// are mapped the same way they are mapped in a `Proof`, and are defined by `definitions`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyDirectedReachabilityPartialResult {
+    /// All the states that are reachable in at most this many transitions satisfy the property,
+    /// `None` if not even the initial states were checked.
+    bounded_safety: Option<usize>,
+    /// The clauses of every frame delta, the first is INIT, F_INF is not included.
+    /// Frame k is the conjunction of the deltas k and above, and of F_INF.
+    deltas: Vec<Vec<Clause>>,
+    /// The clauses that hold in every reachable state.
+    f_inf: Vec<Clause>,
+    definitions: Vec<Definition>,
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl PropertyDirectedReachabilityPartialResult {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    /// A partial result without clauses, for runs whose clauses are not over the variables of
+    /// the model (for example runs on the reversed circuit).
+    pub(super) fn from_bounded_safety(bounded_safety: Option<usize>) -> Self {
+        Self {
+            bounded_safety,
+            deltas: vec![],
+            f_inf: vec![],
+            definitions: vec![],
+        }
+    }
+
+    /// Raises the bounded safety to the given one if it is higher.
+    pub(super) fn add_bounded_safety(&mut self, bounded_safety: Option<usize>) {
+        self.bounded_safety = self.bounded_safety.max(bounded_safety);
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    pub fn get_bounded_safety(&self) -> Option<usize> {
+        self.bounded_safety
+    }
+
+    pub fn get_deltas(&self) -> &[Vec<Clause>] {
+        &self.deltas
+    }
+
+    pub fn get_f_inf(&self) -> &[Clause] {
+        &self.f_inf
+    }
+
+    pub fn get_definitions(&self) -> &[Definition] {
+        &self.definitions
+    }
+
+    /// Returns the clauses of frame k, this over-approximates the states that are reachable in
+    /// at most k transitions.
+    pub fn get_frame(&self, k: usize) -> Vec<Clause> {
+        self.deltas
+            .iter()
+            .skip(k)
+            .chain(std::iter::once(&self.f_inf))
+            .flatten()
+            .cloned()
+            .collect()
+    }
+}
+
+impl<T: PropertyDirectedReachabilitySolver + IncrementalSatSolver, D: DecisionDiagramManager>
+    PropertyDirectedReachability<T, D>
+{
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Returns the partial result of the run so far.
+    /// Every time the depth is increased the frame at the previous depth was found to exclude the
+    /// bad states, so the frames below the depth over-approximate the reachable states and are safe.
+    /// If `is_depth_checked` is true then the frame at the depth itself is also known to be safe.
+    pub fn get_partial_result(
+        &self,
+        is_depth_checked: bool,
+    ) -> PropertyDirectedReachabilityPartialResult {
+        let depth = self.frames.depth();
+        let bounded_safety = if is_depth_checked {
+            Some(depth)
+        } else {
+            depth.checked_sub(1)
+        };
+
+        let (mut deltas, definitions) =
+            self.map_extension_variables(self.frames.get_frame_deltas());
+        let f_inf = deltas.pop().unwrap();
+
+        PropertyDirectedReachabilityPartialResult {
+            bounded_safety,
+            deltas,
+            f_inf,
+            definitions,
+        }
+    }
+}
+
+// ************************************************************************************************
+// printing
+// ************************************************************************************************
+
+impl fmt::Display for PropertyDirectedReachabilityPartialResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.bounded_safety {
+            Some(k) => write!(f, "safe up to {} transitions", k)?,
+            None => write!(f, "no bound proven")?,
+        }
+        write!(
+            f,
+            ", {} frame clauses, {} F_INF clauses, {} definitions",
+            self.deltas.iter().map(|d| d.len()).sum::<usize>(),
+            self.f_inf.len(),
+            self.definitions.len()
+        )
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[test]
+fn test_partial_result_on_max_depth() {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::{PropertyDirectedReachabilityParameters, PropertyDirectedReachabilityProofError};
+    use crate::{
+        models::{AndInverterGraph, Circuit, FiniteStateTransitionSystem, Signal, TernaryValue},
+        solvers::{dd::OxiddBdd, sat::incremental::CaDiCalSolver},
+    };
+
+    // a shift register of 3 latches that shifts in 1, the last latch is set after 3 transitions.
+    let aig = AndInverterGraph::new(
+        Signal::new(3),
+        0,
+        &[
+            (Signal::new(0).wire(true), TernaryValue::False),
+            (Signal::new(1).wire(false), TernaryValue::False),
+            (Signal::new(2).wire(false), TernaryValue::False),
+        ],
+        vec![],
+        vec![Signal::new(3).wire(false)],
+        vec![],
+        &[],
+        String::new(),
+    )
+    .unwrap();
+    let circuit = Circuit::from_aig(&aig);
+    let fin_state = Rc::new(RefCell::new(
+        FiniteStateTransitionSystem::new(&circuit, false).unwrap(),
+    ));
+    let prove_with_max_depth = |max_depth: usize| {
+        let mut parameters = PropertyDirectedReachabilityParameters::new();
+        parameters.max_depth = max_depth;
+        PropertyDirectedReachability::<CaDiCalSolver, OxiddBdd>::new(
+            fin_state.to_owned(),
+            parameters,
+        )
+        .unwrap()
+        .prove()
+    };
+
+    match prove_with_max_depth(1) {
+        Err(PropertyDirectedReachabilityProofError::MaxDepthReached(partial)) => {
+            assert_eq!(partial.get_bounded_safety(), Some(1));
+            assert!(!partial.get_frame(1).is_empty());
+            assert!(partial.get_definitions().is_empty());
+        }
+        _ => panic!("expected the max depth to be reached"),
+    }
+    assert!(prove_with_max_depth(3).unwrap().is_err());
+}
@@ -2,11 +2,17 @@
 // use
 // ************************************************************************************************
 
-use std::{fmt, process::Command};
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
 
 use fxhash::FxHashMap;
 
-use crate::{models::PrettyTable, solvers::sat::incremental::SatResult};
+use crate::{
+    models::{PrettyTable, Utils},
+    solvers::sat::incremental::SatResult,
+};
 
 // ************************************************************************************************
 // struct
@@ -24,6 +30,23 @@ pub struct PDRStats {
     lic_analysis_calls: usize,
     lic_analysis_successful_calls: usize,
     generic_counts: FxHashMap<&'static str, usize>,
+    /// Statistics of every depth that was completed, in order.
+    depths: Vec<PDRDepthStats>,
+    /// The last time the statistics were reported during the run.
+    last_report: Option<Instant>,
+}
+
+/// Statistics of the work done while PDR was at a certain depth.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PDRDepthStats {
+    pub depth: usize,
+    pub seconds: f64,
+    pub sat_calls: usize,
+    pub proof_obligations: usize,
+    pub ternary_simulation_calls: usize,
+    pub generalization_calls: usize,
+    /// Number of extension variables when the depth was completed.
+    pub extension_variables: usize,
 }
 
 // ************************************************************************************************
@@ -31,6 +54,14 @@ pub struct PDRStats {
 // ************************************************************************************************
 
 impl PDRStats {
+    pub const SAT_CALLS: &'static str = "Number of SAT calls";
+    pub const SAT_CALLS_SAT: &'static str = "Number of SAT calls (SAT)";
+    pub const SAT_CALLS_UNSAT: &'static str = "Number of SAT calls (UNSAT)";
+    pub const EV_SAT_CALLS: &'static str = "Number of EV SAT calls";
+    pub const EV_SAT_CALLS_SAT: &'static str = "Number of EV SAT calls (SAT)";
+    pub const EV_SAT_CALLS_UNSAT: &'static str = "Number of EV SAT calls (UNSAT)";
+    pub const PROOF_OBLIGATIONS: &'static str = "Total Proof Obligations";
+
     pub fn new() -> Self {
         Self {
             ternary_simulation_reductions: vec![],
@@ -43,6 +74,8 @@ impl PDRStats {
             lic_analysis_calls: 0,
             lic_analysis_successful_calls: 0,
             generic_counts: Default::default(),
+            depths: vec![],
+            last_report: None,
         }
     }
 
@@ -57,18 +90,18 @@ impl PDRStats {
     }
 
     pub fn note_sat_call(&mut self, r: &SatResult) {
-        self.increment_generic_count("Number of SAT calls");
+        self.increment_generic_count(Self::SAT_CALLS);
         match r {
-            SatResult::Sat => self.increment_generic_count("Number of SAT calls (SAT)"),
-            SatResult::UnSat => self.increment_generic_count("Number of SAT calls (UNSAT)"),
+            SatResult::Sat => self.increment_generic_count(Self::SAT_CALLS_SAT),
+            SatResult::UnSat => self.increment_generic_count(Self::SAT_CALLS_UNSAT),
         }
     }
 
     pub fn note_ev_sat_call(&mut self, r: &SatResult) {
-        self.increment_generic_count("Number of EV SAT calls");
+        self.increment_generic_count(Self::EV_SAT_CALLS);
         match r {
-            SatResult::Sat => self.increment_generic_count("Number of EV SAT calls (SAT)"),
-            SatResult::UnSat => self.increment_generic_count("Number of EV SAT calls (UNSAT)"),
+            SatResult::Sat => self.increment_generic_count(Self::EV_SAT_CALLS_SAT),
+            SatResult::UnSat => self.increment_generic_count(Self::EV_SAT_CALLS_UNSAT),
         }
     }
 
@@ -95,18 +128,70 @@ impl PDRStats {
         *self.generic_counts.entry(name).or_insert(0) += 1;
     }
 
-    fn get_memory_usage() -> Option<usize> {
-        // This command will work on both Linux and macOS.
-        let output = Command::new("ps")
-            .args(["-o", "rss=", "-p", &std::process::id().to_string()])
-            .output()
-            .ok()?;
+    pub fn get_generic_count(&self, name: &'static str) -> usize {
+        self.generic_counts.get(name).copied().unwrap_or_default()
+    }
+
+    /// Returns every generic count, sorted by name.
+    pub fn get_generic_counts(&self) -> Vec<(&'static str, usize)> {
+        let mut v: Vec<(&'static str, usize)> =
+            self.generic_counts.iter().map(|(a, b)| (*a, *b)).collect();
+        v.sort_unstable();
+        v
+    }
+
+    /// Returns the number of calls, and the sum of sizes before and after ternary simulation.
+    pub fn get_ternary_simulation_reductions(&self) -> (usize, usize, usize) {
+        Self::sum_reductions(&self.ternary_simulation_reductions)
+    }
+
+    /// Returns the number of calls, and the sum of sizes before and after generalization.
+    pub fn get_generalization_reductions(&self) -> (usize, usize, usize) {
+        Self::sum_reductions(&self.generalization_reductions)
+    }
+
+    fn sum_reductions(reductions: &[(usize, usize)]) -> (usize, usize, usize) {
+        let before = reductions.iter().map(|(a, _)| a).sum::<usize>();
+        let after = reductions.iter().map(|(_, a)| a).sum::<usize>();
+        (reductions.len(), before, after)
+    }
 
-        let memory_kb = String::from_utf8_lossy(&output.stdout)
-            .trim()
-            .parse::<usize>()
-            .ok()?;
-        Some(memory_kb / 1024) // Convert from KB to MB
+    /// Should be called when PDR is done with a depth, `seconds` is the total time of the run
+    /// so far. The work done since the previous depth is attributed to this depth.
+    pub fn note_depth_completed(&mut self, depth: usize, seconds: f64, extension_variables: usize) {
+        let sum = |f: fn(&PDRDepthStats) -> usize| self.depths.iter().map(f).sum::<usize>();
+        let d = PDRDepthStats {
+            depth,
+            seconds: seconds - self.depths.iter().map(|d| d.seconds).sum::<f64>(),
+            sat_calls: self.get_generic_count(Self::SAT_CALLS) - sum(|d| d.sat_calls),
+            proof_obligations: self.get_generic_count(Self::PROOF_OBLIGATIONS)
+                - sum(|d| d.proof_obligations),
+            ternary_simulation_calls: self.ternary_simulation_reductions.len()
+                - sum(|d| d.ternary_simulation_calls),
+            generalization_calls: self.generalization_reductions.len()
+                - sum(|d| d.generalization_calls),
+            extension_variables,
+        };
+        self.depths.push(d);
+    }
+
+    pub fn get_depth_stats(&self) -> &[PDRDepthStats] {
+        &self.depths
+    }
+
+    /// Returns true if at least `interval` passed since `start` or since the last time this
+    /// returned true, and marks the current time as the time of the last report.
+    pub fn should_report(&mut self, start: Instant, interval: Duration) -> bool {
+        let last = self.last_report.unwrap_or(start);
+        if last.elapsed() < interval {
+            return false;
+        }
+        self.last_report = Some(Instant::now());
+        true
+    }
+
+    fn get_memory_usage() -> Option<usize> {
+        Utils::get_memory_usage_in_mb()
     }
 
     pub fn print_memory_usage(title: &str) {
@@ -117,29 +202,8 @@ impl PDRStats {
     }
 
     pub fn get_stats(&self) -> Vec<(String, String)> {
-        let t = self.ternary_simulation_reductions.len();
-        let sum_before_ternary = self
-            .ternary_simulation_reductions
-            .iter()
-            .map(|(a, _)| a)
-            .sum::<usize>();
-        let sum_after_ternary = self
-            .ternary_simulation_reductions
-            .iter()
-            .map(|(_, a)| a)
-            .sum::<usize>();
-
-        let g = self.generalization_reductions.len();
-        let sum_before_generalize = self
-            .generalization_reductions
-            .iter()
-            .map(|(a, _)| a)
-            .sum::<usize>();
-        let sum_after_generalize = self
-            .generalization_reductions
-            .iter()
-            .map(|(_, a)| a)
-            .sum::<usize>();
+        let (t, sum_before_ternary, sum_after_ternary) = self.get_ternary_simulation_reductions();
+        let (g, sum_before_generalize, sum_after_generalize) = self.get_generalization_reductions();
         let x = vec![
             (
                 "Average state size before ternary simulation".to_string(),
@@ -217,6 +281,18 @@ impl PDRStats {
     }
 }
 
+// ************************************************************************************************
+// rust submodule declaration, they get searched in their respective file  names
+// ************************************************************************************************
+
+pub mod run_stats;
+
+// ************************************************************************************************
+// re-exports of structs in these modules to simplify paths for other imports
+// ************************************************************************************************
+
+pub use run_stats::PDRRunStats;
+
 // ************************************************************************************************
 // Deafult
 // ************************************************************************************************
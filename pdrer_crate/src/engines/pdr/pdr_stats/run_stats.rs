@@ -0,0 +1,346 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::fmt::Write;
+
+use super::{PDRDepthStats, PDRStats};
+use crate::models::{time_stats::TimeStats, Utils};
+
+// ************************************************************************************************
+// struct
+// ************************************************************************************************
+
+/// The total time and number of calls of a timed function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionTime {
+    pub name: String,
+    pub seconds: f64,
+    pub calls: usize,
+}
+
+/// Structured statistics of a PDR run, meant to be consumed by scripts.
+///
+/// The statistics are written as a single line JSON object by `to_json`, averages of things that
+/// never happened are written as `null`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PDRRunStats {
+    pub depth: usize,
+    pub elapsed_seconds: f64,
+
+    pub sat_calls: usize,
+    pub sat_calls_sat: usize,
+    pub sat_calls_unsat: usize,
+    pub ev_sat_calls: usize,
+    pub ev_sat_calls_sat: usize,
+    pub ev_sat_calls_unsat: usize,
+
+    pub proof_obligations: usize,
+
+    pub ternary_simulation_calls: usize,
+    pub average_size_before_ternary_simulation: f64,
+    pub average_size_after_ternary_simulation: f64,
+    pub generalization_calls: usize,
+    pub average_size_before_generalization: f64,
+    pub average_size_after_generalization: f64,
+
+    pub lic_analysis_calls: usize,
+    pub lic_analysis_successful_calls: usize,
+
+    pub extension_variables: usize,
+    pub total_clauses: usize,
+    pub peak_memory_mb: Option<usize>,
+
+    /// All counts that were noted during the run by name, sorted by name.
+    pub generic_counts: Vec<(String, usize)>,
+    pub function_times: Vec<FunctionTime>,
+    pub depths: Vec<PDRDepthStats>,
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl PDRRunStats {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn write_string(out: &mut String, s: &str) {
+        out.push('"');
+        for c in s.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+    }
+
+    fn write_f64(out: &mut String, x: f64) {
+        if x.is_finite() {
+            write!(out, "{}", x).unwrap();
+        } else {
+            out.push_str("null");
+        }
+    }
+
+    fn write_key(out: &mut String, key: &str) {
+        if !out.ends_with('{') {
+            out.push(',');
+        }
+        Self::write_string(out, key);
+        out.push(':');
+    }
+
+    fn write_usize_field(out: &mut String, key: &str, x: usize) {
+        Self::write_key(out, key);
+        write!(out, "{}", x).unwrap();
+    }
+
+    fn write_f64_field(out: &mut String, key: &str, x: f64) {
+        Self::write_key(out, key);
+        Self::write_f64(out, x);
+    }
+
+    fn average(sum: usize, count: usize) -> f64 {
+        sum as f64 / count as f64
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    pub fn new(
+        pdr_stats: &PDRStats,
+        time_stats: &TimeStats,
+        depth: usize,
+        extension_variables: usize,
+        total_clauses: usize,
+    ) -> Self {
+        let (t, before_ternary, after_ternary) = pdr_stats.get_ternary_simulation_reductions();
+        let (g, before_generalize, after_generalize) = pdr_stats.get_generalization_reductions();
+        let (lic_analysis_calls, lic_analysis_successful_calls) = pdr_stats.get_lic_stats();
+        Self {
+            depth,
+            elapsed_seconds: time_stats.get_total_time().as_secs_f64(),
+            sat_calls: pdr_stats.get_generic_count(PDRStats::SAT_CALLS),
+            sat_calls_sat: pdr_stats.get_generic_count(PDRStats::SAT_CALLS_SAT),
+            sat_calls_unsat: pdr_stats.get_generic_count(PDRStats::SAT_CALLS_UNSAT),
+            ev_sat_calls: pdr_stats.get_generic_count(PDRStats::EV_SAT_CALLS),
+            ev_sat_calls_sat: pdr_stats.get_generic_count(PDRStats::EV_SAT_CALLS_SAT),
+            ev_sat_calls_unsat: pdr_stats.get_generic_count(PDRStats::EV_SAT_CALLS_UNSAT),
+            proof_obligations: pdr_stats.get_generic_count(PDRStats::PROOF_OBLIGATIONS),
+            ternary_simulation_calls: t,
+            average_size_before_ternary_simulation: Self::average(before_ternary, t),
+            average_size_after_ternary_simulation: Self::average(after_ternary, t),
+            generalization_calls: g,
+            average_size_before_generalization: Self::average(before_generalize, g),
+            average_size_after_generalization: Self::average(after_generalize, g),
+            lic_analysis_calls,
+            lic_analysis_successful_calls,
+            extension_variables,
+            total_clauses,
+            peak_memory_mb: Utils::get_peak_memory_usage_in_mb(),
+            generic_counts: pdr_stats
+                .get_generic_counts()
+                .into_iter()
+                .map(|(name, count)| (name.to_string(), count))
+                .collect(),
+            function_times: time_stats
+                .get_function_times()
+                .into_iter()
+                .map(|(name, time, calls)| FunctionTime {
+                    name: name.to_string(),
+                    seconds: time.as_secs_f64(),
+                    calls,
+                })
+                .collect(),
+            depths: pdr_stats.get_depth_stats().to_vec(),
+        }
+    }
+
+    /// Returns the statistics as a JSON object without any new lines.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{");
+        Self::write_usize_field(&mut out, "depth", self.depth);
+        Self::write_f64_field(&mut out, "elapsed_seconds", self.elapsed_seconds);
+
+        Self::write_usize_field(&mut out, "sat_calls", self.sat_calls);
+        Self::write_usize_field(&mut out, "sat_calls_sat", self.sat_calls_sat);
+        Self::write_usize_field(&mut out, "sat_calls_unsat", self.sat_calls_unsat);
+        Self::write_usize_field(&mut out, "ev_sat_calls", self.ev_sat_calls);
+        Self::write_usize_field(&mut out, "ev_sat_calls_sat", self.ev_sat_calls_sat);
+        Self::write_usize_field(&mut out, "ev_sat_calls_unsat", self.ev_sat_calls_unsat);
+
+        Self::write_usize_field(&mut out, "proof_obligations", self.proof_obligations);
+
+        Self::write_usize_field(
+            &mut out,
+            "ternary_simulation_calls",
+            self.ternary_simulation_calls,
+        );
+        Self::write_f64_field(
+            &mut out,
+            "average_size_before_ternary_simulation",
+            self.average_size_before_ternary_simulation,
+        );
+        Self::write_f64_field(
+            &mut out,
+            "average_size_after_ternary_simulation",
+            self.average_size_after_ternary_simulation,
+        );
+        Self::write_usize_field(&mut out, "generalization_calls", self.generalization_calls);
+        Self::write_f64_field(
+            &mut out,
+            "average_size_before_generalization",
+            self.average_size_before_generalization,
+        );
+        Self::write_f64_field(
+            &mut out,
+            "average_size_after_generalization",
+            self.average_size_after_generalization,
+        );
+
+        Self::write_usize_field(&mut out, "lic_analysis_calls", self.lic_analysis_calls);
+        Self::write_usize_field(
+            &mut out,
+            "lic_analysis_successful_calls",
+            self.lic_analysis_successful_calls,
+        );
+
+        Self::write_usize_field(&mut out, "extension_variables", self.extension_variables);
+        Self::write_usize_field(&mut out, "total_clauses", self.total_clauses);
+        Self::write_key(&mut out, "peak_memory_mb");
+        match self.peak_memory_mb {
+            Some(m) => write!(out, "{}", m).unwrap(),
+            None => out.push_str("null"),
+        }
+
+        Self::write_key(&mut out, "generic_counts");
+        out.push('{');
+        for (name, count) in self.generic_counts.iter() {
+            Self::write_usize_field(&mut out, name, *count);
+        }
+        out.push('}');
+
+        Self::write_key(&mut out, "function_times");
+        out.push('[');
+        for (i, f) in self.function_times.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push('{');
+            Self::write_key(&mut out, "name");
+            Self::write_string(&mut out, &f.name);
+            Self::write_f64_field(&mut out, "seconds", f.seconds);
+            Self::write_usize_field(&mut out, "calls", f.calls);
+            out.push('}');
+        }
+        out.push(']');
+
+        Self::write_key(&mut out, "depths");
+        out.push('[');
+        for (i, d) in self.depths.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push('{');
+            Self::write_usize_field(&mut out, "depth", d.depth);
+            Self::write_f64_field(&mut out, "seconds", d.seconds);
+            Self::write_usize_field(&mut out, "sat_calls", d.sat_calls);
+            Self::write_usize_field(&mut out, "proof_obligations", d.proof_obligations);
+            Self::write_usize_field(
+                &mut out,
+                "ternary_simulation_calls",
+                d.ternary_simulation_calls,
+            );
+            Self::write_usize_field(&mut out, "generalization_calls", d.generalization_calls);
+            Self::write_usize_field(&mut out, "extension_variables", d.extension_variables);
+            out.push('}');
+        }
+        out.push(']');
+
+        out.push('}');
+        out
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_stats_json_round_trip() {
+        let names = [
+            "quote \" in name",
+            "back\\slash",
+            "new\nline\ttab",
+            "bell \u{7} \u{1f}",
+        ];
+        let stats = PDRRunStats {
+            depth: 3,
+            elapsed_seconds: 1.5,
+            sat_calls: 10,
+            sat_calls_sat: 4,
+            sat_calls_unsat: 6,
+            ev_sat_calls: 0,
+            ev_sat_calls_sat: 0,
+            ev_sat_calls_unsat: 0,
+            proof_obligations: 7,
+            ternary_simulation_calls: 0,
+            average_size_before_ternary_simulation: f64::NAN,
+            average_size_after_ternary_simulation: f64::NAN,
+            generalization_calls: 2,
+            average_size_before_generalization: 2.5,
+            average_size_after_generalization: 1.0,
+            lic_analysis_calls: 0,
+            lic_analysis_successful_calls: 0,
+            extension_variables: 1,
+            total_clauses: 12,
+            peak_memory_mb: None,
+            generic_counts: names.iter().map(|n| (n.to_string(), 1)).collect(),
+            function_times: names
+                .iter()
+                .map(|n| FunctionTime {
+                    name: n.to_string(),
+                    seconds: 0.25,
+                    calls: 2,
+                })
+                .collect(),
+            depths: vec![PDRDepthStats {
+                depth: 1,
+                seconds: 0.5,
+                sat_calls: 3,
+                proof_obligations: 1,
+                ternary_simulation_calls: 0,
+                generalization_calls: 1,
+                extension_variables: 0,
+            }],
+        };
+
+        let json = stats.to_json();
+        assert!(!json.contains('\n'));
+        let value = json::parse(&json).unwrap();
+        assert_eq!(value["depth"], 3);
+        assert_eq!(value["elapsed_seconds"], 1.5);
+        assert!(value["average_size_before_ternary_simulation"].is_null());
+        assert_eq!(value["average_size_before_generalization"], 2.5);
+        assert!(value["peak_memory_mb"].is_null());
+        for (i, name) in names.iter().enumerate() {
+            assert_eq!(value["generic_counts"][*name], 1);
+            assert_eq!(value["function_times"][i]["name"], *name);
+            assert_eq!(value["function_times"][i]["seconds"], 0.25);
+        }
+        assert_eq!(value["depths"][0]["sat_calls"], 3);
+    }
+}
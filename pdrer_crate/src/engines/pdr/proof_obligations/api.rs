@@ -3,6 +3,7 @@
 // ************************************************************************************************
 
 use super::queue::{ProofObligationsQueue, ProofObligationsQueueEntry};
+use super::scheduler::{InsertionOrderScheduler, ProofObligationScheduler};
 use super::trace_tree::TraceTree;
 use super::{ProofObligation, ProofObligations};
 use crate::formulas::{Clause, Cube};
@@ -22,9 +23,14 @@ impl ProofObligations {
     // ********************************************************************************************
 
     pub fn new() -> Self {
+        Self::new_with_scheduler(Box::new(InsertionOrderScheduler::new()))
+    }
+
+    pub fn new_with_scheduler(scheduler: Box<dyn ProofObligationScheduler>) -> Self {
         Self {
             queue: ProofObligationsQueue::new(),
             trace_tree: TraceTree::new(),
+            scheduler,
         }
     }
 
@@ -38,10 +44,12 @@ impl ProofObligations {
         input: Cube,
         successor: Option<Cube>,
     ) -> Result<(), Counterexample> {
+        let priority = self.scheduler.get_priority(&cube, frame);
         let index = self.trace_tree.push(is_initial, cube, input, successor)?;
         self.queue.push(ProofObligationsQueueEntry {
             cube_index: index,
             frame,
+            priority,
             hash_when_added: total_frame_size,
         });
         Ok(())
@@ -49,9 +57,13 @@ impl ProofObligations {
 
     pub fn re_push(&mut self, obligation: ProofObligation) {
         let index = self.trace_tree.get_index(&obligation.cube).unwrap();
+        let priority = self
+            .scheduler
+            .get_priority(&obligation.cube, obligation.frame);
         self.queue.push(ProofObligationsQueueEntry {
             cube_index: index,
             frame: obligation.frame,
+            priority,
             hash_when_added: obligation.hash_when_added,
         });
     }
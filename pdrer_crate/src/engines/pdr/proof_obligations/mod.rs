@@ -2,7 +2,9 @@
 // use
 // ************************************************************************************************
 
-use self::{queue::ProofObligationsQueue, trace_tree::TraceTree};
+use self::{
+    queue::ProofObligationsQueue, scheduler::ProofObligationScheduler, trace_tree::TraceTree,
+};
 use crate::formulas::Cube;
 
 // ************************************************************************************************
@@ -19,6 +21,7 @@ pub struct ProofObligation {
 pub struct ProofObligations {
     queue: ProofObligationsQueue,
     trace_tree: TraceTree,
+    scheduler: Box<dyn ProofObligationScheduler>,
 }
 
 // ************************************************************************************************
@@ -27,6 +30,7 @@ pub struct ProofObligations {
 
 pub mod api;
 pub mod queue;
+pub mod scheduler;
 pub mod trace_tree;
 
 // ************************************************************************************************
@@ -11,21 +11,25 @@ use std::collections::BinaryHeap;
 #[derive(Debug, Clone)]
 struct QueueItem {
     pub frame: usize,
-    pub priority: u32,
+    /// priority given by the scheduler, smaller is better
+    pub priority: f64,
+    /// insertion counter, used to break ties between equal priorities
+    pub counter: u32,
     pub cube_index: usize,
     pub hash_when_added: usize,
 }
 
 pub struct ProofObligationsQueueEntry {
     pub frame: usize,
+    pub priority: f64,
     pub cube_index: usize,
     pub hash_when_added: usize,
 }
 
 pub struct ProofObligationsQueue {
     queue: BinaryHeap<QueueItem>,
-    /// priority, smaller is better
-    current_priority: u32,
+    /// insertion counter, smaller is better
+    current_counter: u32,
 }
 
 // ************************************************************************************************
@@ -41,7 +45,7 @@ impl Default for ProofObligationsQueue {
 impl ProofObligationsQueue {
     pub fn new() -> Self {
         Self {
-            current_priority: 0,
+            current_counter: 0,
             queue: BinaryHeap::new(),
         }
     }
@@ -50,10 +54,11 @@ impl ProofObligationsQueue {
         let ipo = QueueItem {
             cube_index: entry.cube_index,
             frame: entry.frame,
-            priority: self.current_priority,
+            priority: entry.priority,
+            counter: self.current_counter,
             hash_when_added: entry.hash_when_added,
         };
-        self.current_priority += 1;
+        self.current_counter += 1;
         self.queue.push(ipo);
     }
 
@@ -66,6 +71,7 @@ impl ProofObligationsQueue {
         r.map(|x| ProofObligationsQueueEntry {
             cube_index: x.cube_index,
             frame: x.frame,
+            priority: x.priority,
             hash_when_added: x.hash_when_added,
         })
     }
@@ -86,11 +92,12 @@ impl ProofObligationsQueue {
 
 impl Ord for QueueItem {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        // smallest frame, smallest priority is the "greatest" proof obligation
+        // smallest frame, smallest priority, smallest counter is the "greatest" proof obligation
         other
             .frame
             .cmp(&self.frame)
-            .then(other.priority.cmp(&self.priority))
+            .then(other.priority.total_cmp(&self.priority))
+            .then(other.counter.cmp(&self.counter))
             .then(other.cube_index.cmp(&self.cube_index))
     }
 }
@@ -105,7 +112,7 @@ impl PartialEq for QueueItem {
     fn eq(&self, other: &Self) -> bool {
         self.cube_index == other.cube_index
             && self.frame == other.frame
-            && self.priority == other.priority
+            && self.counter == other.counter
     }
 }
 
@@ -0,0 +1,151 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::{cell::RefCell, rc::Rc};
+
+use rand::{rngs::StdRng, Rng};
+
+use crate::{engines::pdr::Weights, formulas::Cube};
+
+// ************************************************************************************************
+// trait
+// ************************************************************************************************
+
+/// Decides the order in which proof obligations are handled.
+/// Obligations of lower frames are always handled first, among the obligations of the same frame
+/// the one with the smallest priority is handled first, ties are broken by insertion order.
+pub trait ProofObligationScheduler {
+    /// Returns the priority of an obligation, called every time the obligation is pushed.
+    fn get_priority(&mut self, cube: &Cube, frame: usize) -> f64;
+}
+
+// ************************************************************************************************
+// struct
+// ************************************************************************************************
+
+/// Handles obligations in the order they were pushed.
+pub struct InsertionOrderScheduler {}
+
+/// Handles obligations with fewer literals first, these are more likely to be blocked with a
+/// short clause.
+pub struct SmallestCubeScheduler {}
+
+/// Handles obligations whose variables have the highest weights first, these are the variables
+/// that appear the most in recently added clauses.
+pub struct HighestWeightScheduler {
+    weights: Rc<RefCell<Weights>>,
+}
+
+/// Handles obligations of the same frame in a random order.
+pub struct RandomScheduler {
+    rng: Rc<RefCell<StdRng>>,
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl InsertionOrderScheduler {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl SmallestCubeScheduler {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl HighestWeightScheduler {
+    pub fn new(weights: Rc<RefCell<Weights>>) -> Self {
+        Self { weights }
+    }
+}
+
+impl RandomScheduler {
+    pub fn new(rng: Rc<RefCell<StdRng>>) -> Self {
+        Self { rng }
+    }
+}
+
+impl Default for InsertionOrderScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for SmallestCubeScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ************************************************************************************************
+// impl trait
+// ************************************************************************************************
+
+impl ProofObligationScheduler for InsertionOrderScheduler {
+    fn get_priority(&mut self, _cube: &Cube, _frame: usize) -> f64 {
+        0.0
+    }
+}
+
+impl ProofObligationScheduler for SmallestCubeScheduler {
+    fn get_priority(&mut self, cube: &Cube, _frame: usize) -> f64 {
+        cube.len() as f64
+    }
+}
+
+impl ProofObligationScheduler for HighestWeightScheduler {
+    fn get_priority(&mut self, cube: &Cube, _frame: usize) -> f64 {
+        let weights = self.weights.borrow();
+        -cube
+            .iter()
+            .map(|l| weights.get_weight(&l.variable()))
+            .sum::<f64>()
+    }
+}
+
+impl ProofObligationScheduler for RandomScheduler {
+    fn get_priority(&mut self, _cube: &Cube, _frame: usize) -> f64 {
+        self.rng.borrow_mut().gen()
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[test]
+fn test_smallest_cube_scheduler_order() {
+    use super::ProofObligations;
+    use crate::formulas::Variable;
+
+    let cube = |n: u32, is_negated: bool| {
+        Cube::from_sequence(
+            (1..=n)
+                .map(|i| Variable::new(i).literal(is_negated))
+                .collect(),
+        )
+    };
+    let mut proof_obligations =
+        ProofObligations::new_with_scheduler(Box::new(SmallestCubeScheduler::new()));
+    for (c, frame) in [
+        (cube(3, false), 1),
+        (cube(1, false), 2),
+        (cube(2, false), 1),
+        (cube(1, true), 1),
+    ] {
+        proof_obligations
+            .push(false, c, frame, 0, Cube::new_true(), None)
+            .unwrap();
+    }
+
+    // lower frames first, then smaller cubes
+    let order: Vec<(usize, usize)> = std::iter::from_fn(|| proof_obligations.pop())
+        .map(|po| (po.cube.len(), po.frame))
+        .collect();
+    assert_eq!(order, vec![(1, 1), (2, 1), (3, 1), (1, 2)]);
+}
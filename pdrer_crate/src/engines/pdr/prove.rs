@@ -13,45 +13,77 @@ use crate::{
     function,
     models::{
         finite_state_transition_system::ProofResult, time_stats::function_timer::FunctionTimer,
-        Definition, Proof, SortedVecOfLiterals,
+        Definition, Proof, SortedVecOfLiterals, Utils,
     },
-    solvers::{dd::DecisionDiagramManager, sat::incremental::CaDiCalSolver},
+    solvers::{dd::DecisionDiagramManager, sat::incremental::IncrementalSatSolver},
 };
 
 // ************************************************************************************************
 // impl
 // ************************************************************************************************
 
-impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager>
+impl<T: PropertyDirectedReachabilitySolver + IncrementalSatSolver, D: DecisionDiagramManager>
     PropertyDirectedReachability<T, D>
 {
     /// Performs the proof on the provided model
     pub fn prove(&mut self) -> Result<ProofResult, PropertyDirectedReachabilityProofError> {
         let _timer = FunctionTimer::start(function!(), self.s.time_stats.clone());
-        self.print_start_message_if_verbose();
-        if let Some(t) = self.s.fin_state.borrow().is_trivial::<CaDiCalSolver>() {
-            self.print_final_message_if_verbose(false);
-            return Ok(t);
+        loop {
+            if let Some(r) = self.prove_up_to_depth(usize::MAX)? {
+                return Ok(r);
+            }
         }
+    }
 
-        loop {
+    /// Performs the proof until it is done, or until the depth of the engine is greater than
+    /// `depth` in which case `None` is returned. Calling this again resumes the proof, which
+    /// allows interleaving the engine with other engines.
+    pub fn prove_up_to_depth(
+        &mut self,
+        depth: usize,
+    ) -> Result<Option<ProofResult>, PropertyDirectedReachabilityProofError> {
+        if !self.is_started {
+            self.is_started = true;
+            self.print_start_message_if_verbose();
+            if let Some(t) = self.s.fin_state.borrow().is_trivial::<T>() {
+                self.print_final_message_if_verbose(false);
+                self.print_final_json_stats_if_needed();
+                return Ok(Some(t));
+            }
+            if self.s.parameters.er_circuit_signals {
+                let added = self.frames.add_definitions_of_internal_signals(
+                    self.s.parameters.er_circuit_signals_max_cone_size,
+                );
+                if self.s.parameters.verbose {
+                    println!(
+                        "Defined {} extension variables from circuit signals.",
+                        added
+                    );
+                }
+            }
+        }
+
+        while self.frames.depth() <= depth {
             let optional_c = self.frames.get_bad_cube(self.frames.depth());
             let result = self.perform_proof_iteration(optional_c);
             match result {
                 Ok(r) => {
                     if let Some(r) = r {
                         self.print_final_message_if_verbose(false);
+                        self.print_final_json_stats_if_needed();
                         debug_assert!(self.frames.regression_check());
-                        return Ok(r);
+                        return Ok(Some(r));
                     }
                 }
                 Err(e) => {
                     self.print_final_message_if_verbose(true);
+                    self.print_final_json_stats_if_needed();
                     debug_assert!(self.frames.regression_check());
                     return Err(e);
                 }
             }
         }
+        Ok(None)
     }
 
     pub fn is_blocked(&mut self, c: &Cube, frame: usize) -> bool {
@@ -70,7 +102,13 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager>
         self.frames.depth()
     }
 
-    fn get_proof(&self, i: usize) -> Proof {
+    /// Extension variables may use any variable number that is not used by the model, this maps
+    /// them to consecutive variables right after the tagged variables, and returns the given
+    /// clauses together with the definitions after the mapping.
+    pub(super) fn map_extension_variables(
+        &self,
+        mut clauses: Vec<Vec<Clause>>,
+    ) -> (Vec<Vec<Clause>>, Vec<Definition>) {
         let mut base = self.s.fin_state.borrow().get_max_variable();
         self.s.fin_state.borrow().add_tags_to_variable(&mut base, 1);
         base = Variable::new(base.number() + 1);
@@ -97,31 +135,53 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager>
             Literal::new(map_var(l.variable())).negate_if_true(l.is_negated())
         };
 
-        Proof {
+        for c in clauses.iter_mut().flat_map(|x| x.iter_mut()) {
+            *c = Clause::from_ordered_set(c.iter().map(|l| map_literal(*l)).collect());
+        }
+        let definitions = self
+            .frames
+            .get_definitions()
+            .iter()
+            .map(|d| Definition {
+                variable: map_var(d.variable),
+                function: d.function,
+                inputs: SortedVecOfLiterals::from_ordered_set(
+                    d.inputs
+                        .iter()
+                        .map(|l| map_literal(*l))
+                        .collect::<Vec<Literal>>(),
+                ),
+            })
+            .collect();
+
+        (clauses, definitions)
+    }
+
+    fn get_proof(&self, i: usize) -> Proof {
+        let (mut clauses, definitions) =
+            self.map_extension_variables(vec![self.frames.get_cnf_of_frame(i)]);
+        let proof = Proof {
             all_initial_states_violate_constraints: false,
-            invariant: CNF::from_sequence({
-                let mut r = self.frames.get_cnf_of_frame(i);
-                for c in r.iter_mut() {
-                    *c = Clause::from_ordered_set(c.iter().map(|l| map_literal(*l)).collect());
-                }
-                r
-            }),
-            definitions: self
-                .frames
-                .get_definitions()
-                .iter()
-                .map(|d| Definition {
-                    variable: map_var(d.variable),
-                    function: d.function,
-                    inputs: SortedVecOfLiterals::from_ordered_set(
-                        d.inputs
-                            .iter()
-                            .map(|l| map_literal(*l))
-                            .collect::<Vec<Literal>>(),
-                    ),
-                })
-                .collect(),
+            invariant: CNF::from_sequence(clauses.pop().unwrap()),
+            definitions,
+        };
+        if !self.s.parameters.minimize_proof {
+            return proof;
+        }
+        let _timer = FunctionTimer::start(function!(), self.s.time_stats.clone());
+        let minimized = self
+            .s
+            .fin_state
+            .borrow()
+            .minimize_proof::<T>(proof, self.s.parameters.minimize_proof_shrink_literals);
+        if self.s.parameters.verbose {
+            println!(
+                "Minimized invariant to {} clauses and {} definitions.",
+                minimized.invariant.len(),
+                minimized.definitions.len()
+            );
         }
+        minimized
     }
 
     pub fn call_propagate(&mut self) -> Option<Proof> {
@@ -149,9 +209,13 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager>
         let _timer = FunctionTimer::start(function!(), self.s.time_stats.clone());
 
         if self.frames.depth() >= self.s.parameters.max_depth {
-            return Err(PropertyDirectedReachabilityProofError::MaxDepthReached);
+            // the depth is only increased once no bad cube is left at the depth
+            return Err(PropertyDirectedReachabilityProofError::MaxDepthReached(
+                self.get_partial_result(true),
+            ));
         }
 
+        self.note_depth_completed();
         self.frames.increase_depth();
         if let Some(proof) = self.call_propagate() {
             return Ok(Some(Ok(proof)));
@@ -164,6 +228,24 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager>
         Ok(None)
     }
 
+    /// Returns an error with the partial result of the run if the time out or the memory limit
+    /// were reached.
+    pub(super) fn check_resource_limits(
+        &self,
+    ) -> Result<(), PropertyDirectedReachabilityProofError> {
+        if self.s.parameters.start_time.unwrap().elapsed() > self.s.parameters.timeout {
+            return Err(PropertyDirectedReachabilityProofError::TimeOutReached(
+                self.get_partial_result(false),
+            ));
+        }
+        if Utils::is_memory_limit_reached(self.s.parameters.memory_limit_mb) {
+            return Err(PropertyDirectedReachabilityProofError::MemoryLimitReached(
+                self.get_partial_result(false),
+            ));
+        }
+        Ok(())
+    }
+
     pub fn perform_proof_iteration(
         &mut self,
         optional_c: Option<(Cube, Cube)>,
@@ -171,9 +253,9 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager>
         let _timer = FunctionTimer::start(function!(), self.s.time_stats.clone());
         // let optional_c = self.z.get_bad_cube(self.depth(), &self.weights);
 
-        if self.s.parameters.start_time.unwrap().elapsed() > self.s.parameters.timeout {
-            return Err(PropertyDirectedReachabilityProofError::TimeOutReached);
-        }
+        self.print_json_stats_if_interval_passed();
+        self.save_checkpoint_if_interval_passed();
+        self.check_resource_limits()?;
 
         match optional_c {
             Some((bad, input)) => {
@@ -211,6 +293,14 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager>
                     Some(Err(e)) => return Ok(Some(Err(e))),
                     None => {}
                 }
+                // obligations that were kept beyond the previous depth can be handled now
+                if self.s.parameters.re_push_proof_obligations_beyond_depth {
+                    match self.block_proof_obligations() {
+                        Ok(Ok(())) => {}
+                        Ok(Err(e)) => return Err(e),
+                        Err(er) => return Ok(Some(Err(er))),
+                    }
+                }
             }
         }
 
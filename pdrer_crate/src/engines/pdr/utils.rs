@@ -2,7 +2,11 @@
 // use
 // ************************************************************************************************
 
-use super::{PropertyDirectedReachability, PropertyDirectedReachabilitySolver};
+use std::time::Duration;
+
+use super::{
+    pdr_stats::PDRRunStats, PropertyDirectedReachability, PropertyDirectedReachabilitySolver,
+};
 use crate::{
     models::{definition::DefinitionFunction, PrettyTable},
     solvers::dd::DecisionDiagramManager,
@@ -19,6 +23,50 @@ use crate::{
 impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager>
     PropertyDirectedReachability<T, D>
 {
+    // ********************************************************************************************
+    // statistics API
+    // ********************************************************************************************
+
+    /// Returns the statistics of the run so far.
+    pub fn get_run_stats(&self) -> PDRRunStats {
+        PDRRunStats::new(
+            &self.s.pdr_stats.borrow(),
+            &self.s.time_stats.borrow(),
+            self.frames.depth(),
+            self.frames.get_definitions().len(),
+            self.frames.iter().map(|f| f.len()).sum::<usize>(),
+        )
+    }
+
+    /// Notes the statistics of the depth that was just completed.
+    pub(super) fn note_depth_completed(&self) {
+        self.s.pdr_stats.borrow_mut().note_depth_completed(
+            self.frames.depth(),
+            self.s
+                .parameters
+                .start_time
+                .unwrap()
+                .elapsed()
+                .as_secs_f64(),
+            self.frames.get_definitions().len(),
+        );
+    }
+
+    pub(super) fn print_json_stats_if_interval_passed(&self) {
+        let start = self.s.parameters.start_time.unwrap();
+        let interval = self.s.parameters.json_stats_interval;
+        if interval != Duration::MAX && self.s.pdr_stats.borrow_mut().should_report(start, interval)
+        {
+            println!("{}", self.get_run_stats().to_json());
+        }
+    }
+
+    pub(super) fn print_final_json_stats_if_needed(&self) {
+        if self.s.parameters.should_print_json_stats {
+            println!("{}", self.get_run_stats().to_json());
+        }
+    }
+
     // ********************************************************************************************
     // printing API
     // ********************************************************************************************
@@ -66,6 +114,24 @@ impl<T: PropertyDirectedReachabilitySolver, D: DecisionDiagramManager>
                     .count()
                     .to_string(),
             ));
+            stats.push((
+                "ITE Extension Variables".to_string(),
+                self.frames
+                    .get_definitions()
+                    .iter()
+                    .filter(|d| matches!(d.function, DefinitionFunction::Ite(_)))
+                    .count()
+                    .to_string(),
+            ));
+            stats.push((
+                "MAJ3 Extension Variables".to_string(),
+                self.frames
+                    .get_definitions()
+                    .iter()
+                    .filter(|d| d.function == DefinitionFunction::Maj3)
+                    .count()
+                    .to_string(),
+            ));
             stats.push((
                 "Elapsed".to_string(),
                 self.s
@@ -42,4 +42,9 @@ impl VariableWeights {
         let index = variable.hash();
         self.weight_per_variable[index]
     }
+
+    /// Returns the weight of every state variable, indexed by the variable number.
+    pub fn get_weights(&self) -> &[VariableWeight] {
+        &self.weight_per_variable
+    }
 }
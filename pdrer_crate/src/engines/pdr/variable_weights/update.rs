@@ -63,4 +63,11 @@ impl VariableWeights {
     pub fn clear(&mut self) {
         self.weight_per_variable.fill(INITIAL_WEIGHT);
     }
+
+    /// Overrides the weight of every state variable, the weights must be indexed by the
+    /// variable number and be of the same length as the ones returned by `get_weights`.
+    pub fn set_weights(&mut self, weights: &[VariableWeight]) {
+        assert_eq!(weights.len(), self.weight_per_variable.len());
+        self.weight_per_variable.copy_from_slice(weights);
+    }
 }
@@ -0,0 +1,50 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use super::{RandomSimulation, RandomSimulationParameters};
+use crate::models::{BitParallelSimulator, Circuit};
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl RandomSimulation {
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Creates the engine for a circuit, the property is read from the circuit the same way
+    /// `FiniteStateTransitionSystem::new` reads it, and counterexamples use the same variables
+    /// as the transition system (the variable of a signal has the number of the signal).
+    pub fn new(
+        circuit: &Circuit,
+        assume_output_is_bad: bool,
+        parameters: RandomSimulationParameters,
+    ) -> Self {
+        let mut properties: Vec<_> = circuit.get_bad_wires().iter().copied().collect();
+        if assume_output_is_bad {
+            properties.extend(circuit.get_output_wires().iter().copied());
+        }
+        let signals: Vec<_> = circuit.iter_sorted().collect();
+
+        Self {
+            simulator: BitParallelSimulator::new(circuit),
+            properties,
+            constraints: circuit
+                .get_invariant_constraint_wires()
+                .iter()
+                .copied()
+                .collect(),
+            parameters,
+            rng: StdRng::seed_from_u64(parameters.seed),
+            signatures: vec![vec![]; signals.len()],
+            signals,
+            valid_lanes: vec![],
+            simulated_cycles: 0,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Random simulation for finding shallow bugs quickly.
+//!
+//! Every signal is simulated 64 times at once using the bit-parallel simulator, each bit runs an
+//! independent random input sequence from a random initial state. A lane that reaches a bad
+//! state (while satisfying the constraints on the way) yields a counterexample. The values seen
+//! during simulation are kept as signatures, signals with equal signatures are candidates for
+//! being equivalent.
+
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use rand::rngs::StdRng;
+
+use crate::models::bit_parallel_simulator::SimulationWord;
+use crate::models::{BitParallelSimulator, Signal, Wire};
+
+// ************************************************************************************************
+// struct
+// ************************************************************************************************
+
+#[derive(Debug, Clone, Copy)]
+pub struct RandomSimulationParameters {
+    /// Seed of the random number generator that picks the inputs and the initial values of the
+    /// latches that have no fixed initial value.
+    pub seed: u64,
+    /// The number of rounds, every round simulates 64 input sequences.
+    pub rounds: usize,
+    /// The number of cycles in every input sequence, so a counterexample that is deeper than
+    /// `depth - 1` cannot be found.
+    pub depth: usize,
+    /// The maximal number of words in the signature of every signal, the simulated cycles are
+    /// sampled evenly when there are more of them.
+    pub signature_length: usize,
+    /// If true, then a line is printed at the end of every round.
+    pub verbose: bool,
+}
+
+pub struct RandomSimulation {
+    simulator: BitParallelSimulator,
+    /// The wires whose disjunction is the bad event.
+    properties: Vec<Wire>,
+    constraints: Vec<Wire>,
+    parameters: RandomSimulationParameters,
+    rng: StdRng,
+
+    /// Every signal in the circuit, and its signature in the same order.
+    signals: Vec<Signal>,
+    signatures: Vec<Vec<SimulationWord>>,
+    /// For every word of the signatures, the lanes that satisfied the constraints so far.
+    valid_lanes: Vec<SimulationWord>,
+    /// The number of cycles that were simulated so far, over all the rounds.
+    simulated_cycles: usize,
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl RandomSimulationParameters {
+    pub const DEFAULT: Self = Self {
+        seed: 0,
+        rounds: 16,
+        depth: 32,
+        signature_length: 64,
+        verbose: false,
+    };
+
+    pub fn new() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Default for RandomSimulationParameters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ************************************************************************************************
+// rust submodule declaration, they get searched in their respective file  names
+// ************************************************************************************************
+
+pub mod construction;
+pub mod signatures;
+pub mod simulate;
@@ -0,0 +1,75 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use fxhash::FxHashMap;
+
+use super::RandomSimulation;
+use crate::models::bit_parallel_simulator::SimulationWord;
+use crate::models::{Signal, Wire};
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl RandomSimulation {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    /// The first valid lane among all the simulated values, as the index of the word and the bit.
+    fn get_first_valid_lane(&self) -> Option<(usize, u32)> {
+        self.valid_lanes
+            .iter()
+            .position(|w| *w != 0)
+            .map(|i| (i, self.valid_lanes[i].trailing_zeros()))
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// The values that the signal had in the recorded cycles, one word per cycle. Only the lanes
+    /// in `get_valid_lanes` reached the cycle while satisfying the constraints.
+    pub fn get_signature(&self, signal: Signal) -> Option<&[SimulationWord]> {
+        self.signals
+            .binary_search(&signal)
+            .ok()
+            .map(|i| self.signatures[i].as_slice())
+    }
+
+    pub fn get_valid_lanes(&self) -> &[SimulationWord] {
+        &self.valid_lanes
+    }
+
+    /// Groups the signals whose values agree (possibly up to negation) in every valid lane.
+    /// Every class holds at least two wires that may be equivalent, the wires are sorted and
+    /// the phases are chosen such that the first valid value of every wire is 0.
+    /// Wires in different classes are certainly not equivalent, wires in the same class still
+    /// need to be proven equivalent. Nothing is returned before any cycle was recorded.
+    pub fn get_equivalence_candidates(&self) -> Vec<Vec<Wire>> {
+        let (first_word, first_bit) = match self.get_first_valid_lane() {
+            Some(lane) => lane,
+            None => return vec![],
+        };
+
+        let mut classes: FxHashMap<Vec<SimulationWord>, Vec<Wire>> = FxHashMap::default();
+        for (signal, signature) in self.signals.iter().zip(self.signatures.iter()) {
+            let is_negated = (signature[first_word] >> first_bit) & 1 == 1;
+            let flip = if is_negated { SimulationWord::MAX } else { 0 };
+            let key = signature
+                .iter()
+                .zip(self.valid_lanes.iter())
+                .map(|(w, valid)| (w ^ flip) & valid)
+                .collect();
+            classes
+                .entry(key)
+                .or_default()
+                .push(signal.wire(is_negated));
+        }
+
+        let mut result: Vec<Vec<Wire>> = classes.into_values().filter(|c| c.len() > 1).collect();
+        result.sort_unstable();
+        result
+    }
+}
@@ -0,0 +1,235 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use rand::Rng;
+
+use super::RandomSimulation;
+use crate::formulas::{Cube, Variable};
+use crate::models::bit_parallel_simulator::SimulationWord;
+use crate::models::{Counterexample, Signal, TernaryValue};
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl RandomSimulation {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn get_cube(signals: &[Signal], words: &[SimulationWord], lane: u32) -> Cube {
+        Cube::from_sequence(
+            signals
+                .iter()
+                .zip(words)
+                .map(|(s, w)| Variable::new(s.number()).literal((w >> lane) & 1 == 0))
+                .collect(),
+        )
+    }
+
+    fn get_counterexample(
+        &self,
+        initial_values: &[SimulationWord],
+        inputs: &[Vec<SimulationWord>],
+        lane: u32,
+    ) -> Counterexample {
+        Counterexample {
+            initial_cube: Self::get_cube(self.simulator.get_latch_signals(), initial_values, lane),
+            inputs: inputs
+                .iter()
+                .map(|words| Self::get_cube(self.simulator.get_input_signals(), words, lane))
+                .collect(),
+        }
+    }
+
+    /// The cycles are sampled evenly, the sampled cycles are shifted by one in every round so
+    /// that periodic behavior is not missed.
+    fn should_record_signature(&self, round: usize, cycle: usize) -> bool {
+        let total = self.parameters.rounds * self.parameters.depth;
+        let stride = (total / self.parameters.signature_length.max(1)).max(1);
+        (cycle + round) % stride == 0 && self.valid_lanes.len() < self.parameters.signature_length
+    }
+
+    fn record_signature(&mut self, valid: SimulationWord) {
+        for (signal, signature) in self.signals.iter().zip(self.signatures.iter_mut()) {
+            signature.push(self.simulator.get_value(*signal));
+        }
+        self.valid_lanes.push(valid);
+    }
+
+    /// Simulates 64 random input sequences from random initial states, returns a counterexample
+    /// if some sequence reaches a bad state.
+    fn simulate_round(&mut self, round: usize) -> Option<Counterexample> {
+        let initial_values: Vec<SimulationWord> = self
+            .simulator
+            .get_latch_initial_values()
+            .iter()
+            .map(|v| match v {
+                TernaryValue::True => SimulationWord::MAX,
+                TernaryValue::False => 0,
+                TernaryValue::X => self.rng.gen(),
+            })
+            .collect();
+        for (j, word) in initial_values.iter().enumerate() {
+            let signal = self.simulator.get_latch_signals()[j];
+            self.simulator.set_value(signal, *word);
+        }
+
+        let mut inputs: Vec<Vec<SimulationWord>> = vec![];
+        let mut valid = SimulationWord::MAX;
+        for cycle in 0..self.parameters.depth {
+            let words: Vec<SimulationWord> = (0..self.simulator.get_input_signals().len())
+                .map(|_| self.rng.gen())
+                .collect();
+            for (j, word) in words.iter().enumerate() {
+                let signal = self.simulator.get_input_signals()[j];
+                self.simulator.set_value(signal, *word);
+            }
+            inputs.push(words);
+            self.simulator.simulate();
+
+            valid = self
+                .constraints
+                .iter()
+                .fold(valid, |r, w| r & self.simulator.get_wire_value(*w));
+            let bad = self
+                .properties
+                .iter()
+                .fold(0, |r, w| r | self.simulator.get_wire_value(*w))
+                & valid;
+            if bad != 0 {
+                return Some(self.get_counterexample(
+                    &initial_values,
+                    &inputs,
+                    bad.trailing_zeros(),
+                ));
+            }
+            if valid == 0 {
+                break;
+            }
+
+            if self.should_record_signature(round, cycle) {
+                self.record_signature(valid);
+            }
+            self.simulated_cycles += 1;
+            self.simulator.step();
+        }
+        None
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Simulates the random input sequences round after round. Returns a counterexample as soon
+    /// as some sequence reaches a bad state, the counterexample is not necessarily the shortest
+    /// one. Returning `None` proves nothing.
+    /// Calling this again continues with new random sequences, the signatures keep growing until
+    /// they reach their maximal length.
+    pub fn find_counterexample(&mut self) -> Option<Counterexample> {
+        for round in 0..self.parameters.rounds {
+            let result = self.simulate_round(round);
+            if self.parameters.verbose {
+                println!(
+                    "Random simulation round = {}, simulated cycles = {}",
+                    round, self.simulated_cycles
+                );
+            }
+            if result.is_some() {
+                return result;
+            }
+        }
+        None
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::engines::{
+        ExplicitStateReachability, ExplicitStateReachabilityParameters, RandomSimulationParameters,
+    };
+    use crate::models::{AndInverterGraph, Circuit, FiniteStateTransitionSystem};
+    use crate::solvers::sat::incremental::CaDiCalSolver;
+
+    #[test]
+    fn test_counterexamples_are_valid() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut found = 0;
+        for i in 0..200 {
+            let aig = AndInverterGraph::geometric_random(&mut rng, 3, 6, 30, 0, 1, i % 3);
+            let circuit = Circuit::from_aig(&aig);
+            let mut parameters = RandomSimulationParameters::new();
+            parameters.seed = i as u64;
+            let mut engine = RandomSimulation::new(&circuit, false, parameters);
+            let counterexample = engine.find_counterexample();
+
+            let mut explicit = ExplicitStateReachability::new(
+                &circuit,
+                false,
+                ExplicitStateReachabilityParameters::new(),
+            )
+            .unwrap();
+            match counterexample {
+                None => continue,
+                Some(c) => {
+                    assert!(explicit.prove().is_err());
+                    if let Ok(mut fin_state) = FiniteStateTransitionSystem::new(&circuit, false) {
+                        fin_state
+                            .check_proof_result::<CaDiCalSolver>(Err(c))
+                            .unwrap();
+                    }
+                    found += 1;
+                }
+            }
+        }
+        assert!(found > 0);
+    }
+
+    #[test]
+    fn test_equivalence_candidates() {
+        // a 2-bit counter whose gates are all duplicated, the next value of the second bit is
+        // the negation of either xnor gate
+        let w = |n: u32, negated: bool| Signal::new(n).wire(negated);
+        let aig = AndInverterGraph::new(
+            Signal::new(8),
+            0,
+            &[
+                (w(1, true), TernaryValue::False),
+                (w(5, true), TernaryValue::False),
+            ],
+            vec![],
+            vec![],
+            vec![],
+            &[
+                (w(1, false), w(2, true)),
+                (w(1, true), w(2, false)),
+                (w(3, true), w(4, true)),
+                (w(2, false), w(1, true)),
+                (w(2, true), w(1, false)),
+                (w(6, true), w(7, true)),
+            ],
+            String::new(),
+        )
+        .unwrap();
+        let circuit = Circuit::from_aig(&aig);
+        let mut engine = RandomSimulation::new(&circuit, false, RandomSimulationParameters::new());
+        assert_eq!(engine.find_counterexample(), None);
+        assert_eq!(
+            engine.get_equivalence_candidates(),
+            vec![
+                vec![w(3, false), w(7, false)],
+                vec![w(4, false), w(6, false)],
+                vec![w(5, true), w(8, true)],
+            ]
+        );
+    }
+}
@@ -0,0 +1,504 @@
+//! Encodings of cardinality constraints (at most k of the literals are true) and pseudo-Boolean
+//! constraints (the sum of the weights of the true literals is at most k) into CNF.
+//!
+//! The encodings introduce auxiliary variables that are taken from a caller-provided allocator,
+//! the allocator must return a fresh variable every time it is called.
+
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::fmt;
+
+use super::{Clause, Literal, Variable, CNF};
+
+// ************************************************************************************************
+// types
+// ************************************************************************************************
+
+/// The encoding to use for a cardinality constraint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardinalityEncoding {
+    /// Sinz's sequential counter, `O(n * k)` clauses and auxiliary variables.
+    SequentialCounter,
+    /// Bailleux and Boufkhad's totalizer where each node only counts up to `k + 1`,
+    /// `O(n * k)` auxiliary variables and `O(n * k^2)` clauses.
+    Totalizer,
+    /// Batcher's odd-even merge sorting network, `O(n * log^2(n))` clauses and auxiliary
+    /// variables.
+    SortingNetwork,
+}
+
+/// The encoding to use for a pseudo-Boolean constraint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PseudoBooleanEncoding {
+    /// The sequential weight counter, `O(n * k)` clauses and auxiliary variables.
+    SequentialWeightCounter,
+}
+
+/// The CNF that encodes a constraint, together with the auxiliary variables that were
+/// introduced, in the order in which they were allocated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardinalityEncodingResult {
+    pub cnf: CNF,
+    pub auxiliary_variables: Vec<Variable>,
+}
+
+/// Collects the clauses and the auxiliary variables of an encoding.
+struct Encoder<F: FnMut() -> Variable> {
+    new_variable: F,
+    clauses: Vec<Clause>,
+    auxiliary_variables: Vec<Variable>,
+}
+
+// ************************************************************************************************
+// encoder
+// ************************************************************************************************
+
+impl<F: FnMut() -> Variable> Encoder<F> {
+    fn new(new_variable: F) -> Self {
+        Self {
+            new_variable,
+            clauses: vec![],
+            auxiliary_variables: vec![],
+        }
+    }
+
+    fn new_literal(&mut self) -> Literal {
+        let v = (self.new_variable)();
+        self.auxiliary_variables.push(v);
+        v.literal(false)
+    }
+
+    fn add_clause(&mut self, literals: Vec<Literal>) {
+        self.clauses.push(Clause::from_sequence(literals));
+    }
+
+    fn finish(self) -> CardinalityEncodingResult {
+        CardinalityEncodingResult {
+            cnf: CNF::from_sequence(self.clauses),
+            auxiliary_variables: self.auxiliary_variables,
+        }
+    }
+
+    // ********************************************************************************************
+    // sequential weight counter
+    // ********************************************************************************************
+
+    /// `s[i][j - 1]` is true if the weight of the true literals among the first `i + 1` literals
+    /// is at least `j`.
+    fn sequential_weight_counter(&mut self, literals: &[(Literal, usize)], k: usize) {
+        let n = literals.len();
+        let mut previous: Vec<Literal> = vec![];
+        for (i, (x, w)) in literals.iter().copied().enumerate() {
+            if w > k {
+                self.add_clause(vec![!x]);
+                continue;
+            }
+            // the overflow of the weight with this literal
+            if !previous.is_empty() {
+                self.add_clause(vec![!x, !previous[k - w]]);
+            }
+            if i + 1 == n {
+                break;
+            }
+            let current: Vec<Literal> = (0..k).map(|_| self.new_literal()).collect();
+            for s in current.iter().take(w) {
+                self.add_clause(vec![!x, *s]);
+            }
+            for (j, s) in previous.iter().enumerate() {
+                self.add_clause(vec![!*s, current[j]]);
+                if j + w < k {
+                    self.add_clause(vec![!x, !*s, current[j + w]]);
+                }
+            }
+            previous = current;
+        }
+    }
+
+    // ********************************************************************************************
+    // totalizer
+    // ********************************************************************************************
+
+    /// Returns literals where the `j`-th literal is true if at least `j + 1` of the literals are
+    /// true, counting only up to `limit`.
+    fn totalizer(&mut self, literals: &[Literal], limit: usize) -> Vec<Literal> {
+        if literals.len() == 1 {
+            return literals.to_vec();
+        }
+        let (left, right) = literals.split_at(literals.len() / 2);
+        let a = self.totalizer(left, limit);
+        let b = self.totalizer(right, limit);
+        let r: Vec<Literal> = (0..literals.len().min(limit))
+            .map(|_| self.new_literal())
+            .collect();
+        for i in 0..=a.len() {
+            for j in 0..=b.len() {
+                if i + j == 0 {
+                    continue;
+                }
+                let mut clause = Vec::with_capacity(3);
+                if i > 0 {
+                    clause.push(!a[i - 1]);
+                }
+                if j > 0 {
+                    clause.push(!b[j - 1]);
+                }
+                clause.push(r[(i + j).min(r.len()) - 1]);
+                self.add_clause(clause);
+            }
+        }
+        r
+    }
+
+    // ********************************************************************************************
+    // sorting network
+    // ********************************************************************************************
+
+    /// Returns the maximum and minimum of the two inputs, `None` stands for false.
+    fn comparator(
+        &mut self,
+        a: Option<Literal>,
+        b: Option<Literal>,
+    ) -> (Option<Literal>, Option<Literal>) {
+        match (a, b) {
+            (None, None) => (None, None),
+            (Some(x), None) | (None, Some(x)) => (Some(x), None),
+            (Some(a), Some(b)) => {
+                let max = self.new_literal();
+                let min = self.new_literal();
+                self.add_clause(vec![!a, max]);
+                self.add_clause(vec![!b, max]);
+                self.add_clause(vec![!a, !b, min]);
+                (Some(max), Some(min))
+            }
+        }
+    }
+
+    /// Merges two sequences of the same power of two length that are sorted with the true
+    /// values first.
+    fn odd_even_merge(
+        &mut self,
+        a: &[Option<Literal>],
+        b: &[Option<Literal>],
+    ) -> Vec<Option<Literal>> {
+        debug_assert_eq!(a.len(), b.len());
+        if a.len() == 1 {
+            let (max, min) = self.comparator(a[0], b[0]);
+            return vec![max, min];
+        }
+        let even = |x: &[Option<Literal>]| x.iter().copied().step_by(2).collect::<Vec<_>>();
+        let odd = |x: &[Option<Literal>]| x.iter().copied().skip(1).step_by(2).collect::<Vec<_>>();
+        let v = self.odd_even_merge(&even(a), &even(b));
+        let w = self.odd_even_merge(&odd(a), &odd(b));
+        let mut result = Vec::with_capacity(a.len() + b.len());
+        result.push(v[0]);
+        for i in 1..v.len() {
+            let (max, min) = self.comparator(w[i - 1], v[i]);
+            result.push(max);
+            result.push(min);
+        }
+        result.push(w[w.len() - 1]);
+        result
+    }
+
+    /// Sorts a sequence of power of two length so that the true values are first.
+    fn odd_even_merge_sort(&mut self, values: &[Option<Literal>]) -> Vec<Option<Literal>> {
+        if values.len() <= 1 {
+            return values.to_vec();
+        }
+        let (left, right) = values.split_at(values.len() / 2);
+        let a = self.odd_even_merge_sort(left);
+        let b = self.odd_even_merge_sort(right);
+        self.odd_even_merge(&a, &b)
+    }
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl CardinalityEncoding {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn negate_all(literals: &[Literal]) -> Vec<Literal> {
+        literals.iter().map(|l| !*l).collect()
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Returns a CNF that is satisfiable if and only if at most `k` of the literals are true.
+    /// The literals must be over different variables.
+    pub fn at_most_k<F: FnMut() -> Variable>(
+        &self,
+        literals: &[Literal],
+        k: usize,
+        new_variable: F,
+    ) -> CardinalityEncodingResult {
+        debug_assert!(Clause::from_sequence(literals.to_vec()).len() == literals.len());
+        let mut encoder = Encoder::new(new_variable);
+        if k >= literals.len() {
+            return encoder.finish();
+        }
+        if k == 0 {
+            for l in literals.iter() {
+                encoder.add_clause(vec![!*l]);
+            }
+            return encoder.finish();
+        }
+
+        match self {
+            CardinalityEncoding::SequentialCounter => {
+                let literals: Vec<(Literal, usize)> = literals.iter().map(|l| (*l, 1)).collect();
+                encoder.sequential_weight_counter(&literals, k);
+            }
+            CardinalityEncoding::Totalizer => {
+                let outputs = encoder.totalizer(literals, k + 1);
+                encoder.add_clause(vec![!outputs[k]]);
+            }
+            CardinalityEncoding::SortingNetwork => {
+                let mut values: Vec<Option<Literal>> = literals.iter().copied().map(Some).collect();
+                values.resize(literals.len().next_power_of_two(), None);
+                let sorted = encoder.odd_even_merge_sort(&values);
+                if let Some(l) = sorted[k] {
+                    encoder.add_clause(vec![!l]);
+                }
+            }
+        }
+        encoder.finish()
+    }
+
+    /// Returns a CNF that is satisfiable if and only if at least `k` of the literals are true.
+    /// The literals must be over different variables.
+    pub fn at_least_k<F: FnMut() -> Variable>(
+        &self,
+        literals: &[Literal],
+        k: usize,
+        new_variable: F,
+    ) -> CardinalityEncodingResult {
+        if k > literals.len() {
+            return CardinalityEncodingResult {
+                cnf: Clause::new_false().to_cnf(),
+                auxiliary_variables: vec![],
+            };
+        }
+        self.at_most_k(
+            &Self::negate_all(literals),
+            literals.len() - k,
+            new_variable,
+        )
+    }
+
+    /// Returns a CNF that is satisfiable if and only if exactly `k` of the literals are true.
+    /// The literals must be over different variables.
+    pub fn exactly_k<F: FnMut() -> Variable>(
+        &self,
+        literals: &[Literal],
+        k: usize,
+        mut new_variable: F,
+    ) -> CardinalityEncodingResult {
+        let mut result = self.at_most_k(literals, k, &mut new_variable);
+        let mut at_least = self.at_least_k(literals, k, &mut new_variable);
+        result.cnf.append(at_least.cnf);
+        result
+            .auxiliary_variables
+            .append(&mut at_least.auxiliary_variables);
+        result
+    }
+}
+
+impl PseudoBooleanEncoding {
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Returns a CNF that is satisfiable if and only if the sum of the weights of the true
+    /// literals is at most `k`.
+    /// The literals must be over different variables.
+    pub fn at_most_k<F: FnMut() -> Variable>(
+        &self,
+        literals: &[(Literal, usize)],
+        k: usize,
+        new_variable: F,
+    ) -> CardinalityEncodingResult {
+        debug_assert!(
+            Clause::from_sequence(literals.iter().map(|(l, _)| *l).collect()).len()
+                == literals.len()
+        );
+        let mut encoder = Encoder::new(new_variable);
+        let literals: Vec<(Literal, usize)> =
+            literals.iter().copied().filter(|(_, w)| *w > 0).collect();
+        if literals.iter().map(|(_, w)| w).sum::<usize>() <= k {
+            return encoder.finish();
+        }
+        if k == 0 {
+            for (l, _) in literals.iter() {
+                encoder.add_clause(vec![!*l]);
+            }
+            return encoder.finish();
+        }
+
+        match self {
+            PseudoBooleanEncoding::SequentialWeightCounter => {
+                encoder.sequential_weight_counter(&literals, k)
+            }
+        }
+        encoder.finish()
+    }
+
+    /// Returns a CNF that is satisfiable if and only if the sum of the weights of the true
+    /// literals is at least `k`.
+    /// The literals must be over different variables.
+    pub fn at_least_k<F: FnMut() -> Variable>(
+        &self,
+        literals: &[(Literal, usize)],
+        k: usize,
+        new_variable: F,
+    ) -> CardinalityEncodingResult {
+        let total: usize = literals.iter().map(|(_, w)| w).sum();
+        if k > total {
+            return CardinalityEncodingResult {
+                cnf: Clause::new_false().to_cnf(),
+                auxiliary_variables: vec![],
+            };
+        }
+        let negated: Vec<(Literal, usize)> = literals.iter().map(|(l, w)| (!*l, *w)).collect();
+        self.at_most_k(&negated, total - k, new_variable)
+    }
+}
+
+// ************************************************************************************************
+// printing
+// ************************************************************************************************
+
+impl fmt::Display for CardinalityEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CardinalityEncoding::SequentialCounter => write!(f, "sequential counter"),
+            CardinalityEncoding::Totalizer => write!(f, "totalizer"),
+            CardinalityEncoding::SortingNetwork => write!(f, "sorting network"),
+        }
+    }
+}
+
+impl fmt::Display for PseudoBooleanEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PseudoBooleanEncoding::SequentialWeightCounter => {
+                write!(f, "sequential weight counter")
+            }
+        }
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvers::sat::incremental::{CaDiCalSolver, SatResult};
+
+    /// Checks that for every assignment to the inputs the result is satisfiable if and only if
+    /// `expected` holds for the number of true inputs.
+    fn check<E, P>(n: u32, encode: E, expected: P)
+    where
+        E: Fn(&[Literal], &mut dyn FnMut() -> Variable) -> CardinalityEncodingResult,
+        P: Fn(u32) -> bool,
+    {
+        let literals: Vec<Literal> = (1..=n)
+            .map(|i| Variable::new(i).literal(i % 2 == 0))
+            .collect();
+        let mut next = n;
+        let mut new_variable = || {
+            next += 1;
+            Variable::new(next)
+        };
+        let result = encode(&literals, &mut new_variable);
+        assert_eq!(
+            result.auxiliary_variables,
+            (n + 1..=next).map(Variable::new).collect::<Vec<_>>()
+        );
+        assert!(result.cnf.get_max_variable() <= Variable::new(next));
+
+        let mut solver = CaDiCalSolver::new(0);
+        for c in result.cnf.iter() {
+            solver.add_clause(c.iter().copied());
+        }
+        for assignment in 0..(1_u32 << n) {
+            let assumptions: Vec<Literal> = literals
+                .iter()
+                .enumerate()
+                .map(|(i, l)| if (assignment >> i) & 1 == 1 { *l } else { !*l })
+                .collect();
+            let is_sat = matches!(solver.solve(assumptions, vec![]), SatResult::Sat);
+            assert_eq!(
+                is_sat,
+                expected(assignment),
+                "assignment = {:b}",
+                assignment
+            );
+        }
+    }
+
+    #[test]
+    fn test_cardinality_encodings() {
+        for encoding in [
+            CardinalityEncoding::SequentialCounter,
+            CardinalityEncoding::Totalizer,
+            CardinalityEncoding::SortingNetwork,
+        ] {
+            for n in 1..=6 {
+                for k in 0..=(n + 1) {
+                    check(
+                        n,
+                        |l, v| encoding.at_most_k(l, k as usize, v),
+                        |a| a.count_ones() <= k,
+                    );
+                    check(
+                        n,
+                        |l, v| encoding.at_least_k(l, k as usize, v),
+                        |a| a.count_ones() >= k,
+                    );
+                    check(
+                        n,
+                        |l, v| encoding.exactly_k(l, k as usize, v),
+                        |a| a.count_ones() == k,
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pseudo_boolean_encodings() {
+        let weights = [3, 1, 0, 2, 5, 2];
+        let weight_of = |a: u32| -> usize {
+            (0..weights.len())
+                .filter(|i| (a >> i) & 1 == 1)
+                .map(|i| weights[i])
+                .sum()
+        };
+        let encoding = PseudoBooleanEncoding::SequentialWeightCounter;
+        for k in 0..=14 {
+            let weighted = |l: &[Literal]| -> Vec<(Literal, usize)> {
+                l.iter().copied().zip(weights.iter().copied()).collect()
+            };
+            check(
+                weights.len() as u32,
+                |l, v| encoding.at_most_k(&weighted(l), k, v),
+                |a| weight_of(a) <= k,
+            );
+            check(
+                weights.len() as u32,
+                |l, v| encoding.at_least_k(&weighted(l), k, v),
+                |a| weight_of(a) >= k,
+            );
+        }
+    }
+}
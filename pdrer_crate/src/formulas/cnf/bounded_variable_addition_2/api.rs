@@ -82,6 +82,63 @@ impl BVA2Pattern {
         }
     }
 
+    /// A pattern that can indicate a multiplexer, x = ite(c, t, e)
+    pub fn ite_pattern() -> Self {
+        let lits = Self::get_n_lits(4);
+        let c = lits[0];
+        let t = lits[1];
+        let e = lits[2];
+        let x = lits[3];
+
+        Self {
+            inputs: vec![
+                SortedVecOfLiterals::from_ordered_set(vec![!c, t]),
+                SortedVecOfLiterals::from_ordered_set(vec![c, e]),
+            ],
+            result: vec![SortedVecOfLiterals::from_ordered_set(vec![x])],
+            definitions: vec![(
+                x.variable(),
+                DefinitionFunction::Ite(0),
+                SortedVecOfLiterals::from_ordered_set(vec![c, t, e]),
+            )],
+            score: 1,
+            neq: vec![
+                (c.variable(), t.variable()),
+                (c.variable(), e.variable()),
+                (t.variable(), e.variable()),
+            ],
+        }
+    }
+
+    /// A pattern that can indicate a majority gate, x = maj(a, b, c)
+    pub fn maj3_pattern() -> Self {
+        let lits = Self::get_n_lits(4);
+        let a = lits[0];
+        let b = lits[1];
+        let c = lits[2];
+        let x = lits[3];
+
+        Self {
+            inputs: vec![
+                SortedVecOfLiterals::from_ordered_set(vec![a, b]),
+                SortedVecOfLiterals::from_ordered_set(vec![a, c]),
+                SortedVecOfLiterals::from_ordered_set(vec![b, c]),
+            ],
+            result: vec![SortedVecOfLiterals::from_ordered_set(vec![x])],
+            definitions: vec![(
+                x.variable(),
+                DefinitionFunction::Maj3,
+                SortedVecOfLiterals::from_ordered_set(vec![a, b, c]),
+            )],
+            score: 1,
+            neq: vec![
+                (a.variable(), b.variable()),
+                (a.variable(), c.variable()),
+                (b.variable(), c.variable()),
+            ],
+        }
+    }
+
     pub fn xor_pattern_2() -> Self {
         let lits = Self::get_n_lits(8);
 
@@ -364,25 +421,30 @@ impl BVA2MatchedPattern<'_> {
             .definitions
             .iter()
             .map(|(og_v, f, i)| {
-                let mut inputs: Vec<Literal> = i
+                let inputs: Vec<Literal> = i
                     .iter()
                     .map(|x| map_pattern_literal(*x, &seen_extension_variables))
                     .collect();
-                inputs.sort_unstable();
+                let (f, inputs) = f.sort_inputs(inputs);
                 // println!("Defining inputs = {:?}", inputs);
                 if !SortedVecOfLiterals::are_variables_sorted_and_unique(&inputs) {
-                    let v = get_ev(f, None);
+                    let v = get_ev(&f, None);
                     let l = match f {
                         DefinitionFunction::And => !v,
                         DefinitionFunction::Xor => v,
+                        DefinitionFunction::Ite(_)
+                        | DefinitionFunction::Maj3
+                        | DefinitionFunction::TruthTable(_) => {
+                            unreachable!("The pattern requires the inputs to be different.")
+                        }
                     };
                     Err(l)
                 } else {
                     let inputs = SortedVecOfLiterals::from_ordered_set(inputs);
-                    let v = get_ev(f, Some(&inputs));
+                    let v = get_ev(&f, Some(&inputs));
                     let d = Definition {
                         variable: v.variable(),
-                        function: *f,
+                        function: f,
                         inputs,
                     };
                     seen_extension_variables.insert(*og_v, v);
@@ -689,12 +751,11 @@ impl CNF {
         for (i, m) in matches.v.iter().enumerate() {
             let mut defs: Vec<(DefinitionFunction, Vec<Literal>)> = vec![];
             for (_, f, inputs) in m.pattern.definitions.iter() {
-                let mut inputs: Vec<Literal> = inputs
+                let inputs: Vec<Literal> = inputs
                     .iter()
                     .map(|x| matches.v[i].convert_literal(*x).unwrap())
                     .collect();
-                inputs.sort_unstable();
-                defs.push((*f, inputs));
+                defs.push(f.sort_inputs(inputs));
             }
             defs.sort_unstable();
             match_map.entry(defs).or_insert_with(Vec::new).push(i);
@@ -866,3 +866,34 @@ const _INVARIANT_OF_BUF_ALLOC_8: &str = "p cnf 22 310
 6 -15 16 -17 -18 -19 -20 -21 -22 0
 -6 -15 -16 -17 -18 -19 -20 -21 -22 0
 -7 -15 -16 -17 -18 -19 -20 -21 -22 0";
+
+#[test]
+fn test_bva_ite_and_maj3_patterns() {
+    let l = |i: u32, is_negated: bool| Variable::new(i).literal(is_negated);
+    let (c, t, e) = (l(10, true), l(11, false), l(12, true));
+    let (a, b) = (l(13, false), l(14, false));
+
+    let cnf = vec![
+        // ite(c, t, e) in two clauses
+        Clause::from_sequence(vec![!c, t, a]),
+        Clause::from_sequence(vec![c, e, a]),
+        Clause::from_sequence(vec![!c, t, b]),
+        Clause::from_sequence(vec![c, e, b]),
+        // maj(a, b, t) under e
+        Clause::from_sequence(vec![a, b, e]),
+        Clause::from_sequence(vec![a, t, e]),
+        Clause::from_sequence(vec![b, t, e]),
+    ];
+
+    let patterns = vec![BVA2Pattern::ite_pattern(), BVA2Pattern::maj3_pattern()];
+    let r = CNF::bva2_match_patterns_on_cnfs(std::slice::from_ref(&cnf), &patterns);
+    for (i, p) in patterns.iter().enumerate() {
+        assert!(
+            r.v.iter().any(|x| std::ptr::eq(p, x.pattern)),
+            "pattern {i} was not found"
+        );
+    }
+    for x in r.v.iter() {
+        _check_pattern_match_correctness(&cnf, x);
+    }
+}
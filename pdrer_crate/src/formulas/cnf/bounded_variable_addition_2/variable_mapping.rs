@@ -223,6 +223,49 @@ impl VariableMapping {
         Ok(())
     }
 
+    /// Removes from the possible mappings of a variable every literal whose variable is the
+    /// mapping of a unit that must be of a different variable.
+    fn propagate_inequalities(
+        m: &mut MappingObjects,
+        vars_to_be_mapped: &[Variable],
+        x: usize,
+        neq: &[(usize, usize)],
+    ) -> Result<(), ()> {
+        let mut is_changed = true;
+        while is_changed {
+            is_changed = false;
+            for (yi, yj) in neq.iter().flat_map(|(a, b)| [(*a, *b), (*b, *a)]) {
+                let unit = match m.mapping_units.get(x, yi) {
+                    Some(u) => *u,
+                    None => continue,
+                };
+                if m.mapping_units.get(x, yj).is_some() {
+                    continue;
+                }
+                let mut is_row_changed = false;
+                for z in 0..m.mappings.get_row(x, yj).len() {
+                    if let Some(l) = *m.mappings.get(x, yj, z) {
+                        if l.variable() == unit.variable() {
+                            m.mappings.set(x, yj, z, None);
+                            Self::decrement_mapping_counts(&mut m.mapping_counts, x, yj);
+                            is_row_changed = true;
+                        }
+                    }
+                }
+                if !is_row_changed {
+                    continue;
+                }
+                is_changed = true;
+                match Self::get_mapping_counts(&m.mapping_counts, x, yj) {
+                    0 => return Err(()),
+                    1 => Self::mark_new_unit(m, vars_to_be_mapped, x, yj)?,
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// adds to the variable mapping the variables that we now know the mapping of.
     /// Returns false if the mapping is not possible (contradiction to the pattern found).
     /// Returns true if the mapping is possible.
@@ -287,6 +330,15 @@ impl VariableMapping {
             }
         }
 
+        if let Err(()) = Self::propagate_inequalities(
+            &mut self.m,
+            &self.vars_to_be_mapped,
+            self.stack_pointer,
+            &self.neq,
+        ) {
+            return false;
+        }
+
         for (yi, yj) in self.neq.iter() {
             if let (Some(a), Some(b)) = (
                 self.m.mapping_units.get(self.stack_pointer, *yi),
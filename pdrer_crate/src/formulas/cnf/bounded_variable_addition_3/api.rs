@@ -3,7 +3,7 @@
 // ************************************************************************************************
 
 use super::{BVA3Parameters, BVA3Pattern, BVA3PatternMatch};
-use crate::formulas::{Clause, CNF};
+use crate::formulas::{Clause, Literal, CNF};
 use fxhash::{FxHashMap, FxHashSet};
 
 // ************************************************************************************************
@@ -72,6 +72,28 @@ fn find_most_pattern_that_can_coexist(
     result
 }
 
+/// Given the diff of (!c or t or ...) and (c or e or ...), returns (c, t, e) such that t < e.
+fn get_ite_from_diff(
+    diff_i: &[Literal],
+    diff_j: &[Literal],
+) -> Option<(Literal, Literal, Literal)> {
+    let complementary: Vec<(usize, usize)> = (0..2)
+        .flat_map(|i| (0..2).map(move |j| (i, j)))
+        .filter(|(i, j)| diff_i[*i].variable() == diff_j[*j].variable())
+        .collect();
+    if complementary.len() != 1 {
+        return None;
+    }
+    let (i, j) = complementary[0];
+    let (p, t, e) = (diff_i[i], diff_i[1 - i], diff_j[1 - j]);
+    debug_assert_eq!(p, !diff_j[j]);
+    if t.variable() == e.variable() {
+        return None;
+    }
+    // ite(c, t, e) = ite(!c, e, t)
+    Some(if t < e { (!p, t, e) } else { (p, e, t) })
+}
+
 fn make_clause_length_index(cnf: &[Clause]) -> Vec<Vec<&Clause>> {
     let max_clause_length = cnf.iter().map(|x| x.len()).max().unwrap();
     let mut length_index = vec![vec![]; max_clause_length + 1];
@@ -104,17 +126,23 @@ impl CNF {
             // make index to cnf clauses by clause length
             let index = make_clause_length_index(cnf);
 
+            let max_diff = if params.xor_pattern || params.ite_pattern {
+                3
+            } else {
+                2
+            };
+
             for (n, same_length_clauses) in index.iter().enumerate() {
                 for (skip, ci) in same_length_clauses.iter().enumerate() {
                     debug_assert!(ci.len() == n);
-                    for cj in same_length_clauses.iter().skip(skip + 1) {
+                    for (skip_j, cj) in same_length_clauses.iter().enumerate().skip(skip + 1) {
                         sub_vec_1.clear();
                         sub_vec_2.clear();
 
                         ci.peek().peek().symmetric_difference_custom(
                             cj.peek().peek(),
-                            if params.xor_pattern { 3 } else { 2 },
-                            if params.xor_pattern { 3 } else { 2 },
+                            max_diff,
+                            max_diff,
                             &mut sub_vec_1,
                             &mut sub_vec_2,
                         );
@@ -137,6 +165,34 @@ impl CNF {
                                 });
                             }
 
+                            if params.maj3_pattern {
+                                // (a or b) and (a or c) and (b or c)
+                                // each triplet is found once, from its first two clauses
+                                let (b, c) = (sub_vec_1[0], sub_vec_2[0]);
+                                for ck in same_length_clauses.iter().skip(skip_j + 1) {
+                                    if !ck.contains(&b) || !ck.contains(&c) {
+                                        continue;
+                                    }
+                                    let (diff_a, diff_c) =
+                                        ci.peek().peek().symmetric_difference(ck.peek().peek());
+                                    if diff_a.len() != 1 || *diff_c.peek() != [c] {
+                                        continue;
+                                    }
+                                    let a = diff_a.peek()[0];
+                                    if a.variable() == b.variable() || a.variable() == c.variable()
+                                    {
+                                        continue;
+                                    }
+                                    let mut l = [a, b, c];
+                                    l.sort_unstable();
+                                    m.push(BVA3PatternMatch {
+                                        cnf_index,
+                                        pattern: BVA3Pattern::MajorityPattern(l[0], l[1], l[2]),
+                                        clause_indices: vec![ci, cj, ck],
+                                    });
+                                }
+                            }
+
                             if params.half_adder_pattern && n + 1 < index.len() {
                                 // (a or b or c) and (a or b or d) or (!a or !b or c or d)
                                 let c = sub_vec_1[0];
@@ -199,6 +255,15 @@ impl CNF {
                                 pattern: BVA3Pattern::XorPattern(a, b),
                                 clause_indices: vec![*ci, *cj],
                             });
+                        } else if params.ite_pattern && sub_vec_1.len() == 2 && sub_vec_2.len() == 2
+                        {
+                            if let Some((c, t, e)) = get_ite_from_diff(&sub_vec_1, &sub_vec_2) {
+                                m.push(BVA3PatternMatch {
+                                    cnf_index,
+                                    pattern: BVA3Pattern::IfThenElsePattern(c, t, e),
+                                    clause_indices: vec![ci, cj],
+                                });
+                            }
                         }
                     }
                 }
@@ -26,4 +26,6 @@ pub struct BVA3Parameters {
     pub and_pattern: bool,
     pub xor_pattern: bool,
     pub half_adder_pattern: bool,
+    pub ite_pattern: bool,
+    pub maj3_pattern: bool,
 }
@@ -14,6 +14,10 @@ pub enum BVA3Pattern {
     AndPattern(Literal, Literal),
     XorPattern(Variable, Variable),
     HalfAdderPattern(Literal, Literal, Literal, Literal),
+    /// ite(c, t, e) where t < e
+    IfThenElsePattern(Literal, Literal, Literal),
+    /// maj(a, b, c) where a < b < c
+    MajorityPattern(Literal, Literal, Literal),
 }
 
 // ************************************************************************************************
@@ -32,6 +36,12 @@ impl Debug for BVA3Pattern {
             Self::HalfAdderPattern(a, b, c, d) => {
                 write!(f, "HalfAdderPattern({}, {}, {}, {})", a, b, c, d)
             }
+            Self::IfThenElsePattern(c, t, e) => {
+                write!(f, "IfThenElsePattern({}, {}, {})", c, t, e)
+            }
+            Self::MajorityPattern(a, b, c) => {
+                write!(f, "MajorityPattern({}, {}, {})", a, b, c)
+            }
         }
     }
 }
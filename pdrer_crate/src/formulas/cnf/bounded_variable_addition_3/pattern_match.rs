@@ -105,6 +105,22 @@ impl BVA3PatternMatch<'_> {
                 ];
                 (clauses_after, vec![x, y, z])
             }
+            BVA3Pattern::IfThenElsePattern(c, t, e) => {
+                let (f, inputs) = DefinitionFunction::Ite(0).sort_inputs(vec![c, t, e]);
+                let x = get_ev(f, SortedVecOfLiterals::from_ordered_set(inputs));
+                common.insert(x);
+                let clauses_after = vec![Clause::from_ordered_set(common.unpack().unpack())];
+                (clauses_after, vec![x])
+            }
+            BVA3Pattern::MajorityPattern(a, b, c) => {
+                let x = get_ev(
+                    DefinitionFunction::Maj3,
+                    SortedVecOfLiterals::from_sequence(vec![a, b, c]),
+                );
+                common.insert(x);
+                let clauses_after = vec![Clause::from_ordered_set(common.unpack().unpack())];
+                (clauses_after, vec![x])
+            }
         }
     }
 }
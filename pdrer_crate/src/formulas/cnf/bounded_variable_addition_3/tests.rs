@@ -131,6 +131,8 @@ fn test_bva_1() {
             and_pattern: true,
             xor_pattern: true,
             half_adder_pattern: true,
+            ite_pattern: false,
+            maj3_pattern: false,
         },
     );
 
@@ -174,7 +176,7 @@ fn test_bva_2() {
     const N: usize = 1000;
     const CNF_SIZE: usize = 20;
     const MAX_CLAUSE_LENGTH: usize = 5;
-    let mut counters = [0, 0, 0];
+    let mut counters = [0, 0, 0, 0, 0];
     for i in 0..=N {
         let seed: u64 = ThreadRng::default().gen();
         println!("i = {}, seed = {seed}\t, counters = {:?}", i, counters);
@@ -206,6 +208,8 @@ fn test_bva_2() {
                 and_pattern: true,
                 xor_pattern: true,
                 half_adder_pattern: true,
+                ite_pattern: true,
+                maj3_pattern: true,
             },
         );
         let _ = CNF::bva3_get_best_set_of_patterns_to_add(&r, true);
@@ -225,6 +229,8 @@ fn test_bva_2() {
                 BVA3Pattern::AndPattern { .. } => counters[0] += 1,
                 BVA3Pattern::XorPattern { .. } => counters[1] += 1,
                 BVA3Pattern::HalfAdderPattern { .. } => counters[2] += 1,
+                BVA3Pattern::IfThenElsePattern { .. } => counters[3] += 1,
+                BVA3Pattern::MajorityPattern { .. } => counters[4] += 1,
             }
         }
 
@@ -414,6 +420,8 @@ fn test_bva_3() {
                 and_pattern: true,
                 xor_pattern: true,
                 half_adder_pattern: true,
+                ite_pattern: false,
+                maj3_pattern: false,
             },
         );
         let elapsed = start_time.elapsed();
@@ -430,6 +438,9 @@ fn test_bva_3() {
                 BVA3Pattern::AndPattern { .. } => counters[0] += 1,
                 BVA3Pattern::XorPattern { .. } => counters[1] += 1,
                 BVA3Pattern::HalfAdderPattern { .. } => counters[2] += 1,
+                BVA3Pattern::IfThenElsePattern { .. } | BVA3Pattern::MajorityPattern { .. } => {
+                    unreachable!()
+                }
             }
         }
 
@@ -474,6 +485,8 @@ fn test_bva_4() {
                 and_pattern: false,
                 xor_pattern: false,
                 half_adder_pattern: true,
+                ite_pattern: false,
+                maj3_pattern: false,
             },
         );
         if r.is_empty() {
@@ -483,6 +496,8 @@ fn test_bva_4() {
                     and_pattern: true,
                     xor_pattern: false,
                     half_adder_pattern: false,
+                    ite_pattern: false,
+                    maj3_pattern: false,
                 },
             );
             if r.is_empty() {
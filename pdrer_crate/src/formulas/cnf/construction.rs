@@ -9,7 +9,6 @@ use rand::Rng;
 
 use super::CNF;
 use crate::formulas::Clause;
-use crate::formulas::Literal;
 use crate::formulas::Variable;
 use crate::models::UniqueSortedVec;
 
@@ -63,48 +62,6 @@ impl CNF {
         }
     }
 
-    /// create cnf from a DIMACS string.
-    /// The DIMACS format is a simple text-based format for representing CNF formulas.
-    ///
-    pub fn from_dimacs(dimacs: &str) -> Result<Self, String> {
-        let mut clauses: Vec<Clause> = vec![];
-        for line in dimacs.lines() {
-            // skip comment
-            if line.starts_with('c') {
-                continue;
-            }
-
-            // skip header line
-            if line.starts_with('p') {
-                let number_of_clauses = line.split_whitespace().nth(3).unwrap().parse().unwrap();
-                clauses = Vec::with_capacity(number_of_clauses);
-                continue;
-            }
-
-            // clause line, looks something like this: "1 2 3 0"
-            let mut clause = vec![];
-            for literal in line.split_whitespace() {
-                let literal = literal.parse::<i32>().unwrap();
-                if literal == 0 {
-                    // reached end of clause
-                    break;
-                }
-                let internal_literal = Literal::new(Variable::new(literal.unsigned_abs()));
-                if literal < 0 {
-                    clause.push(!internal_literal);
-                } else {
-                    clause.push(internal_literal);
-                }
-            }
-
-            // add clause to cnf
-            // assert!(clauses.is_some(), "Reached clause before header line.");
-            let new_clause = Clause::from_sequence(clause);
-            clauses.push(new_clause);
-        }
-        Ok(CNF::from_sequence(clauses))
-    }
-
     /// Generate a random CNF formula, with a given number of variables and clauses.
     /// The probability of negating a variable in a clause is given by `probability_of_negating_variable`.
     /// The length of each clause is determined by the closure `length_per_clause`.
@@ -0,0 +1,338 @@
+//! Reading and writing CNF formulas in the DIMACS format.
+//!
+//! The reader is streaming, so large files are never held in memory as text, and accepts:
+//! - comment lines starting with `c` anywhere in the file.
+//! - clauses that span several lines, or several clauses on the same line.
+//! - gzipped files when reading from a path.
+//! - a `%` line that ends the formula (as found in the SATLIB benchmarks).
+//!
+//! The header `p cnf <variables> <clauses>` must appear before the first clause, literals may
+//! not use variables above the declared amount, and the number of clauses must match the header.
+//! Repeated literals in a clause are merged, and clauses with both a literal and its negation are
+//! dropped since they always hold, they are still counted against the header.
+
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader, Write},
+    path::Path,
+};
+
+use flate2::read::MultiGzDecoder;
+
+use super::CNF;
+use crate::{
+    formulas::{Clause, Literal},
+    models::SortedVecOfLiterals,
+};
+
+// ************************************************************************************************
+// error
+// ************************************************************************************************
+
+#[derive(Debug)]
+pub enum DimacsError {
+    Io(io::Error),
+    /// The input ended without a header.
+    MissingHeader,
+    /// The header on this line is not of the form `p cnf <variables> <clauses>`.
+    InvalidHeader(usize),
+    /// A second header was found on this line.
+    DuplicateHeader(usize),
+    /// A clause starts on this line before the header.
+    ClauseBeforeHeader(usize),
+    /// A token on this line is not a valid literal.
+    InvalidLiteral(usize, String),
+    /// A literal on this line uses a variable above the number of variables in the header.
+    VariableOutOfRange(usize, u32, u32),
+    /// The input ended inside a clause that started on this line.
+    UnterminatedClause(usize),
+    /// The number of clauses in the header and the number of clauses read.
+    ClauseCountMismatch(usize, usize),
+}
+
+impl From<io::Error> for DimacsError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl fmt::Display for DimacsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "Could not read the DIMACS input: {}", e),
+            Self::MissingHeader => write!(f, "The DIMACS input has no 'p cnf' header."),
+            Self::InvalidHeader(l) => write!(
+                f,
+                "Line {}: the header must be of the form 'p cnf <variables> <clauses>'.",
+                l
+            ),
+            Self::DuplicateHeader(l) => write!(f, "Line {}: the header appears twice.", l),
+            Self::ClauseBeforeHeader(l) => {
+                write!(f, "Line {}: a clause appears before the header.", l)
+            }
+            Self::InvalidLiteral(l, token) => {
+                write!(f, "Line {}: '{}' is not a valid literal.", l, token)
+            }
+            Self::VariableOutOfRange(l, v, max) => write!(
+                f,
+                "Line {}: variable {} is above the {} variables declared in the header.",
+                l, v, max
+            ),
+            Self::UnterminatedClause(l) => write!(
+                f,
+                "Line {}: the clause that starts here is not terminated by 0.",
+                l
+            ),
+            Self::ClauseCountMismatch(declared, found) => write!(
+                f,
+                "The header declares {} clauses but {} clauses were found.",
+                declared, found
+            ),
+        }
+    }
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl CNF {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn parse_dimacs_header(line: &str, line_number: usize) -> Result<(u32, usize), DimacsError> {
+        let mut words = line.split_whitespace();
+        let header = (
+            words.next(),
+            words.next(),
+            words.next().and_then(|w| w.parse::<u32>().ok()),
+            words.next().and_then(|w| w.parse::<usize>().ok()),
+            words.next(),
+        );
+        match header {
+            (Some("p"), Some("cnf"), Some(variables), Some(clauses), None)
+                if variables < (1 << 31) =>
+            {
+                Ok((variables, clauses))
+            }
+            _ => Err(DimacsError::InvalidHeader(line_number)),
+        }
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Reads a CNF in the DIMACS format from a reader, the input is read line by line.
+    pub fn read_dimacs<R: BufRead>(mut reader: R) -> Result<Self, DimacsError> {
+        let mut header: Option<(u32, usize)> = None;
+        let mut clauses: Vec<Clause> = vec![];
+        let mut number_of_read_clauses = 0;
+        let mut clause: Vec<Literal> = vec![];
+        let mut clause_start_line = 0;
+
+        let mut line = String::new();
+        let mut line_number = 0;
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            line_number += 1;
+
+            let trimmed = line.trim_start();
+            if trimmed.starts_with('c') {
+                continue;
+            } else if trimmed.starts_with('%') {
+                break;
+            } else if trimmed.starts_with('p') {
+                if header.is_some() {
+                    return Err(DimacsError::DuplicateHeader(line_number));
+                }
+                let (variables, number_of_clauses) =
+                    Self::parse_dimacs_header(trimmed, line_number)?;
+                // do not trust the header too much when allocating
+                clauses.reserve(number_of_clauses.min(1 << 20));
+                header = Some((variables, number_of_clauses));
+                continue;
+            }
+
+            for token in trimmed.split_whitespace() {
+                let (max_variable, _) =
+                    header.ok_or(DimacsError::ClauseBeforeHeader(line_number))?;
+                let literal = match token.parse::<i32>() {
+                    Ok(l) if l != i32::MIN => l,
+                    _ => return Err(DimacsError::InvalidLiteral(line_number, token.to_string())),
+                };
+                if literal == 0 {
+                    number_of_read_clauses += 1;
+                    clause.sort_unstable();
+                    clause.dedup();
+                    if SortedVecOfLiterals::are_variables_sorted_and_unique(&clause) {
+                        clauses.push(Clause::from_ordered_set(std::mem::take(&mut clause)));
+                    } else {
+                        // a tautology
+                        clause.clear();
+                    }
+                    continue;
+                }
+                if literal.unsigned_abs() > max_variable {
+                    return Err(DimacsError::VariableOutOfRange(
+                        line_number,
+                        literal.unsigned_abs(),
+                        max_variable,
+                    ));
+                }
+                if clause.is_empty() {
+                    clause_start_line = line_number;
+                }
+                clause.push(Literal::from_dimacs_number(literal));
+            }
+        }
+
+        if !clause.is_empty() {
+            return Err(DimacsError::UnterminatedClause(clause_start_line));
+        }
+        let (_, number_of_clauses) = header.ok_or(DimacsError::MissingHeader)?;
+        if number_of_read_clauses != number_of_clauses {
+            return Err(DimacsError::ClauseCountMismatch(
+                number_of_clauses,
+                number_of_read_clauses,
+            ));
+        }
+
+        Ok(CNF::from_sequence(clauses))
+    }
+
+    /// Reads a CNF in the DIMACS format from a file, the file may be gzipped.
+    pub fn read_dimacs_from_path(path: &Path) -> Result<Self, DimacsError> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let is_gzipped = reader.fill_buf()?.starts_with(&[0x1f, 0x8b]);
+        if is_gzipped {
+            Self::read_dimacs(BufReader::new(MultiGzDecoder::new(reader)))
+        } else {
+            Self::read_dimacs(reader)
+        }
+    }
+
+    /// Reads a CNF from a DIMACS string.
+    pub fn from_dimacs(dimacs: &str) -> Result<Self, DimacsError> {
+        Self::read_dimacs(dimacs.as_bytes())
+    }
+
+    /// Writes the CNF in the DIMACS format, the number of variables in the header is the
+    /// maximal variable of the CNF.
+    pub fn write_dimacs<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(writer);
+        writeln!(
+            writer,
+            "p cnf {} {}",
+            self.max_variable_number.number(),
+            self.len()
+        )?;
+        for c in self.iter() {
+            for l in c.iter() {
+                write!(writer, "{} ", l.get_dimacs_number())?;
+            }
+            writeln!(writer, "0")?;
+        }
+        writer.flush()
+    }
+
+    /// Returns the CNF in the DIMACS format, reading the result with `from_dimacs` returns an
+    /// equal CNF, except that tautological clauses are dropped when reading.
+    pub fn to_dimacs(&self) -> String {
+        let mut result = vec![];
+        self.write_dimacs(&mut result).unwrap();
+        String::from_utf8(result).unwrap()
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clauses_spanning_lines_and_comments() {
+        let dimacs = "c a comment\np cnf 4 3\n1 -2\nc inside a clause\n 3 0\n-4 0 2\n0\n%\n0\n";
+        let cnf = CNF::from_dimacs(dimacs).unwrap();
+        let expected = CNF::from_sequence(vec![
+            Clause::from_sequence(
+                vec![1, -2, 3]
+                    .into_iter()
+                    .map(Literal::from_dimacs_number)
+                    .collect(),
+            ),
+            Clause::from_sequence(vec![Literal::from_dimacs_number(-4)]),
+            Clause::from_sequence(vec![Literal::from_dimacs_number(2)]),
+        ]);
+        assert_eq!(cnf, expected);
+    }
+
+    #[test]
+    fn test_tautologies_and_repeated_literals() {
+        let cnf = CNF::from_dimacs("p cnf 1 1\n1 -1 0\n").unwrap();
+        assert_eq!(cnf.len(), 0);
+
+        let cnf = CNF::from_dimacs("p cnf 3 3\n2 -1 2 0\n3 1 -3 2 0\n-3 -3 0\n").unwrap();
+        let expected = CNF::from_sequence(vec![
+            Clause::from_sequence(vec![
+                Literal::from_dimacs_number(-1),
+                Literal::from_dimacs_number(2),
+            ]),
+            Clause::from_sequence(vec![Literal::from_dimacs_number(-3)]),
+        ]);
+        assert_eq!(cnf, expected);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dimacs = "p cnf 5 4\n1 2 0\n-3 5 -1 0\n4 0\n-2 -5 0\n";
+        let cnf = CNF::from_dimacs(dimacs).unwrap();
+        assert_eq!(CNF::from_dimacs(&cnf.to_dimacs()).unwrap(), cnf);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |dimacs: &str| CNF::from_dimacs(dimacs).unwrap_err().to_string();
+        assert!(matches!(
+            CNF::from_dimacs("1 2 0\n"),
+            Err(DimacsError::ClauseBeforeHeader(1))
+        ));
+        assert!(matches!(
+            CNF::from_dimacs("c\np cnf 2\n"),
+            Err(DimacsError::InvalidHeader(2))
+        ));
+        assert!(matches!(
+            CNF::from_dimacs("p cnf 2 1\n1 x 0\n"),
+            Err(DimacsError::InvalidLiteral(2, _))
+        ));
+        assert!(matches!(
+            CNF::from_dimacs("p cnf 2 1\n1 3 0\n"),
+            Err(DimacsError::VariableOutOfRange(2, 3, 2))
+        ));
+        assert!(matches!(
+            CNF::from_dimacs("p cnf 2 2\n1 0\n2\n-1\n"),
+            Err(DimacsError::UnterminatedClause(3))
+        ));
+        assert!(matches!(
+            CNF::from_dimacs("p cnf 2 2\n1 0\n"),
+            Err(DimacsError::ClauseCountMismatch(2, 1))
+        ));
+        assert!(matches!(
+            CNF::from_dimacs("c\n"),
+            Err(DimacsError::MissingHeader)
+        ));
+        assert!(error("p cnf 2 1\np cnf 2 1\n").starts_with("Line 2"));
+    }
+}
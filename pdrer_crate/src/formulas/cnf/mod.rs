@@ -25,11 +25,16 @@ pub mod bounded_variable_addition_2;
 pub mod bounded_variable_addition_3;
 pub mod bounded_variable_elimination;
 pub mod construction;
+pub mod dimacs;
 pub mod implementations;
 pub mod operations;
+pub mod preprocessor;
 pub mod rename_variable;
 pub mod simplify;
 
 // ************************************************************************************************
 // re-exports of structs in these modules to simplify paths for other imports
 // ************************************************************************************************
+
+pub use dimacs::DimacsError;
+pub use preprocessor::{CNFPreprocessingResult, CNFPreprocessorParameters};
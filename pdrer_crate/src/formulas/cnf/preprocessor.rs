@@ -0,0 +1,786 @@
+//! A CNF preprocessor that packages bounded variable elimination (BVE) and structured bounded
+//! variable addition (BVA) for use in front of a SAT solver.
+//!
+//! Every step of the transformation can be written as a DRAT proof, so that a DRAT refutation of
+//! the simplified CNF appended to it is a refutation of the original CNF:
+//! - clauses of a new definition are added as RAT clauses on the fresh variable.
+//! - clauses that replace matched clauses are derived by RUP, splitting on the matched variables
+//!   when unit propagation alone is not enough.
+//! - resolvents of an eliminated variable are added as RUP clauses.
+//! - replaced clauses and clauses of eliminated variables are deleted.
+//!
+//! Models of the simplified CNF are models of the original CNF once they are extended to the
+//! eliminated variables using `CNFPreprocessingResult::extend_model`, or by an external tool
+//! using the stack written by `CNFPreprocessingResult::write_reconstruction_stack`.
+//!
+//! Variables are only added with the pattern matching of `bounded_variable_addition_2`, the
+//! simple BVA of `bounded_variable_addition_1` and the clustered patterns of
+//! `bounded_variable_addition_3` are not used here.
+
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::io::{self, Write};
+
+use fxhash::{FxHashMap, FxHashSet};
+
+use super::{
+    bounded_variable_addition_2::{api::PatternMatches, BVA2Pattern},
+    CNF,
+};
+use crate::{
+    formulas::{Clause, Literal, Variable},
+    models::{definition::DefinitionFunction, Definition, SortedVecOfLiterals},
+};
+
+// ************************************************************************************************
+// parameters
+// ************************************************************************************************
+
+/// Parameters of `CNF::preprocess`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CNFPreprocessorParameters {
+    /// Run bounded variable elimination before bounded variable addition.
+    pub bounded_variable_elimination: bool,
+    /// A variable is only eliminated if it appears in at most this many clauses.
+    pub bve_max_occurrences: usize,
+    /// A variable is only eliminated if none of its resolvents are longer than this.
+    pub bve_max_resolvent_length: usize,
+    /// Match the AND pattern `(C | a) & (C | b)` when adding variables.
+    pub and_pattern: bool,
+    /// Match the XOR pattern `(C | a | b) & (C | !a | !b)` when adding variables.
+    pub xor_pattern: bool,
+    /// Match the second XOR pattern, that adds an XOR and two AND definitions at once.
+    pub xor_pattern_2: bool,
+    /// The maximal number of definitions to add, each addition re-matches the patterns.
+    pub bva_max_iterations: usize,
+}
+
+impl CNFPreprocessorParameters {
+    pub const DEFAULT: Self = Self {
+        bounded_variable_elimination: true,
+        bve_max_occurrences: 16,
+        bve_max_resolvent_length: 16,
+        and_pattern: true,
+        xor_pattern: true,
+        xor_pattern_2: false,
+        bva_max_iterations: 1000,
+    };
+
+    fn get_patterns(&self) -> Vec<BVA2Pattern> {
+        let mut patterns = vec![];
+        if self.and_pattern {
+            patterns.push(BVA2Pattern::and_pattern());
+        }
+        if self.xor_pattern {
+            patterns.push(BVA2Pattern::xor_pattern());
+        }
+        if self.xor_pattern_2 {
+            patterns.push(BVA2Pattern::xor_pattern_2());
+        }
+        patterns
+    }
+}
+
+impl Default for CNFPreprocessorParameters {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+// ************************************************************************************************
+// result
+// ************************************************************************************************
+
+/// The result of `CNF::preprocess`.
+#[derive(Debug, Clone)]
+pub struct CNFPreprocessingResult {
+    /// The simplified CNF, it is satisfiable if and only if the original CNF is.
+    pub cnf: CNF,
+    /// The definitions of the variables that were added, in the order they were added.
+    pub definitions: Vec<Definition>,
+    /// The variables that were eliminated, in the order they were eliminated.
+    pub eliminated_variables: Vec<Variable>,
+    /// The stack used to extend models to the eliminated variables, the clauses that contained
+    /// each eliminated variable positively along with that literal, followed by the negation of
+    /// the variable as a unit clause.
+    eliminated_clauses: Vec<(Literal, Clause)>,
+}
+
+impl CNFPreprocessingResult {
+    /// Extends a model of the simplified CNF, given as the set of true literals, to a model of
+    /// the original CNF. Variables missing from the model are taken to be false.
+    pub fn extend_model(&self, model: &[Literal]) -> Vec<Literal> {
+        let mut values: FxHashMap<Variable, bool> = model
+            .iter()
+            .map(|l| (l.variable(), !l.is_negated()))
+            .collect();
+        let is_true = |values: &FxHashMap<Variable, bool>, l: &Literal| {
+            values.get(&l.variable()).copied().unwrap_or(false) != l.is_negated()
+        };
+
+        for (pivot, clause) in self.eliminated_clauses.iter().rev() {
+            for l in clause.iter() {
+                values.entry(l.variable()).or_insert(false);
+            }
+            if !clause.iter().any(|l| is_true(&values, l)) {
+                values.insert(pivot.variable(), !pivot.is_negated());
+            }
+        }
+
+        let mut result: Vec<Literal> = values
+            .into_iter()
+            .map(|(v, value)| v.literal(!value))
+            .collect();
+        result.sort_unstable_by_key(|l| l.variable());
+        result
+    }
+
+    /// Writes the stack that `extend_model` uses, one clause per line in the order they were
+    /// pushed. Each line is the pivot literal followed by the other literals of the clause and a
+    /// terminating `0`, all in DIMACS numbers. A model is extended by going over the lines from
+    /// last to first and making the pivot true whenever the clause is falsified, where unassigned
+    /// variables are false.
+    pub fn write_reconstruction_stack<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(writer);
+        for (pivot, clause) in self.eliminated_clauses.iter() {
+            write!(writer, "{} ", pivot.get_dimacs_number())?;
+            for l in clause.iter().filter(|l| *l != pivot) {
+                write!(writer, "{} ", l.get_dimacs_number())?;
+            }
+            writeln!(writer, "0")?;
+        }
+        writer.flush()
+    }
+}
+
+// ************************************************************************************************
+// preprocessor
+// ************************************************************************************************
+
+struct CNFPreprocessor<'a> {
+    parameters: CNFPreprocessorParameters,
+    clauses: Vec<Option<Clause>>,
+    present: FxHashSet<Clause>,
+    occurrences: FxHashMap<Literal, Vec<usize>>,
+    max_variable: Variable,
+    drat: Option<&'a mut dyn Write>,
+    definitions: Vec<Definition>,
+    definition_variables: FxHashMap<(DefinitionFunction, SortedVecOfLiterals), Variable>,
+    eliminated_variables: Vec<Variable>,
+    eliminated_clauses: Vec<(Literal, Clause)>,
+}
+
+impl<'a> CNFPreprocessor<'a> {
+    // ********************************************************************************************
+    // clause database
+    // ********************************************************************************************
+
+    fn new(
+        cnf: &CNF,
+        parameters: CNFPreprocessorParameters,
+        drat: Option<&'a mut dyn Write>,
+    ) -> Self {
+        let mut p = Self {
+            parameters,
+            clauses: Vec::with_capacity(cnf.len()),
+            present: FxHashSet::default(),
+            occurrences: FxHashMap::default(),
+            max_variable: cnf.get_max_variable(),
+            drat,
+            definitions: vec![],
+            definition_variables: FxHashMap::default(),
+            eliminated_variables: vec![],
+            eliminated_clauses: vec![],
+        };
+        for c in cnf.iter() {
+            p.insert_clause(c.to_owned());
+        }
+        p
+    }
+
+    fn insert_clause(&mut self, clause: Clause) {
+        if !self.present.insert(clause.clone()) {
+            return;
+        }
+        let i = self.clauses.len();
+        for l in clause.iter() {
+            self.occurrences.entry(*l).or_default().push(i);
+        }
+        self.clauses.push(Some(clause));
+    }
+
+    fn remove_clause(&mut self, i: usize) -> Clause {
+        let clause = self.clauses[i].take().unwrap();
+        self.present.remove(&clause);
+        clause
+    }
+
+    /// Returns the indices of the clauses that contain the literal, dropping removed clauses
+    /// from the occurrence list on the way.
+    fn get_occurrences(&mut self, l: Literal) -> Vec<usize> {
+        let clauses = &self.clauses;
+        match self.occurrences.get_mut(&l) {
+            Some(o) => {
+                o.retain(|i| clauses[*i].is_some());
+                o.clone()
+            }
+            None => vec![],
+        }
+    }
+
+    fn get_clauses(&self) -> Vec<Clause> {
+        self.clauses.iter().flatten().cloned().collect()
+    }
+
+    // ********************************************************************************************
+    // drat
+    // ********************************************************************************************
+
+    fn write_drat_line<'b>(
+        &mut self,
+        is_deletion: bool,
+        literals: impl Iterator<Item = &'b Literal>,
+    ) -> io::Result<()> {
+        if let Some(w) = self.drat.as_mut() {
+            if is_deletion {
+                write!(w, "d ")?;
+            }
+            for l in literals {
+                write!(w, "{} ", l.get_dimacs_number())?;
+            }
+            writeln!(w, "0")?;
+        }
+        Ok(())
+    }
+
+    fn add_to_proof(&mut self, clause: &[Literal]) -> io::Result<()> {
+        self.write_drat_line(false, clause.iter())
+    }
+
+    fn delete_from_proof(&mut self, clause: &Clause) -> io::Result<()> {
+        self.write_drat_line(true, clause.iter())
+    }
+
+    /// Checks if unit propagation on the given clauses falsifies one of them once all literals
+    /// of `clause` are assigned false.
+    fn is_rup(clause: &[Literal], clauses: &[Clause]) -> bool {
+        let mut values: FxHashMap<Variable, bool> = FxHashMap::default();
+        for l in clause.iter() {
+            if values.insert(l.variable(), l.is_negated()) == Some(!l.is_negated()) {
+                // the clause is a tautology
+                return true;
+            }
+        }
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for c in clauses.iter() {
+                let mut unassigned = None;
+                let mut number_of_unassigned = 0;
+                let mut is_satisfied = false;
+                for l in c.iter() {
+                    match values.get(&l.variable()) {
+                        Some(value) if *value != l.is_negated() => {
+                            is_satisfied = true;
+                            break;
+                        }
+                        Some(_) => {}
+                        None => {
+                            number_of_unassigned += 1;
+                            unassigned = Some(*l);
+                        }
+                    }
+                }
+                if is_satisfied {
+                    continue;
+                }
+                match (number_of_unassigned, unassigned) {
+                    (0, _) => return true,
+                    (1, Some(l)) => {
+                        values.insert(l.variable(), !l.is_negated());
+                        changed = true;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        false
+    }
+
+    /// Writes a RUP derivation of `clause` to the proof, given that it is implied by `reasons`
+    /// once all variables in `split` are assigned. Unit propagation is tried first, and when it
+    /// fails the derivation splits on the next variable and resolves the two halves.
+    fn derive_by_case_split(
+        &mut self,
+        clause: &mut Vec<Literal>,
+        split: &[Variable],
+        reasons: &[Clause],
+    ) -> io::Result<()> {
+        if split.is_empty() || Self::is_rup(clause, reasons) {
+            debug_assert!(Self::is_rup(clause, reasons));
+            return self.add_to_proof(clause);
+        }
+
+        for is_negated in [false, true] {
+            clause.push(split[0].literal(is_negated));
+            self.derive_by_case_split(clause, &split[1..], reasons)?;
+            clause.pop();
+        }
+        self.add_to_proof(clause)?;
+        for is_negated in [false, true] {
+            clause.push(split[0].literal(is_negated));
+            let lemma = Clause::from_sequence(clause.to_owned());
+            clause.pop();
+            self.delete_from_proof(&lemma)?;
+        }
+        Ok(())
+    }
+
+    // ********************************************************************************************
+    // bounded variable elimination
+    // ********************************************************************************************
+
+    fn resolve(a: &Clause, b: &Clause, v: Variable) -> Option<Clause> {
+        let mut literals: Vec<Literal> = a
+            .iter()
+            .chain(b.iter())
+            .filter(|l| l.variable() != v)
+            .copied()
+            .collect();
+        literals.sort_unstable();
+        literals.dedup();
+        if literals
+            .windows(2)
+            .any(|w| w[0].variable() == w[1].variable())
+        {
+            None
+        } else {
+            Some(Clause::from_ordered_set(literals))
+        }
+    }
+
+    /// Tries to eliminate the variable, returns true if it was eliminated.
+    fn try_to_eliminate(&mut self, v: Variable) -> io::Result<bool> {
+        let positive = self.get_occurrences(v.literal(false));
+        let negative = self.get_occurrences(v.literal(true));
+        let number_of_clauses = positive.len() + negative.len();
+        if number_of_clauses == 0 || number_of_clauses > self.parameters.bve_max_occurrences {
+            return Ok(false);
+        }
+
+        let mut resolvents = vec![];
+        for p in positive.iter() {
+            for n in negative.iter() {
+                let a = self.clauses[*p].as_ref().unwrap();
+                let b = self.clauses[*n].as_ref().unwrap();
+                if let Some(r) = Self::resolve(a, b, v) {
+                    if r.len() > self.parameters.bve_max_resolvent_length {
+                        return Ok(false);
+                    }
+                    resolvents.push(r);
+                    if resolvents.len() > number_of_clauses {
+                        return Ok(false);
+                    }
+                }
+            }
+        }
+
+        for r in resolvents {
+            if !self.present.contains(&r) {
+                self.add_to_proof(&r.iter().copied().collect::<Vec<Literal>>())?;
+                self.insert_clause(r);
+            }
+        }
+        // the model of v is reconstructed from the clauses with v, and is false otherwise
+        for i in positive {
+            let clause = self.remove_clause(i);
+            self.delete_from_proof(&clause)?;
+            self.eliminated_clauses.push((v.literal(false), clause));
+        }
+        for i in negative {
+            let clause = self.remove_clause(i);
+            self.delete_from_proof(&clause)?;
+        }
+        let unit = Clause::from_sequence(vec![v.literal(true)]);
+        self.eliminated_clauses.push((v.literal(true), unit));
+        self.eliminated_variables.push(v);
+        Ok(true)
+    }
+
+    fn bounded_variable_elimination(&mut self) -> io::Result<()> {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            let eliminated: FxHashSet<Variable> =
+                self.eliminated_variables.iter().copied().collect();
+            let mut variables: Vec<(usize, Variable)> = (1..=self.max_variable.number())
+                .map(Variable::new)
+                .map(|v| {
+                    let p = self
+                        .occurrences
+                        .get(&v.literal(false))
+                        .map_or(0, |o| o.len());
+                    let n = self
+                        .occurrences
+                        .get(&v.literal(true))
+                        .map_or(0, |o| o.len());
+                    (p * n, v)
+                })
+                .filter(|(_, v)| !eliminated.contains(v))
+                .collect();
+            variables.sort_unstable();
+            for (_, v) in variables {
+                if self.try_to_eliminate(v)? {
+                    changed = true;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // ********************************************************************************************
+    // bounded variable addition
+    // ********************************************************************************************
+
+    fn get_existing_definition(
+        &self,
+        f: DefinitionFunction,
+        inputs: &[Literal],
+    ) -> Option<Variable> {
+        let key = (f, SortedVecOfLiterals::from_ordered_set(inputs.to_vec()));
+        let v = *self.definition_variables.get(&key)?;
+        let d = Definition {
+            variable: v,
+            function: f,
+            inputs: key.1,
+        };
+        // the definition can only be reused if its clauses were not changed since
+        d.to_cnf()
+            .iter()
+            .all(|c| self.present.contains(c))
+            .then_some(v)
+    }
+
+    /// Returns the number of clauses the set of matches removes from the CNF, or None if
+    /// the matches cannot be applied.
+    fn get_gain(
+        &self,
+        key: &[(DefinitionFunction, Vec<Literal>)],
+        matches: usize,
+        p: &BVA2Pattern,
+    ) -> Option<isize> {
+        let mut cost = 0;
+        for (f, inputs) in key.iter() {
+            if !SortedVecOfLiterals::are_variables_sorted_and_unique(inputs) {
+                return None;
+            }
+            if self.get_existing_definition(*f, inputs).is_none() {
+                let d = Definition {
+                    variable: Variable::new(self.max_variable.number() + 1),
+                    function: *f,
+                    inputs: SortedVecOfLiterals::from_ordered_set(inputs.to_owned()),
+                };
+                cost += d.to_cnf().len() as isize;
+            }
+        }
+        let gain_per_match = p.inputs.len() as isize - p.result.len() as isize;
+        Some(gain_per_match * matches as isize - cost)
+    }
+
+    fn add_definition(
+        &mut self,
+        f: DefinitionFunction,
+        inputs: SortedVecOfLiterals,
+    ) -> io::Result<Variable> {
+        if let Some(v) = self.get_existing_definition(f, inputs.peek().peek()) {
+            return Ok(v);
+        }
+
+        self.max_variable.bump(1);
+        let v = self.max_variable;
+        let d = Definition {
+            variable: v,
+            function: f,
+            inputs,
+        };
+        debug_assert!(d.is_valid());
+
+        // the clauses with !v come first, each clause is then RAT on its literal of v
+        let mut cnf = d.to_cnf();
+        cnf.sort_by_key(|c| c.contains(&v.literal(false)));
+        for c in cnf {
+            let pivot = if c.contains(&v.literal(false)) {
+                v.literal(false)
+            } else {
+                v.literal(true)
+            };
+            let line: Vec<Literal> = [pivot]
+                .into_iter()
+                .chain(c.iter().copied().filter(|l| *l != pivot))
+                .collect();
+            self.add_to_proof(&line)?;
+            self.insert_clause(c);
+        }
+
+        self.definition_variables
+            .insert((d.function, d.inputs.clone()), v);
+        self.definitions.push(d);
+        Ok(v)
+    }
+
+    /// Matches the patterns and applies the set of matches that removes the most clauses,
+    /// returns false if no set of matches removes clauses.
+    fn perform_bva_iteration(&mut self, patterns: &[BVA2Pattern]) -> io::Result<bool> {
+        let cnfs = [self.get_clauses()];
+        let cnf = &cnfs[0];
+        let matches: PatternMatches = CNF::bva2_match_patterns_on_cnfs(&cnfs, patterns);
+        let best = CNF::bva2_get_definition_candidates(&matches, true)
+            .into_iter()
+            .filter_map(|(key, mut indices)| {
+                // a definition that already exists cannot replace clauses that define it
+                let existing: Vec<Variable> = key
+                    .iter()
+                    .filter_map(|(f, inputs)| self.get_existing_definition(*f, inputs))
+                    .collect();
+                indices.retain(|i| {
+                    let common = matches.v[*i].get_common_clause(cnf);
+                    existing.iter().all(|v| !common.contains_variable(v))
+                });
+                let p = matches.v[*indices.first()?].pattern;
+                self.get_gain(&key, indices.len(), p)
+                    .map(|gain| (gain, key, indices))
+            })
+            .max();
+        let (key, indices) = match best {
+            Some((gain, key, indices)) if gain > 0 => (key, indices),
+            _ => return Ok(false),
+        };
+
+        let mut variables = FxHashMap::default();
+        for (f, inputs) in key {
+            let inputs = SortedVecOfLiterals::from_ordered_set(inputs);
+            let v = self.add_definition(f, inputs.clone())?;
+            variables.insert((f, inputs), v);
+        }
+
+        let mut to_remove = FxHashSet::default();
+        let mut added = FxHashSet::default();
+        for m in indices.iter().map(|i| &matches.v[*i]) {
+            let before: Vec<Clause> = m.get_clauses_in_pattern(cnf).cloned().collect();
+            let (after, definitions) = m.get_resulting_clauses(cnf, |f, inputs| {
+                variables
+                    .get(&(*f, inputs.unwrap().to_owned()))
+                    .unwrap()
+                    .literal(false)
+            });
+
+            // every literal of the pattern that is not in the resulting clause may need a split
+            let mut split: Vec<Variable> = m
+                .variable_mapping
+                .iter()
+                .map(|(_, l)| l.variable())
+                .collect();
+            split.sort_unstable();
+            split.dedup();
+            let mut reasons = before.clone();
+            for d in definitions {
+                reasons.extend(d.unwrap().to_cnf());
+            }
+
+            for c in after {
+                if self.present.contains(&c) || !added.insert(c.clone()) {
+                    continue;
+                }
+                let split: Vec<Variable> = split
+                    .iter()
+                    .copied()
+                    .filter(|v| !c.contains_variable(v))
+                    .collect();
+                let mut lemma: Vec<Literal> = c.iter().copied().collect();
+                self.derive_by_case_split(&mut lemma, &split, &reasons)?;
+                self.insert_clause(c);
+            }
+            to_remove.extend(before);
+        }
+
+        for i in 0..self.clauses.len() {
+            let should_remove = match &self.clauses[i] {
+                Some(c) => to_remove.contains(c) && !added.contains(c),
+                None => false,
+            };
+            if should_remove {
+                let clause = self.remove_clause(i);
+                self.delete_from_proof(&clause)?;
+            }
+        }
+        Ok(true)
+    }
+
+    fn bounded_variable_addition(&mut self) -> io::Result<()> {
+        let patterns = self.parameters.get_patterns();
+        if patterns.is_empty() {
+            return Ok(());
+        }
+        for _ in 0..self.parameters.bva_max_iterations {
+            if !self.perform_bva_iteration(&patterns)? {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn run(mut self) -> io::Result<CNFPreprocessingResult> {
+        if self.parameters.bounded_variable_elimination {
+            self.bounded_variable_elimination()?;
+        }
+        self.bounded_variable_addition()?;
+        if let Some(w) = self.drat.as_mut() {
+            w.flush()?;
+        }
+
+        Ok(CNFPreprocessingResult {
+            cnf: CNF::from_sequence(self.get_clauses()),
+            definitions: self.definitions,
+            eliminated_variables: self.eliminated_variables,
+            eliminated_clauses: self.eliminated_clauses,
+        })
+    }
+}
+
+// ************************************************************************************************
+// API
+// ************************************************************************************************
+
+impl CNF {
+    /// Simplifies the CNF using bounded variable elimination followed by bounded variable
+    /// addition with the patterns selected in the parameters.
+    pub fn preprocess(&self, parameters: CNFPreprocessorParameters) -> CNFPreprocessingResult {
+        CNFPreprocessor::new(self, parameters, None)
+            .run()
+            .expect("No proof is written so writing cannot fail.")
+    }
+
+    /// Like `preprocess`, and also writes a DRAT proof that derives the simplified CNF from this
+    /// CNF to `drat`.
+    pub fn preprocess_with_drat<W: Write>(
+        &self,
+        parameters: CNFPreprocessorParameters,
+        mut drat: W,
+    ) -> io::Result<CNFPreprocessingResult> {
+        CNFPreprocessor::new(self, parameters, Some(&mut drat)).run()
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvers::sat::incremental::{CaDiCalSolver, SatResult};
+
+    fn is_sat(clauses: &[Clause]) -> bool {
+        let mut solver = CaDiCalSolver::new(0);
+        for c in clauses.iter() {
+            solver.add_clause(c.iter().copied());
+        }
+        matches!(solver.solve(vec![], vec![]), SatResult::Sat)
+    }
+
+    #[test]
+    fn test_and_pattern_is_added_and_proof_lines_are_rup_or_rat() {
+        // five pairs of clauses that share the literals 1 and 2, with different remainders
+        let mut dimacs = String::from("p cnf 12 10\n");
+        for i in 3..=7 {
+            dimacs.push_str(&format!("1 {} 0\n2 {} 0\n", i, i));
+        }
+        let cnf = CNF::from_dimacs(&dimacs).unwrap();
+        let parameters = CNFPreprocessorParameters {
+            bounded_variable_elimination: false,
+            ..CNFPreprocessorParameters::DEFAULT
+        };
+
+        let mut proof = vec![];
+        let result = cnf.preprocess_with_drat(parameters, &mut proof).unwrap();
+        assert_eq!(result.definitions.len(), 1);
+        assert_eq!(result.cnf.len(), 5 + 3);
+
+        // every added clause is implied by the clauses before it
+        let mut clauses = cnf.iter().cloned().collect::<Vec<Clause>>();
+        for line in String::from_utf8(proof).unwrap().lines() {
+            let (is_deletion, line) = match line.strip_prefix("d ") {
+                Some(l) => (true, l),
+                None => (false, line),
+            };
+            let literals: Vec<Literal> = line
+                .split_whitespace()
+                .map(|x| x.parse::<i32>().unwrap())
+                .take_while(|x| *x != 0)
+                .map(Literal::from_dimacs_number)
+                .collect();
+            let clause = Clause::from_sequence(literals.clone());
+            if is_deletion {
+                let i = clauses.iter().position(|c| *c == clause).unwrap();
+                clauses.remove(i);
+            } else {
+                let is_rat_on_first = literals.first().is_some_and(|pivot| {
+                    clauses.iter().filter(|c| c.contains(&!*pivot)).all(|c| {
+                        let mut resolvent = literals.clone();
+                        resolvent.extend(c.iter().filter(|l| **l != !*pivot));
+                        CNFPreprocessor::is_rup(&resolvent, &clauses)
+                    })
+                });
+                assert!(is_rat_on_first || CNFPreprocessor::is_rup(&literals, &clauses));
+                clauses.push(clause);
+            }
+        }
+        assert_eq!(CNF::from_sequence(clauses), result.cnf);
+    }
+
+    #[test]
+    fn test_preprocessing_keeps_satisfiability() {
+        let dimacs = "p cnf 6 9\n1 2 3 0\n1 2 4 0\n-1 -2 3 0\n-1 -2 4 0\n1 5 0\n2 5 0\n3 -5 6 0\n4 -6 0\n-3 -4 0\n";
+        let cnf = CNF::from_dimacs(dimacs).unwrap();
+        let original: Vec<Clause> = cnf.iter().cloned().collect();
+        let result = cnf.preprocess(CNFPreprocessorParameters::DEFAULT);
+        let simplified: Vec<Clause> = result.cnf.iter().cloned().collect();
+        assert_eq!(is_sat(&original), is_sat(&simplified));
+
+        // extend a model of the simplified cnf and check it on the original one
+        let mut solver = CaDiCalSolver::new(0);
+        for c in simplified.iter() {
+            solver.add_clause(c.iter().copied());
+        }
+        if let SatResult::Sat = solver.solve(vec![], vec![]) {
+            let model: Vec<Literal> = result
+                .cnf
+                .get_variables()
+                .iter()
+                .map(|v| v.literal(!solver.val(v.literal(false)).unwrap_or(false)))
+                .collect();
+            let model = result.extend_model(&model);
+            for c in original.iter() {
+                assert!(c.iter().any(|l| model.contains(l)));
+            }
+        }
+
+        // the written stack has a line per clause of the stack, starting with its pivot
+        let mut stack = vec![];
+        result.write_reconstruction_stack(&mut stack).unwrap();
+        let stack = String::from_utf8(stack).unwrap();
+        assert_eq!(stack.lines().count(), result.eliminated_clauses.len());
+        for (line, (pivot, clause)) in stack.lines().zip(result.eliminated_clauses.iter()) {
+            let numbers: Vec<i32> = line
+                .split_whitespace()
+                .map(|x| x.parse().unwrap())
+                .collect();
+            assert_eq!(numbers[0], pivot.get_dimacs_number());
+            assert_eq!(numbers.len(), clause.len() + 1);
+            assert_eq!(numbers.last(), Some(&0));
+        }
+    }
+}
@@ -8,6 +8,7 @@
 // rust submodule declaration, they get searched in their respective file  names
 // ************************************************************************************************
 
+pub mod cardinality;
 pub mod clause; // requires file in this directory with the name 'clause.rs'
 pub mod cnf; // requires file in this directory with the name 'cnf.rs'
 pub mod cube;
@@ -18,6 +19,7 @@ pub mod variable;
 // re-exports of structs in these modules to simplify paths for other imports
 // ************************************************************************************************
 
+pub use cardinality::{CardinalityEncoding, PseudoBooleanEncoding};
 pub use clause::Clause;
 pub use cnf::CNF;
 pub use cube::Cube;
@@ -555,6 +555,20 @@ impl AndInverterGraph {
         Self::from_vector_of_bytes(&file_as_vec_of_bytes)
     }
 
+    /// Adds a symbol to an input ('i'), latch ('l'), output ('o'), bad ('b') or constraint ('c')
+    /// at the provided index.
+    pub fn add_symbol(
+        &mut self,
+        symbol_type: char,
+        index: u32,
+        symbol: &str,
+    ) -> Result<(), String> {
+        if !"ilobc".contains(symbol_type) {
+            return Err(format!("Unknown symbol type '{symbol_type}'."));
+        }
+        self.add_symbol_to_node(&symbol_type.to_string(), index, symbol)
+    }
+
     /// This is the way to create an AndInverterGraph object directly.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -49,6 +49,35 @@ impl AndInverterGraph {
         (from..to).map(Signal::new).collect()
     }
 
+    /// Function that gets a vector of the latch signals in the system, in the order
+    /// they appear in the AIGER file (the index of a latch symbol refers to this order).
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_formal_verification::models::{Signal, AndInverterGraph, TernaryValue};
+    ///
+    /// let aig = AndInverterGraph::new(
+    ///     Signal::new(3),
+    ///     1,
+    ///     &[
+    ///         (Signal::new(1).wire(false), TernaryValue::False),
+    ///         (Signal::new(2).wire(true), TernaryValue::True),
+    ///     ],
+    ///     vec![],
+    ///     vec![Signal::new(3).wire(false)],
+    ///     vec![],
+    ///     &[],
+    ///     String::new(),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(vec![Signal::new(2), Signal::new(3)], aig.get_latch_signals());
+    /// ```
+    pub fn get_latch_signals(&self) -> Vec<Signal> {
+        let from = 1 + self.number_of_inputs;
+        let to = from + self.number_of_latches;
+        (from..to).map(Signal::new).collect()
+    }
+
     /// Function that gets a vector describing the bad nodes in the system.
     /// The output is a vector containing usize numbers, these are the literals
     /// that are bad.
@@ -243,4 +272,21 @@ impl AndInverterGraph {
     pub fn get_comments(&self) -> &String {
         &self.comments
     }
+
+    /// Returns the symbols of the AIG as (type, index, symbol), where the type is one of 'i',
+    /// 'l', 'o', 'b' or 'c' and the index is the position of the input, latch, output, bad or
+    /// constraint.
+    pub fn get_symbols(&self) -> Vec<(char, u32, String)> {
+        let a = self.input_symbols.iter().map(|(x, y)| ('i', x, y));
+        let b = self.latch_symbols.iter().map(|(x, y)| ('l', x, y));
+        let c = self.output_symbols.iter().map(|(x, y)| ('o', x, y));
+        let d = self.bad_symbols.iter().map(|(x, y)| ('b', x, y));
+        let e = self.constraint_symbols.iter().map(|(x, y)| ('c', x, y));
+        a.chain(b)
+            .chain(c)
+            .chain(d)
+            .chain(e)
+            .map(|(t, i, symbol)| (t, *i, symbol.to_owned()))
+            .collect()
+    }
 }
@@ -0,0 +1,68 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use super::{BitParallelGate, BitParallelSimulator};
+use crate::models::circuit::node_types::{CircuitNode, CircuitNodeType};
+use crate::models::Circuit;
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl BitParallelSimulator {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn convert_circuit_node_to_gate(node: &CircuitNode) -> Option<BitParallelGate> {
+        match &node.node_type {
+            CircuitNodeType::And(a) => Some(BitParallelGate::And(a.inputs.clone())),
+            CircuitNodeType::GenericGate(g) => {
+                let tt = &g.truth_table;
+                let rows = (0..tt.calculate_number_of_rows())
+                    .filter(|row| (tt.peek() & tt.get_mask()) >> row & 1 == 1)
+                    .collect();
+                Some(BitParallelGate::Generic(
+                    tt.get_signals().peek().to_owned(),
+                    rows,
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Creates a simulator for the circuit, all the signals start at 0.
+    pub fn new(circuit: &Circuit) -> Self {
+        let length = circuit.get_highest_signal().number() as usize + 1;
+        let gates = circuit
+            .iter_sorted()
+            .filter_map(|s| {
+                let node = circuit.get_node(&s).unwrap();
+                Self::convert_circuit_node_to_gate(node).map(|g| (s, g))
+            })
+            .collect();
+
+        let latches: Vec<_> = circuit.get_latch_signals().iter().copied().collect();
+        let (latch_inputs, latch_initial_values) = latches
+            .iter()
+            .map(|l| match &circuit.get_node(l).unwrap().node_type {
+                CircuitNodeType::Latch(latch) => (latch.input, latch.initial),
+                _ => unreachable!(),
+            })
+            .unzip();
+
+        Self {
+            values: vec![0; length],
+            gates,
+            inputs: circuit.get_input_signals().iter().copied().collect(),
+            latches,
+            latch_inputs,
+            latch_initial_values,
+        }
+    }
+}
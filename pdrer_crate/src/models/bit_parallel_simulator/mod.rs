@@ -0,0 +1,45 @@
+//! Two-valued circuit simulation where every signal holds 64 independent values, one per bit of
+//! a machine word. This allows simulating 64 states (or input sequences) at the cost of one.
+
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use super::{Signal, TernaryValue, UniqueSortedVec, Wire};
+
+// ************************************************************************************************
+// types
+// ************************************************************************************************
+
+/// The 64 values of a signal, bit `i` is the value in the `i`-th simulation.
+pub type SimulationWord = u64;
+
+#[derive(Clone, Debug)]
+enum BitParallelGate {
+    And(UniqueSortedVec<Wire>),
+    /// The inputs of the truth table in order, and the rows in which it evaluates to true.
+    Generic(Vec<Signal>, Vec<usize>),
+}
+
+// ************************************************************************************************
+// struct
+// ************************************************************************************************
+
+#[derive(Clone, Debug)]
+pub struct BitParallelSimulator {
+    /// The current value of every signal, indexed by the signal number.
+    values: Vec<SimulationWord>,
+    /// The gates in topological order.
+    gates: Vec<(Signal, BitParallelGate)>,
+    inputs: Vec<Signal>,
+    latches: Vec<Signal>,
+    latch_inputs: Vec<Wire>,
+    latch_initial_values: Vec<TernaryValue>,
+}
+
+// ************************************************************************************************
+// rust submodule declaration, they get searched in their respective file  names
+// ************************************************************************************************
+
+pub mod construction;
+pub mod simulation;
@@ -0,0 +1,96 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use super::{BitParallelGate, BitParallelSimulator, SimulationWord};
+use crate::models::{Signal, TernaryValue, Wire};
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl BitParallelSimulator {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn evaluate_gate(&self, gate: &BitParallelGate) -> SimulationWord {
+        match gate {
+            BitParallelGate::And(inputs) => inputs
+                .iter()
+                .fold(SimulationWord::MAX, |r, w| r & self.get_wire_value(*w)),
+            BitParallelGate::Generic(inputs, rows) => {
+                let mut result = 0;
+                for row in rows.iter() {
+                    let mut minterm = SimulationWord::MAX;
+                    for (i, s) in inputs.iter().enumerate() {
+                        let v = self.values[s.number() as usize];
+                        minterm &= if (row >> i) & 1 == 1 { v } else { !v };
+                    }
+                    result |= minterm;
+                }
+                result
+            }
+        }
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    pub fn get_input_signals(&self) -> &[Signal] {
+        &self.inputs
+    }
+
+    pub fn get_latch_signals(&self) -> &[Signal] {
+        &self.latches
+    }
+
+    /// The initial values of the latches, in the order of `get_latch_signals`.
+    pub fn get_latch_initial_values(&self) -> &[TernaryValue] {
+        &self.latch_initial_values
+    }
+
+    pub fn set_value(&mut self, signal: Signal, value: SimulationWord) {
+        self.values[signal.number() as usize] = value;
+    }
+
+    pub fn get_value(&self, signal: Signal) -> SimulationWord {
+        self.values[signal.number() as usize]
+    }
+
+    pub fn get_wire_value(&self, wire: Wire) -> SimulationWord {
+        let v = self.values[wire.signal().number() as usize];
+        if wire.is_negated() {
+            !v
+        } else {
+            v
+        }
+    }
+
+    /// Evaluates all the gates given the current values of the inputs and latches.
+    pub fn simulate(&mut self) {
+        for i in 0..self.gates.len() {
+            let value = self.evaluate_gate(&self.gates[i].1);
+            let signal = self.gates[i].0;
+            self.values[signal.number() as usize] = value;
+        }
+    }
+
+    /// The values the latches will have in the next cycle, in the order of `get_latch_signals`.
+    /// Valid after `simulate` was called.
+    pub fn get_next_latch_values(&self) -> Vec<SimulationWord> {
+        self.latch_inputs
+            .iter()
+            .map(|w| self.get_wire_value(*w))
+            .collect()
+    }
+
+    /// Moves the latches to their next values, the gates need to be simulated again afterwards.
+    pub fn step(&mut self) {
+        let next = self.get_next_latch_values();
+        for (l, v) in self.latches.iter().zip(next) {
+            self.values[l.number() as usize] = v;
+        }
+    }
+}
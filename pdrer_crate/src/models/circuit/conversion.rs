@@ -0,0 +1,453 @@
+//! Conversion of a circuit back into an AIG, so that a simplified circuit can be written out in
+//! the AIGER format and used by other tools.
+//!
+//! And gates with more than two inputs are split into balanced trees of 2-input and gates and
+//! generic gates are bit-blasted using Shannon expansion. The gates of the AIG are structurally
+//! hashed while they are created, so logic that is shared between several generic gates is only
+//! created once.
+
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use super::{node_types::CircuitNodeType, Circuit};
+use crate::models::{
+    truth_table::TruthTableEntry, AndInverterGraph, Signal, SignalTracker, TernaryValue,
+    UniqueSortedHashMap, UniqueSortedVec, Wire,
+};
+use fxhash::FxHashMap;
+
+// ************************************************************************************************
+// struct
+// ************************************************************************************************
+
+/// The and gates of the AIG under construction, the i-th gate is signal `first_gate + i`.
+struct AigAndGates {
+    first_gate: u32,
+    gates: Vec<(Wire, Wire)>,
+    strash: FxHashMap<(Wire, Wire), Wire>,
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl AigAndGates {
+    fn new(first_gate: u32) -> Self {
+        Self {
+            first_gate,
+            gates: vec![],
+            strash: FxHashMap::default(),
+        }
+    }
+
+    fn add_and(&mut self, a: Wire, b: Wire) -> Wire {
+        if a == Wire::CONSTANT_ZERO || b == Wire::CONSTANT_ZERO || a == !b {
+            return Wire::CONSTANT_ZERO;
+        }
+        if a == Wire::CONSTANT_ONE || a == b {
+            return b;
+        }
+        if b == Wire::CONSTANT_ONE {
+            return a;
+        }
+        let key = if a > b { (a, b) } else { (b, a) };
+        if let Some(w) = self.strash.get(&key) {
+            return *w;
+        }
+        let w = Signal::new(self.first_gate + self.gates.len() as u32).wire(false);
+        self.gates.push(key);
+        self.strash.insert(key, w);
+        w
+    }
+
+    /// Adds the and of all the wires as a balanced tree.
+    fn add_and_of_wires(&mut self, mut wires: Vec<Wire>) -> Wire {
+        if wires.is_empty() {
+            return Wire::CONSTANT_ONE;
+        }
+        while wires.len() > 1 {
+            let mut next = Vec::with_capacity(wires.len().div_ceil(2));
+            for pair in wires.chunks(2) {
+                match pair {
+                    [a, b] => next.push(self.add_and(*a, *b)),
+                    [a] => next.push(*a),
+                    _ => unreachable!(),
+                }
+            }
+            wires = next;
+        }
+        wires[0]
+    }
+
+    /// Adds the function of the truth table over the first `inputs.len()` inputs by splitting it
+    /// on the last input, `f = (x & f1) | (!x & f0)`.
+    fn add_truth_table(&mut self, f: TruthTableEntry, inputs: &[Wire]) -> Wire {
+        let rows = 1 << inputs.len();
+        let mask = if rows == TruthTableEntry::BITS as usize {
+            TruthTableEntry::MAX
+        } else {
+            !(TruthTableEntry::MAX << rows)
+        };
+        let f = f & mask;
+        if f == 0 {
+            return Wire::CONSTANT_ZERO;
+        } else if f == mask {
+            return Wire::CONSTANT_ONE;
+        }
+
+        let half = rows / 2;
+        let low_mask = mask >> half;
+        let f0 = f & low_mask;
+        let f1 = (f >> half) & low_mask;
+        let rest = &inputs[..inputs.len() - 1];
+        if f0 == f1 {
+            return self.add_truth_table(f0, rest);
+        }
+        let x = inputs[inputs.len() - 1];
+        let w0 = self.add_truth_table(f0, rest);
+        let w1 = self.add_truth_table(f1, rest);
+        let a = self.add_and(x, w1);
+        let b = self.add_and(!x, w0);
+        !self.add_and(!a, !b)
+    }
+}
+
+impl Circuit {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn translate_wire_to_aig(
+        wire: &Wire,
+        signal_to_wire: &UniqueSortedHashMap<Signal, Wire>,
+    ) -> Wire {
+        let w = *signal_to_wire.get(&wire.signal()).unwrap();
+        if wire.is_negated() {
+            !w
+        } else {
+            w
+        }
+    }
+
+    /// Orders the wires of the circuit like the original wires they came from, including
+    /// repetitions, wires that did not come from an original wire are placed at the end.
+    /// Returns the ordered wires and the new index of every original wire.
+    fn get_wires_in_original_order<F: Fn(&Wire) -> Option<Wire>>(
+        wires: &UniqueSortedVec<Wire>,
+        original: &[Wire],
+        translate: F,
+    ) -> (Vec<Wire>, Vec<Option<usize>>) {
+        let mut result = Vec::with_capacity(original.len());
+        let mut is_placed = vec![false; wires.len()];
+        let new_indices = original
+            .iter()
+            .map(|w| {
+                let w = translate(w)?;
+                let i = wires.peek().binary_search(&w).ok()?;
+                is_placed[i] = true;
+                result.push(w);
+                Some(result.len() - 1)
+            })
+            .collect();
+        for (w, is_placed) in wires.iter().zip(is_placed) {
+            if !is_placed {
+                result.push(*w);
+            }
+        }
+        (result, new_indices)
+    }
+
+    /// Returns the AIG with the given outputs, bad and constraint wires, and the wire in the AIG
+    /// of every signal of the circuit.
+    fn to_aig_and_signal_map(
+        &self,
+        outputs: &[Wire],
+        bad: &[Wire],
+        constraints: &[Wire],
+    ) -> (AndInverterGraph, UniqueSortedHashMap<Signal, Wire>) {
+        let number_of_inputs = self.inputs.len() as u32;
+        let number_of_latches = self.latches.len() as u32;
+        let mut signal_to_wire = UniqueSortedHashMap::new(self.greatest_signal);
+        signal_to_wire.insert(Signal::GROUND, Wire::CONSTANT_ZERO);
+        for (i, s) in self.inputs.iter().enumerate() {
+            signal_to_wire.insert(*s, Signal::new(i as u32 + 1).wire(false));
+        }
+        for (i, s) in self.latches.iter().enumerate() {
+            let signal = Signal::new(number_of_inputs + i as u32 + 1);
+            signal_to_wire.insert(*s, signal.wire(false));
+        }
+
+        // gates are sorted topologically since the inputs of a gate are smaller than it
+        let mut and_gates = AigAndGates::new(number_of_inputs + number_of_latches + 1);
+        for s in self.gates.iter() {
+            let w = match &self.nodes.get(s).unwrap().node_type {
+                CircuitNodeType::And(a) => {
+                    let inputs = a
+                        .inputs
+                        .iter()
+                        .map(|w| Self::translate_wire_to_aig(w, &signal_to_wire))
+                        .collect();
+                    and_gates.add_and_of_wires(inputs)
+                }
+                CircuitNodeType::GenericGate(g) => {
+                    let tt = &g.truth_table;
+                    let inputs: Vec<Wire> = tt
+                        .get_signals()
+                        .iter()
+                        .map(|s| *signal_to_wire.get(s).unwrap())
+                        .collect();
+                    and_gates.add_truth_table(tt.peek() & tt.get_mask(), &inputs)
+                }
+                _ => unreachable!(),
+            };
+            signal_to_wire.insert(*s, w);
+        }
+
+        let latches: Vec<(Wire, TernaryValue)> = self
+            .latches
+            .iter()
+            .map(|s| match &self.nodes.get(s).unwrap().node_type {
+                CircuitNodeType::Latch(l) => (
+                    Self::translate_wire_to_aig(&l.input, &signal_to_wire),
+                    l.initial,
+                ),
+                _ => unreachable!(),
+            })
+            .collect();
+        let translate = |wires: &[Wire]| -> Vec<Wire> {
+            wires
+                .iter()
+                .map(|w| Self::translate_wire_to_aig(w, &signal_to_wire))
+                .collect()
+        };
+
+        let max_signal =
+            Signal::new(number_of_inputs + number_of_latches + and_gates.gates.len() as u32);
+        let aig = AndInverterGraph::new(
+            max_signal,
+            number_of_inputs,
+            &latches,
+            translate(outputs),
+            translate(bad),
+            translate(constraints),
+            &and_gates.gates,
+            String::new(),
+        )
+        .unwrap();
+        (aig, signal_to_wire)
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Converts the circuit into an AIG. Inputs and latches keep their order, and latches keep
+    /// their reset values. The circuit stores outputs, bad and constraint wires as sorted sets,
+    /// so they appear sorted and without repetitions, use `to_aig_with_symbols` in order to keep
+    /// the indices of the properties of the original AIG. And gates with more than two inputs
+    /// and generic gates are bit-blasted into 2-input and gates.
+    pub fn to_aig(&self) -> AndInverterGraph {
+        self.to_aig_and_signal_map(
+            self.outputs.peek(),
+            self.bad.peek(),
+            self.constraints.peek(),
+        )
+        .0
+    }
+
+    /// Converts the circuit into an AIG like `to_aig`, and copies the symbols of the original AIG
+    /// the circuit was created from. The tracker should contain the transformations that were
+    /// performed on the circuit since it was created. Outputs, bad and constraint wires keep the
+    /// order and repetitions of the original wires they came from, so the indices of properties
+    /// are kept as long as no property was removed. Symbols of inputs and latches that were
+    /// removed, and of outputs, bad and constraint wires that no longer exist, are dropped.
+    pub fn to_aig_with_symbols(
+        &self,
+        original: &AndInverterGraph,
+        tracker: &SignalTracker,
+    ) -> AndInverterGraph {
+        // the final wire of a wire in the original AIG
+        let translate = |w: &Wire| -> Option<Wire> {
+            tracker
+                .get(w.signal())
+                .filter(|s| *s == Signal::GROUND || self.nodes.contains_key(s))
+                .map(|s| s.wire(w.is_negated()))
+        };
+
+        let original_outputs = original.get_output_wires();
+        let original_bad = original.get_bad_wires();
+        let original_constraints = original.get_constraints_wires();
+        let (outputs, output_indices) =
+            Self::get_wires_in_original_order(&self.outputs, &original_outputs, translate);
+        let (bad, bad_indices) =
+            Self::get_wires_in_original_order(&self.bad, &original_bad, translate);
+        let (constraints, constraint_indices) =
+            Self::get_wires_in_original_order(&self.constraints, &original_constraints, translate);
+        let (mut aig, _) = self.to_aig_and_signal_map(&outputs, &bad, &constraints);
+
+        let original_inputs = original.get_input_signals();
+        let original_latches: Vec<Signal> = original
+            .get_latch_information()
+            .iter()
+            .map(|l| l.output)
+            .collect();
+        let mut is_named = FxHashMap::default();
+        for (symbol_type, index, symbol) in original.get_symbols() {
+            let index = index as usize;
+            let new_index = match symbol_type {
+                'i' => original_inputs
+                    .get(index)
+                    .and_then(|s| translate(&s.wire(false)))
+                    .and_then(|w| self.inputs.peek().binary_search(&w.signal()).ok()),
+                'l' => original_latches
+                    .get(index)
+                    .and_then(|s| translate(&s.wire(false)))
+                    .and_then(|w| self.latches.peek().binary_search(&w.signal()).ok()),
+                'o' => output_indices.get(index).copied().flatten(),
+                'b' => bad_indices.get(index).copied().flatten(),
+                'c' => constraint_indices.get(index).copied().flatten(),
+                _ => unreachable!(),
+            };
+
+            // several original inputs or latches can be merged into one, only the first symbol
+            // is kept
+            if let Some(i) = new_index {
+                if is_named.insert((symbol_type, i), ()).is_none() {
+                    aig.add_symbol(symbol_type, i as u32, &symbol).unwrap();
+                }
+            }
+        }
+        aig
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[test]
+fn test_circuit_to_aig() {
+    use crate::models::circuit::simplifiers::{
+        detect_generic_patterns::CircuitGenericPatternDetector, CircuitAndGateMerger,
+    };
+    use crate::models::circuit::CircuitSimplifier;
+    use crate::models::CircuitSimulator;
+
+    // inputs 1, 2, 3 and latch 4, 5 = 1 & 2, 6 = !1 & !2, 7 = !5 & !6 (xor of 1 and 2),
+    // 8 = 7 & 3, 9 = 8 & 4 with 9 as bad, and the latch is 8 with reset 1
+    let w = |n: u32, negated: bool| Signal::new(n).wire(negated);
+    let mut aig = AndInverterGraph::new(
+        Signal::new(9),
+        3,
+        &[(w(8, false), TernaryValue::True)],
+        vec![],
+        vec![w(9, false)],
+        vec![],
+        &[
+            (w(1, false), w(2, false)),
+            (w(1, true), w(2, true)),
+            (w(5, true), w(6, true)),
+            (w(7, false), w(3, false)),
+            (w(8, false), w(4, false)),
+        ],
+        String::new(),
+    )
+    .unwrap();
+    for (t, i, symbol) in [('i', 0, "a"), ('i', 1, "b"), ('l', 0, "r"), ('b', 0, "p")] {
+        aig.add_symbol(t, i, symbol).unwrap();
+    }
+    let mut circuit = Circuit::from_aig(&aig);
+    let mut tracker = SignalTracker::new();
+    tracker.push(CircuitGenericPatternDetector::new(false).simplify(&mut circuit));
+    tracker.push(CircuitAndGateMerger::new(false).simplify(&mut circuit));
+    assert!(circuit.get_gate_signals().iter().any(|s| matches!(
+        circuit.get_node(s).unwrap().node_type,
+        CircuitNodeType::GenericGate(_)
+    )));
+
+    let exported = circuit.to_aig_with_symbols(&aig, &tracker);
+    let text = exported.get_aag_string();
+    assert!(text.contains("i0 a\ni1 b\nl0 r\nb0 p\n"));
+    assert_eq!(
+        exported.get_latch_information()[0].initial,
+        TernaryValue::True
+    );
+
+    // the exported AIG computes the same functions as the original one
+    let round_trip =
+        Circuit::from_aig(&AndInverterGraph::from_vector_of_bytes(&exported.get_aig()).unwrap());
+    let original = Circuit::from_aig(&aig);
+    let mut s1 = CircuitSimulator::new(&original);
+    let mut s2 = CircuitSimulator::new(&round_trip);
+    let get_latch_input = |c: &Circuit| match &c
+        .get_node(&c.get_latch_signals().peek()[0])
+        .unwrap()
+        .node_type
+    {
+        CircuitNodeType::Latch(l) => l.input,
+        _ => unreachable!(),
+    };
+    for assignment in 0..16 {
+        let values: Vec<TernaryValue> = (0..4)
+            .map(|i| {
+                if (assignment >> i) & 1 == 1 {
+                    TernaryValue::True
+                } else {
+                    TernaryValue::False
+                }
+            })
+            .collect();
+        let mut results = vec![];
+        for (c, s) in [(&original, &mut s1), (&round_trip, &mut s2)] {
+            let signals = c
+                .get_input_signals()
+                .iter()
+                .chain(c.get_latch_signals().iter());
+            s.full_simulation(signals.copied().zip(values.iter().copied()));
+            let wires = [c.get_bad_wires().peek()[0], get_latch_input(c)];
+            let v = s.get_signal_simulation_values(&wires.map(|w| w.signal()));
+            results.push(
+                wires
+                    .iter()
+                    .zip(v)
+                    .map(|(w, v)| match (w.is_negated(), v) {
+                        (true, TernaryValue::True) => TernaryValue::False,
+                        (true, TernaryValue::False) => TernaryValue::True,
+                        (_, v) => v,
+                    })
+                    .collect::<Vec<TernaryValue>>(),
+            );
+        }
+        assert_eq!(results[0], results[1]);
+    }
+}
+
+#[test]
+fn test_circuit_to_aig_keeps_property_order() {
+    // inputs 1, 2, 3 and 4 = 1 & 2, the bad wires are out of order and repeated
+    let w = |n: u32, negated: bool| Signal::new(n).wire(negated);
+    let bad = vec![w(4, false), w(1, true), w(4, false), w(3, false)];
+    let mut aig = AndInverterGraph::new(
+        Signal::new(4),
+        3,
+        &[],
+        vec![w(3, false), w(2, false)],
+        bad.to_owned(),
+        vec![],
+        &[(w(1, false), w(2, false))],
+        String::new(),
+    )
+    .unwrap();
+    for (i, symbol) in ["p", "q", "r", "s"].iter().enumerate() {
+        aig.add_symbol('b', i as u32, symbol).unwrap();
+    }
+    let circuit = Circuit::from_aig(&aig);
+    assert_eq!(circuit.get_bad_wires().len(), 3);
+
+    let exported = circuit.to_aig_with_symbols(&aig, &SignalTracker::new());
+    assert_eq!(exported.get_bad_wires(), bad);
+    assert_eq!(exported.get_output_wires(), vec![w(3, false), w(2, false)]);
+    let text = exported.get_aag_string();
+    assert!(text.contains("b0 p\nb1 q\nb2 r\nb3 s\n"));
+}
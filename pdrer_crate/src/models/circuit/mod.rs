@@ -75,6 +75,7 @@ pub trait CircuitSimplifier {
 
 pub mod check;
 pub mod construction;
+pub mod conversion;
 pub mod cut_enumeration;
 pub mod dot;
 pub mod fixes;
@@ -82,12 +83,14 @@ pub mod get_nodes;
 pub mod get_wires;
 pub mod graph;
 pub mod node_types;
+pub mod retime;
 pub mod simplifiers;
 pub mod technology_map_area_flow;
 pub mod technology_map_min_popularity;
 pub mod technology_map_minimizing_depth;
 pub mod technology_map_using_levels;
 pub mod un_constraint;
+pub mod verilog;
 
 // ************************************************************************************************
 // re-exports of structs in these modules to simplify paths for other imports
@@ -0,0 +1,834 @@
+//! Minimum-register forward retiming of latches, moves latches across AND and generic gates in
+//! order to reduce the number of state variables.
+
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use super::{
+    node_types::{CircuitLatch, CircuitNodeType},
+    Circuit,
+};
+use crate::{
+    formulas::{Clause, Cube, Literal, Variable, CNF},
+    models::{
+        circuit_builder::CircuitBuilder, definition::DefinitionFunction, Counterexample,
+        Definition, Proof, Signal, SortedVecOfLiterals, TernaryValue, TruthTable,
+        UniqueSortedHashMap, UniqueSortedVec, Wire,
+    },
+};
+use fxhash::FxHashMap;
+use std::collections::VecDeque;
+
+// ************************************************************************************************
+// struct
+// ************************************************************************************************
+
+/// A flow network with integer capacities, edge `i ^ 1` is the reverse of edge `i`.
+struct FlowNetwork {
+    edges_of_node: Vec<Vec<usize>>,
+    /// (target, remaining capacity)
+    edges: Vec<(usize, usize)>,
+}
+
+/// A gate that was turned into a latch during retiming.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetimedGate {
+    /// The signal of the gate in the original circuit.
+    pub gate: Signal,
+    /// The function of the gate in the original circuit (And or GenericGate).
+    pub function: CircuitNodeType,
+    /// The latch that replaced the gate in the retimed circuit, None if the latch was later
+    /// absorbed by some other retimed gate.
+    pub latch: Option<Signal>,
+}
+
+/// Information needed in order to translate results on a retimed circuit back to the original
+/// circuit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircuitRetiming {
+    original_highest_signal: Signal,
+    /// inputs and latches that survived retiming, retimed signal -> original signal
+    retimed_to_original: UniqueSortedHashMap<Signal, Signal>,
+    /// gates that became latches, in topological order
+    retimed_gates: Vec<RetimedGate>,
+    /// latches that were removed from the circuit, along with their initial value
+    absorbed_latches: Vec<(Signal, TernaryValue)>,
+}
+
+// ************************************************************************************************
+// impl FlowNetwork
+// ************************************************************************************************
+
+impl FlowNetwork {
+    const INFINITY: usize = usize::MAX / 2;
+
+    fn new(number_of_nodes: usize) -> Self {
+        Self {
+            edges_of_node: vec![Vec::new(); number_of_nodes],
+            edges: Vec::new(),
+        }
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, capacity: usize) {
+        self.edges_of_node[from].push(self.edges.len());
+        self.edges.push((to, capacity));
+        self.edges_of_node[to].push(self.edges.len());
+        self.edges.push((from, 0));
+    }
+
+    /// Returns the parent edge of every node that is reachable from the source using edges
+    /// with remaining capacity, the source itself is marked with `Some(usize::MAX)`.
+    fn get_residual_tree(&self, source: usize) -> Vec<Option<usize>> {
+        let mut parent = vec![None; self.edges_of_node.len()];
+        parent[source] = Some(usize::MAX);
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            for e in self.edges_of_node[node].iter() {
+                let (to, capacity) = self.edges[*e];
+                if capacity > 0 && parent[to].is_none() {
+                    parent[to] = Some(*e);
+                    queue.push_back(to);
+                }
+            }
+        }
+        parent
+    }
+
+    /// Saturates the network using shortest augmenting paths, and returns the nodes on the
+    /// source side of the minimum cut that is closest to the source.
+    fn get_minimum_cut(&mut self, source: usize, sink: usize) -> Vec<bool> {
+        loop {
+            let parent = self.get_residual_tree(source);
+            if parent[sink].is_none() {
+                return parent.iter().map(|p| p.is_some()).collect();
+            }
+            let mut path = Vec::new();
+            let mut node = sink;
+            while node != source {
+                let e = parent[node].unwrap();
+                path.push(e);
+                node = self.edges[e ^ 1].0;
+            }
+            let flow = path.iter().map(|e| self.edges[*e].1).min().unwrap();
+            debug_assert!(flow < Self::INFINITY);
+            for e in path {
+                self.edges[e].1 -= flow;
+                self.edges[e ^ 1].1 += flow;
+            }
+        }
+    }
+}
+
+// ************************************************************************************************
+// impl Circuit
+// ************************************************************************************************
+
+impl Circuit {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn get_latch_details(&self, latch: &Signal) -> &CircuitLatch {
+        match &self.nodes.get(latch).unwrap().node_type {
+            CircuitNodeType::Latch(l) => l,
+            _ => unreachable!(),
+        }
+    }
+
+    fn get_ternary_value_of_wire(
+        wire: &Wire,
+        values: &FxHashMap<Signal, TernaryValue>,
+    ) -> TernaryValue {
+        match (values.get(&wire.signal()).unwrap(), wire.is_negated()) {
+            (TernaryValue::X, _) => TernaryValue::X,
+            (TernaryValue::True, false) | (TernaryValue::False, true) => TernaryValue::True,
+            (TernaryValue::False, false) | (TernaryValue::True, true) => TernaryValue::False,
+        }
+    }
+
+    /// Calculates the initial value of a retimed gate given the initial values of its fan-in.
+    fn get_initial_value_of_retimed_gate(
+        node_type: &CircuitNodeType,
+        initial_values: &FxHashMap<Signal, TernaryValue>,
+    ) -> TernaryValue {
+        match node_type {
+            CircuitNodeType::And(a) => {
+                let mut result = TernaryValue::True;
+                for w in a.inputs.iter() {
+                    match Self::get_ternary_value_of_wire(w, initial_values) {
+                        TernaryValue::False => return TernaryValue::False,
+                        TernaryValue::X => result = TernaryValue::X,
+                        TernaryValue::True => {}
+                    }
+                }
+                result
+            }
+            CircuitNodeType::GenericGate(g) => g.truth_table.get_ternary_result(
+                g.truth_table
+                    .get_signals()
+                    .iter()
+                    .map(|s| *initial_values.get(s).unwrap()),
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    fn get_fan_in_of_gate(node_type: &CircuitNodeType) -> UniqueSortedVec<Signal> {
+        match node_type {
+            CircuitNodeType::And(a) => {
+                UniqueSortedVec::from_sequence(a.inputs.iter().map(|w| w.signal()).collect())
+            }
+            CircuitNodeType::GenericGate(g) => g.truth_table.get_signals().to_owned(),
+            _ => unreachable!(),
+        }
+    }
+
+    fn get_truth_table_of_wire(wire: Wire) -> TruthTable {
+        if wire.is_constant() {
+            if wire.is_constant_one() {
+                TruthTable::new_constant_1()
+            } else {
+                TruthTable::new_constant_0()
+            }
+        } else {
+            let mut tt = TruthTable::new_identity_truth_table(&wire.signal());
+            if wire.is_negated() {
+                tt.negate();
+            }
+            tt
+        }
+    }
+
+    /// Given a truth table over some signals, and a wire for each of these signals, calculate
+    /// the truth table of the function over the given wires.
+    fn compose_truth_table(truth_table: &TruthTable, wires: &[Wire]) -> TruthTable {
+        debug_assert_eq!(truth_table.len(), wires.len());
+        let mut result = TruthTable::new_constant_0();
+        for row in 0..truth_table.calculate_number_of_rows() {
+            if !truth_table.get_value(row) {
+                continue;
+            }
+            let mut minterm = TruthTable::new_constant_1();
+            for (j, w) in wires.iter().enumerate() {
+                let w = if (row >> j) & 1 == 1 { *w } else { !*w };
+                minterm = TruthTable::and(minterm, Self::get_truth_table_of_wire(w));
+            }
+            // result = result \/ minterm
+            result.negate();
+            minterm.negate();
+            result = TruthTable::and(result, minterm);
+            result.negate();
+        }
+        result.simplify();
+        result
+    }
+
+    /// Adds the gate that calculates the next state of a retimed gate, returns the wire that
+    /// should be the input of the new latch.
+    fn add_next_state_gate_of_retimed_gate(
+        builder: &mut CircuitBuilder,
+        node_type: &CircuitNodeType,
+        next_state: &FxHashMap<Signal, Wire>,
+    ) -> Wire {
+        let next_wire = |w: &Wire| {
+            let n = *next_state.get(&w.signal()).unwrap();
+            if w.is_negated() {
+                !n
+            } else {
+                n
+            }
+        };
+
+        match node_type {
+            CircuitNodeType::And(a) => {
+                let mut inputs: Vec<Wire> = a.inputs.iter().map(next_wire).collect();
+                inputs.retain(|w| *w != Wire::CONSTANT_ONE);
+                let inputs = UniqueSortedVec::from_sequence(inputs);
+                let is_contradiction = inputs.contains(&Wire::CONSTANT_ZERO)
+                    || inputs.iter().any(|w| inputs.contains(&!*w));
+                if is_contradiction {
+                    Wire::CONSTANT_ZERO
+                } else if inputs.is_empty() {
+                    Wire::CONSTANT_ONE
+                } else if inputs.len() == 1 {
+                    inputs.peek()[0]
+                } else {
+                    let s = builder.get_unused_signal();
+                    builder.add_and_gate(s, inputs).unwrap();
+                    s.wire(false)
+                }
+            }
+            CircuitNodeType::GenericGate(g) => {
+                let wires: Vec<Wire> = g
+                    .truth_table
+                    .get_signals()
+                    .iter()
+                    .map(|s| next_wire(&s.wire(false)))
+                    .collect();
+                let tt = Self::compose_truth_table(&g.truth_table, &wires);
+                if tt.is_constant_0() {
+                    Wire::CONSTANT_ZERO
+                } else if tt.is_constant_1() {
+                    Wire::CONSTANT_ONE
+                } else if tt.len() == 1 {
+                    let s = tt.get_signals().peek()[0];
+                    s.wire(tt != TruthTable::new_identity_truth_table(&s))
+                } else {
+                    let s = builder.get_unused_signal();
+                    builder.add_generic_gate(s, tt).unwrap();
+                    s.wire(false)
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Returns true if the signal is a latch or a retimed gate that is not needed after
+    /// retiming, meaning that all of its users are retimed gates.
+    fn is_absorbed_by_retiming(
+        signal: &Signal,
+        users: &UniqueSortedHashMap<Signal, UniqueSortedVec<Signal>>,
+        references: &UniqueSortedHashMap<Signal, usize>,
+        retimed: &UniqueSortedVec<Signal>,
+    ) -> bool {
+        let users = users
+            .get(signal)
+            .map(|u| u.peek().as_slice())
+            .unwrap_or(&[]);
+        *references.get(signal).unwrap() == users.len() && users.iter().all(|u| retimed.contains(u))
+    }
+
+    /// Chooses which of the candidate gates to retime such that the number of latches after
+    /// retiming is minimal. A candidate can only be retimed together with the candidates in its
+    /// fan-in, and a latch or retimed gate remains a latch if it has a user that is not retimed.
+    /// This is solved as a minimum cut, as in "Fast Minimum-Register Retiming via Binary
+    /// Maximum-Flow" by Hurst, Mishchenko and Brayton. Every node `x` is split into `x` and
+    /// `x'`, the edge between them costs 1 and `x'` must be retimed with all the users of `x`.
+    fn get_gates_to_retime(
+        &self,
+        candidates: &[Signal],
+        users: &UniqueSortedHashMap<Signal, UniqueSortedVec<Signal>>,
+        references: &UniqueSortedHashMap<Signal, usize>,
+    ) -> Vec<Signal> {
+        let nodes: Vec<Signal> = self
+            .latches
+            .iter()
+            .chain(candidates.iter())
+            .copied()
+            .collect();
+        let index: FxHashMap<Signal, usize> =
+            nodes.iter().enumerate().map(|(i, s)| (*s, i)).collect();
+        let is_candidate = |s: &Signal| index.get(s).is_some_and(|i| *i >= self.latches.len());
+
+        // node 0 is the source, 1 is the sink, x is 2 + 2i and x' is 3 + 2i
+        let (source, sink) = (0, 1);
+        let mut network = FlowNetwork::new(2 + 2 * nodes.len());
+        for (i, s) in nodes.iter().enumerate() {
+            let (x, x_prime) = (2 + 2 * i, 3 + 2 * i);
+            if i < self.latches.len() {
+                network.add_edge(source, x, FlowNetwork::INFINITY);
+            } else {
+                let fan_in = Self::get_fan_in_of_gate(&self.nodes.get(s).unwrap().node_type);
+                for f in fan_in.iter() {
+                    network.add_edge(x, 2 + 2 * index[f], FlowNetwork::INFINITY);
+                }
+            }
+            network.add_edge(x, x_prime, 1);
+
+            let users_of_s = users.get(s).map(|u| u.peek().as_slice()).unwrap_or(&[]);
+            for u in users_of_s.iter().filter(|u| is_candidate(u)) {
+                network.add_edge(x_prime, 2 + 2 * index[u], FlowNetwork::INFINITY);
+            }
+            let is_used_outside = *references.get(s).unwrap() > users_of_s.len()
+                || users_of_s.iter().any(|u| !is_candidate(u));
+            if is_used_outside {
+                network.add_edge(x_prime, sink, FlowNetwork::INFINITY);
+            }
+        }
+
+        let is_source_side = network.get_minimum_cut(source, sink);
+        candidates
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| is_source_side[2 + 2 * (self.latches.len() + i)])
+            .map(|(_, g)| *g)
+            .collect()
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Performs forward retiming with the minimal number of latches.
+    /// Gates are visited by level (see `get_level_per_signal`), a gate can be retimed if:
+    /// 1. All of its inputs are latches (or gates that can be retimed).
+    /// 2. The initial value of the gate is known, this guarantees that the retimed circuit has
+    ///    exactly the same behavior as the original circuit in every clock cycle.
+    ///
+    /// Out of these gates, a set that minimizes the number of latches is retimed using a
+    /// minimum cut. The result is optimal among forward retimings, latches are never moved
+    /// backwards since that requires computing the initial state of the retimed latches.
+    ///
+    /// The returned `CircuitRetiming` can be used to translate counterexamples and proofs that
+    /// were found on the retimed circuit back to this circuit.
+    pub fn retime(&self) -> (Circuit, CircuitRetiming) {
+        let levels = self.get_level_per_signal();
+        let users = self.get_users_per_signal();
+        let references = self.get_number_of_references();
+
+        let mut initial_values: FxHashMap<Signal, TernaryValue> = FxHashMap::default();
+        for l in self.latches.iter() {
+            initial_values.insert(*l, self.get_latch_details(l).initial);
+        }
+
+        let mut gates: Vec<Signal> = self.gates.iter().copied().collect();
+        gates.sort_by_key(|s| (*levels.get(s).unwrap(), *s));
+
+        let mut candidates: Vec<Signal> = Vec::new();
+        for g in gates {
+            let node_type = &self.nodes.get(&g).unwrap().node_type;
+            let fan_in = Self::get_fan_in_of_gate(node_type);
+            if let CircuitNodeType::And(a) = node_type {
+                if fan_in.len() != a.inputs.len() {
+                    // both polarities of some signal
+                    continue;
+                }
+            }
+            if fan_in.iter().any(|s| !initial_values.contains_key(s)) {
+                continue;
+            }
+            let initial = Self::get_initial_value_of_retimed_gate(node_type, &initial_values);
+            if initial == TernaryValue::X {
+                continue;
+            }
+            initial_values.insert(g, initial);
+            candidates.push(g);
+        }
+
+        let retimed = self.get_gates_to_retime(&candidates, &users, &references);
+        let retimed_set = UniqueSortedVec::from_sequence(retimed.to_owned());
+        let absorbed = UniqueSortedVec::from_sequence(
+            self.latches
+                .iter()
+                .chain(retimed.iter())
+                .copied()
+                .filter(|s| Self::is_absorbed_by_retiming(s, &users, &references, &retimed_set))
+                .collect(),
+        );
+
+        // build the retimed circuit
+        let mut builder = CircuitBuilder::from_circuit(self);
+        builder.add_ground();
+        let mut next_state: FxHashMap<Signal, Wire> = FxHashMap::default();
+        for l in self.latches.iter() {
+            next_state.insert(*l, self.get_latch_details(l).input);
+        }
+        for g in retimed.iter() {
+            let node_type = &self.nodes.get(g).unwrap().node_type;
+            let next =
+                Self::add_next_state_gate_of_retimed_gate(&mut builder, node_type, &next_state);
+            next_state.insert(*g, next);
+            builder.add_latch(*g, next, *initial_values.get(g).unwrap());
+        }
+        for s in absorbed.iter() {
+            builder.remove_signal(*s);
+        }
+        let (circuit, mapping) = builder.build().unwrap();
+
+        // save information needed to translate results back
+        let mut retimed_to_original = UniqueSortedHashMap::new(circuit.greatest_signal);
+        for s in self.inputs.iter().chain(self.latches.iter()) {
+            if let Some(r) = mapping.get(s) {
+                retimed_to_original.insert(*r, *s);
+            }
+        }
+        let retimed_gates = retimed
+            .iter()
+            .map(|g| RetimedGate {
+                gate: *g,
+                function: self.nodes.get(g).unwrap().node_type.to_owned(),
+                latch: mapping.get(g).copied(),
+            })
+            .collect();
+        let absorbed_latches = absorbed
+            .iter()
+            .filter(|s| self.latches.contains(s))
+            .map(|s| (*s, self.get_latch_details(s).initial))
+            .collect();
+
+        let retiming = CircuitRetiming {
+            original_highest_signal: self.greatest_signal,
+            retimed_to_original,
+            retimed_gates,
+            absorbed_latches,
+        };
+
+        (circuit, retiming)
+    }
+}
+
+// ************************************************************************************************
+// impl CircuitRetiming
+// ************************************************************************************************
+
+impl CircuitRetiming {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn signal_to_variable(signal: Signal) -> Variable {
+        Variable::new(signal.number())
+    }
+
+    /// Get the variable of the i-th definition, avoids collisions with the tagged variables of
+    /// the original circuit and of other definitions.
+    fn get_definition_variable(&self, index: usize) -> Variable {
+        let n = std::cmp::max(self.original_highest_signal.number() as usize, 1);
+        let base = 2 * n + 1;
+        let v = base + (2 * n * (index / n)) + (index % n);
+        Variable::new(v as u32)
+    }
+
+    fn define_and(&self, inputs: Vec<Literal>, definitions: &mut Vec<Definition>) -> Literal {
+        debug_assert!(!inputs.is_empty());
+        if inputs.len() == 1 {
+            return inputs[0];
+        }
+        let variable = self.get_definition_variable(definitions.len());
+        definitions.push(Definition {
+            variable,
+            function: DefinitionFunction::And,
+            inputs: SortedVecOfLiterals::from_sequence(inputs),
+        });
+        variable.literal(false)
+    }
+
+    /// Defines a literal that is equal to the truth table using a sum of products over either
+    /// the on-set or the off-set, depending on which is smaller.
+    fn define_truth_table(
+        &self,
+        truth_table: &TruthTable,
+        inputs: &[Literal],
+        definitions: &mut Vec<Definition>,
+    ) -> Literal {
+        let rows = truth_table.calculate_number_of_rows();
+        let on_set: Vec<usize> = (0..rows).filter(|r| truth_table.get_value(*r)).collect();
+        let off_set: Vec<usize> = (0..rows).filter(|r| !truth_table.get_value(*r)).collect();
+        let (set, is_on_set) = if on_set.len() <= off_set.len() {
+            (on_set, true)
+        } else {
+            (off_set, false)
+        };
+        debug_assert!(!set.is_empty());
+
+        let mut minterms = Vec::with_capacity(set.len());
+        for row in set {
+            let literals = inputs
+                .iter()
+                .enumerate()
+                .map(|(j, l)| if (row >> j) & 1 == 1 { *l } else { !*l })
+                .collect();
+            minterms.push(self.define_and(literals, definitions));
+        }
+        // !(/\ !m_i) = \/ m_i
+        let nor = self.define_and(minterms.into_iter().map(|m| !m).collect(), definitions);
+        if is_on_set {
+            !nor
+        } else {
+            nor
+        }
+    }
+
+    fn translate_literal(map: &FxHashMap<Variable, Literal>, literal: &Literal) -> Literal {
+        let l = *map.get(&literal.variable()).unwrap();
+        if literal.is_negated() {
+            !l
+        } else {
+            l
+        }
+    }
+
+    fn translate_cube(&self, cube: &Cube) -> Cube {
+        Cube::from_sequence(
+            cube.iter()
+                .filter_map(|l| {
+                    self.retimed_to_original
+                        .get(&Signal::new(l.variable().number()))
+                        .map(|s| Self::signal_to_variable(*s).literal(l.is_negated()))
+                })
+                .collect(),
+        )
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Returns the number of gates that were converted into latches.
+    pub fn get_number_of_retimed_gates(&self) -> usize {
+        self.retimed_gates.len()
+    }
+
+    /// Returns the gates that were converted into latches.
+    pub fn get_retimed_gates(&self) -> &[RetimedGate] {
+        &self.retimed_gates
+    }
+
+    /// Returns the latches of the original circuit that are no longer in the retimed circuit.
+    pub fn get_absorbed_latches(&self) -> &[(Signal, TernaryValue)] {
+        &self.absorbed_latches
+    }
+
+    /// Translates a counterexample on the retimed circuit to a counterexample on the original
+    /// circuit. Retiming does not change the length of counterexamples, since the initial value
+    /// of every retimed gate is known.
+    pub fn translate_counterexample(&self, counterexample: &Counterexample) -> Counterexample {
+        let mut initial_cube = self.translate_cube(&counterexample.initial_cube);
+        for (s, initial) in self.absorbed_latches.iter() {
+            match initial {
+                TernaryValue::True => {
+                    initial_cube.insert(Self::signal_to_variable(*s).literal(false))
+                }
+                TernaryValue::False => {
+                    initial_cube.insert(Self::signal_to_variable(*s).literal(true))
+                }
+                TernaryValue::X => false,
+            };
+        }
+        Counterexample {
+            initial_cube,
+            inputs: counterexample
+                .inputs
+                .iter()
+                .map(|c| self.translate_cube(c))
+                .collect(),
+        }
+    }
+
+    /// Translates a proof on the retimed circuit to a proof on the original circuit.
+    /// Each retimed latch is replaced by a definition that calculates the function of the
+    /// original gate over the original latches.
+    pub fn translate_proof(&self, proof: &Proof) -> Proof {
+        let mut map: FxHashMap<Variable, Literal> = FxHashMap::default();
+        for (r, o) in self.retimed_to_original.iter_pairs() {
+            map.insert(
+                Self::signal_to_variable(r),
+                Self::signal_to_variable(*o).literal(false),
+            );
+        }
+
+        let mut definitions = Vec::new();
+        let mut gate_to_literal: FxHashMap<Signal, Literal> = FxHashMap::default();
+        for r in self.retimed_gates.iter() {
+            let literal_of_wire = |w: &Wire| {
+                let l = gate_to_literal
+                    .get(&w.signal())
+                    .copied()
+                    .unwrap_or(Self::signal_to_variable(w.signal()).literal(false));
+                if w.is_negated() {
+                    !l
+                } else {
+                    l
+                }
+            };
+            let l = match &r.function {
+                CircuitNodeType::And(a) => self.define_and(
+                    a.inputs.iter().map(literal_of_wire).collect(),
+                    &mut definitions,
+                ),
+                CircuitNodeType::GenericGate(g) => {
+                    let inputs: Vec<Literal> = g
+                        .truth_table
+                        .get_signals()
+                        .iter()
+                        .map(|s| literal_of_wire(&s.wire(false)))
+                        .collect();
+                    self.define_truth_table(&g.truth_table, &inputs, &mut definitions)
+                }
+                _ => unreachable!(),
+            };
+            gate_to_literal.insert(r.gate, l);
+            if let Some(latch) = r.latch {
+                map.insert(Self::signal_to_variable(latch), l);
+            }
+        }
+
+        let mut proof_definitions = proof.definitions.to_owned();
+        proof_definitions.sort_by_key(|d| d.variable);
+        for d in proof_definitions {
+            let variable = self.get_definition_variable(definitions.len());
+            let inputs = d
+                .inputs
+                .iter()
+                .map(|l| Self::translate_literal(&map, l))
+                .collect();
+            definitions.push(Definition {
+                variable,
+                function: d.function,
+                inputs: SortedVecOfLiterals::from_sequence(inputs),
+            });
+            map.insert(d.variable, variable.literal(false));
+        }
+
+        let invariant = CNF::from_sequence(
+            proof
+                .invariant
+                .iter()
+                .map(|c| {
+                    Clause::from_sequence(
+                        c.iter().map(|l| Self::translate_literal(&map, l)).collect(),
+                    )
+                })
+                .collect(),
+        );
+
+        Proof {
+            all_initial_states_violate_constraints: proof.all_initial_states_violate_constraints,
+            invariant,
+            definitions,
+        }
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[test]
+fn test_retime() {
+    use crate::models::{AndInverterGraph, FiniteStateTransitionSystem};
+    use crate::solvers::sat::incremental::CaDiCalSolver;
+
+    // 4 latches that are initialized to 0 and all take the value of the same input, the property
+    // is that the and of the first two latches is equal to the and of the last two.
+    let aig = AndInverterGraph::new(
+        Signal::new(10),
+        1,
+        &[
+            (Signal::new(1).wire(false), TernaryValue::False),
+            (Signal::new(1).wire(false), TernaryValue::False),
+            (Signal::new(1).wire(false), TernaryValue::False),
+            (Signal::new(1).wire(false), TernaryValue::False),
+        ],
+        vec![],
+        vec![Signal::new(10).wire(true)],
+        vec![],
+        &[
+            (Signal::new(2).wire(false), Signal::new(3).wire(false)),
+            (Signal::new(4).wire(false), Signal::new(5).wire(false)),
+            (Signal::new(6).wire(false), Signal::new(7).wire(true)),
+            (Signal::new(6).wire(true), Signal::new(7).wire(false)),
+            (Signal::new(8).wire(true), Signal::new(9).wire(true)),
+        ],
+        String::new(),
+    )
+    .unwrap();
+    let circuit = Circuit::from_aig(&aig);
+    let (retimed, retiming) = circuit.retime();
+    assert_eq!(retimed.get_latch_signals().len(), 1);
+    assert_eq!(retiming.get_number_of_retimed_gates(), 5);
+    assert_eq!(retiming.get_absorbed_latches().len(), 4);
+
+    // the only latch is the negation of the bad wire and it is always 1
+    let fin_state = FiniteStateTransitionSystem::new(&retimed, false).unwrap();
+    let a = Variable::new(retimed.get_latch_signals().peek()[0].number());
+    let proof = Proof {
+        all_initial_states_violate_constraints: false,
+        invariant: CNF::from_sequence(vec![Clause::from_sequence(vec![a.literal(false)])]),
+        definitions: vec![],
+    };
+    assert!(fin_state.check_proof::<CaDiCalSolver>(&proof).is_ok());
+
+    let fin_state = FiniteStateTransitionSystem::new(&circuit, false).unwrap();
+    let proof = retiming.translate_proof(&proof);
+    assert_eq!(proof.definitions.len(), 5);
+    assert!(fin_state.check_proof::<CaDiCalSolver>(&proof).is_ok());
+}
+
+#[test]
+fn test_retime_is_minimal() {
+    use crate::models::AndInverterGraph;
+
+    // 3 latches a, b, c that take the value of 3 inputs, 7 = a & b, 8 = b & c and 9 = 7 & 8 is
+    // bad. No gate has 2 fan-in latches that are used only by it, yet retiming all the gates
+    // leaves a single latch. When b is also an output the optimum is 2 latches.
+    for (outputs, optimum) in [(vec![], 1), (vec![Signal::new(5).wire(false)], 2)] {
+        let aig = AndInverterGraph::new(
+            Signal::new(9),
+            3,
+            &[
+                (Signal::new(1).wire(false), TernaryValue::False),
+                (Signal::new(2).wire(false), TernaryValue::False),
+                (Signal::new(3).wire(false), TernaryValue::False),
+            ],
+            outputs,
+            vec![Signal::new(9).wire(false)],
+            vec![],
+            &[
+                (Signal::new(4).wire(false), Signal::new(5).wire(false)),
+                (Signal::new(5).wire(false), Signal::new(6).wire(false)),
+                (Signal::new(7).wire(false), Signal::new(8).wire(false)),
+            ],
+            String::new(),
+        )
+        .unwrap();
+        let circuit = Circuit::from_aig(&aig);
+        let (retimed, retiming) = circuit.retime();
+        assert_eq!(retimed.get_latch_signals().len(), optimum);
+        assert_eq!(retiming.get_number_of_retimed_gates(), 3);
+    }
+}
+
+#[test]
+fn test_retime_generic_gate() {
+    use crate::models::{AndInverterGraph, FiniteStateTransitionSystem};
+
+    // 2 latches that take the value of 2 inputs, the property is that their xor is never 1.
+    let aig = AndInverterGraph::new(
+        Signal::new(7),
+        2,
+        &[
+            (Signal::new(1).wire(false), TernaryValue::False),
+            (Signal::new(2).wire(false), TernaryValue::False),
+        ],
+        vec![],
+        vec![Signal::new(7).wire(true)],
+        vec![],
+        &[
+            (Signal::new(3).wire(false), Signal::new(4).wire(true)),
+            (Signal::new(3).wire(true), Signal::new(4).wire(false)),
+            (Signal::new(5).wire(true), Signal::new(6).wire(true)),
+        ],
+        String::new(),
+    )
+    .unwrap();
+    let mut circuit = Circuit::from_aig(&aig);
+    circuit.detect_generic_patterns();
+    circuit.remove_unused_signals();
+    let (retimed, retiming) = circuit.retime();
+    assert_eq!(retimed.get_latch_signals().len(), 1);
+    assert_eq!(retiming.get_absorbed_latches().len(), 2);
+
+    // the retimed latch is the xor of the previous inputs, so the property fails after 1 cycle
+    let x = Variable::new(retimed.get_input_signals().peek()[0].number());
+    let y = Variable::new(retimed.get_input_signals().peek()[1].number());
+    let counterexample = Counterexample {
+        initial_cube: Cube::from_sequence(vec![]),
+        inputs: vec![
+            Cube::from_sequence(vec![x.literal(false), y.literal(true)]),
+            Cube::from_sequence(vec![x.literal(false), y.literal(false)]),
+        ],
+    };
+    let mut fin_state = FiniteStateTransitionSystem::new(&retimed, false).unwrap();
+    assert!(fin_state
+        .check_counter_example(counterexample.to_owned(), false)
+        .is_ok());
+
+    let mut fin_state = FiniteStateTransitionSystem::new(&circuit, false).unwrap();
+    let counterexample = retiming.translate_counterexample(&counterexample);
+    assert_eq!(counterexample.initial_cube.len(), 2);
+    assert!(fin_state
+        .check_counter_example(counterexample, false)
+        .is_ok());
+}
@@ -8,6 +8,7 @@ use crate::models::{
         CircuitSimplifier,
     },
     signal_tracker::SignalTransformation,
+    truth_table::npn::{NpnCache, NpnClass},
     Circuit, Signal, TruthTable, UniqueSortedVec,
 };
 
@@ -17,6 +18,9 @@ use crate::models::{
 
 pub struct CircuitGenericPatternDetector {
     verbose: bool,
+    // when provided, only patterns in these NPN classes are turned into generic gates
+    classes: Option<Vec<NpnClass>>,
+    cache: NpnCache,
 }
 
 // ************************************************************************************************
@@ -29,6 +33,7 @@ impl CircuitGenericPatternDetector {
     // ********************************************************************************************
 
     fn detect_xor_pattern_if_exists(
+        &mut self,
         circuit: &Circuit,
         signal: &Signal,
         aggregator: &mut Vec<(Signal, TruthTable)>,
@@ -127,6 +132,13 @@ impl CircuitGenericPatternDetector {
         //     false
         // }
 
+        if let Some(classes) = &self.classes {
+            let (class, _) = tt.get_npn_class_with_cache(&mut self.cache);
+            if !classes.contains(&class) {
+                return false;
+            }
+        }
+
         aggregator.push((*signal, tt));
         true
     }
@@ -136,7 +148,21 @@ impl CircuitGenericPatternDetector {
     // ********************************************************************************************
 
     pub fn new(verbose: bool) -> Self {
-        Self { verbose }
+        Self {
+            verbose,
+            classes: None,
+            cache: NpnCache::default(),
+        }
+    }
+
+    /// Creates a detector that only turns patterns into generic gates if their function is in one
+    /// of the provided NPN classes, regardless of the polarity and order of the inputs.
+    pub fn new_with_npn_classes(verbose: bool, classes: Vec<NpnClass>) -> Self {
+        Self {
+            verbose,
+            classes: Some(classes),
+            cache: NpnCache::default(),
+        }
     }
 
     pub fn detect_4_input_cuts(&mut self, circuit: &mut Circuit) -> SignalTransformation {
@@ -164,7 +190,7 @@ impl CircuitGenericPatternDetector {
                 );
             }
 
-            Self::detect_xor_pattern_if_exists(circuit, &signal, &mut aggregator);
+            self.detect_xor_pattern_if_exists(circuit, &signal, &mut aggregator);
         }
 
         for (signal, tt) in aggregator {
@@ -5,7 +5,10 @@
 pub mod condense;
 pub mod detect_generic_patterns;
 pub mod merge_and_gates;
+pub mod pipeline;
+pub mod property_cone;
 pub mod remove_unused;
+pub mod rewrite;
 pub mod simplify;
 pub mod structural_hash;
 pub mod technology_map;
@@ -16,6 +19,9 @@ pub mod technology_map;
 
 pub use condense::CircuitCondenser;
 pub use merge_and_gates::CircuitAndGateMerger;
+pub use pipeline::{CircuitSimplificationPass, CircuitSimplificationPipeline};
+pub use property_cone::CircuitPropertyConeReducer;
 pub use remove_unused::CircuitUnusedSignalRemover;
+pub use rewrite::CircuitRewriter;
 pub use structural_hash::CircuitStructuralHashing;
 pub use technology_map::CircuitTechnologyMapper;
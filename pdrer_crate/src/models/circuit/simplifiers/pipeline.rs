@@ -0,0 +1,504 @@
+//! Declarative simplification pipeline, a sequence of named simplification passes that can be
+//! parsed from a string such as `"remove_unused;strash;tech_map:k=4,l=10;condense"`. Pattern
+//! detection can be restricted to NPN classes by name, `"generic_patterns:classes=xor2+ite"`.
+
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::{fmt, ops::RangeInclusive, time::Duration, time::Instant};
+
+use crate::models::{
+    circuit::CircuitSimplifier,
+    truth_table::{npn::NpnClass, TRUTH_TABLE_MAX_INPUTS},
+    Circuit, PrettyTable, SignalTracker, Utils,
+};
+
+use super::{
+    detect_generic_patterns::CircuitGenericPatternDetector, CircuitAndGateMerger, CircuitCondenser,
+    CircuitRewriter, CircuitStructuralHashing, CircuitTechnologyMapper, CircuitUnusedSignalRemover,
+};
+
+// ************************************************************************************************
+// errors
+// ************************************************************************************************
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitSimplificationPipelineError {
+    EmptyPassName,
+    UnknownPass(String),
+    UnknownParameter(String, String),
+    ParameterWithoutValue(String, String),
+    InvalidParameterValue(String, String, String),
+    DuplicateParameter(String, String),
+}
+
+// ************************************************************************************************
+// pass
+// ************************************************************************************************
+
+/// A single pass in the pipeline, the name and parameters are kept so that the pipeline can be
+/// printed back in the same syntax it was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitSimplificationPass {
+    RemoveUnused,
+    StructuralHash,
+    DetectGenericPatterns { classes: Option<Vec<NpnClass>> },
+    MergeAndGates,
+    Condense,
+    TechnologyMap { k: usize, l: usize },
+    Rewrite { l: usize },
+}
+
+/// Statistics of a single run of a pass.
+#[derive(Debug, Clone)]
+pub struct CircuitSimplificationPassStatistics {
+    pub iteration: usize,
+    pub pass: CircuitSimplificationPass,
+    pub title: String,
+    pub time: Duration,
+    pub nodes_before: usize,
+    pub nodes_after: usize,
+}
+
+// ************************************************************************************************
+// pipeline
+// ************************************************************************************************
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitSimplificationPipeline {
+    passes: Vec<CircuitSimplificationPass>,
+    /// Passes are not started once the resident memory of the process reaches this limit in MB.
+    memory_limit_mb: usize,
+}
+
+// ************************************************************************************************
+// impl pass
+// ************************************************************************************************
+
+impl CircuitSimplificationPass {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    /// Parse a parameter that must be a number in the given range.
+    fn parse_usize_parameter(
+        pass: &str,
+        name: &str,
+        value: &str,
+        range: RangeInclusive<usize>,
+    ) -> Result<usize, CircuitSimplificationPipelineError> {
+        match value.parse::<usize>() {
+            Ok(x) if range.contains(&x) => Ok(x),
+            _ => Err(CircuitSimplificationPipelineError::InvalidParameterValue(
+                pass.to_string(),
+                name.to_string(),
+                value.to_string(),
+            )),
+        }
+    }
+
+    fn parse_npn_classes_parameter(
+        pass: &str,
+        name: &str,
+        value: &str,
+    ) -> Result<Vec<NpnClass>, CircuitSimplificationPipelineError> {
+        value
+            .split('+')
+            .map(|c| {
+                NpnClass::from_name(c.trim()).ok_or_else(|| {
+                    CircuitSimplificationPipelineError::InvalidParameterValue(
+                        pass.to_string(),
+                        name.to_string(),
+                        value.to_string(),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn parse_parameters(
+        pass: &str,
+        parameters: &str,
+    ) -> Result<Vec<(String, String)>, CircuitSimplificationPipelineError> {
+        let mut result: Vec<(String, String)> = vec![];
+        for parameter in parameters.split(',').map(|p| p.trim()) {
+            let (name, value) = match parameter.split_once('=') {
+                Some((n, v)) if !v.trim().is_empty() => (n.trim(), v.trim()),
+                _ => {
+                    return Err(CircuitSimplificationPipelineError::ParameterWithoutValue(
+                        pass.to_string(),
+                        parameter.to_string(),
+                    ))
+                }
+            };
+            if result.iter().any(|(n, _)| n == name) {
+                return Err(CircuitSimplificationPipelineError::DuplicateParameter(
+                    pass.to_string(),
+                    name.to_string(),
+                ));
+            }
+            result.push((name.to_string(), value.to_string()));
+        }
+        Ok(result)
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Parse a single pass, the syntax is `name` or `name:param=value,param=value`.
+    pub fn parse(pass: &str) -> Result<Self, CircuitSimplificationPipelineError> {
+        let (name, parameters) = match pass.split_once(':') {
+            Some((n, p)) => (n.trim(), Self::parse_parameters(n.trim(), p)?),
+            None => (pass.trim(), vec![]),
+        };
+        if name.is_empty() {
+            return Err(CircuitSimplificationPipelineError::EmptyPassName);
+        }
+
+        let mut result = match name {
+            "remove_unused" => Self::RemoveUnused,
+            "strash" => Self::StructuralHash,
+            "generic_patterns" => Self::DetectGenericPatterns { classes: None },
+            "merge_and_gates" => Self::MergeAndGates,
+            "condense" => Self::Condense,
+            "tech_map" => Self::TechnologyMap { k: 4, l: 10 },
+            "rewrite" => Self::Rewrite { l: 8 },
+            _ => {
+                return Err(CircuitSimplificationPipelineError::UnknownPass(
+                    name.to_string(),
+                ))
+            }
+        };
+
+        for (parameter, value) in parameters.iter() {
+            match (&mut result, parameter.as_str()) {
+                (Self::TechnologyMap { k, l: _ }, "k") => {
+                    // the cuts are turned into truth tables
+                    *k = Self::parse_usize_parameter(
+                        name,
+                        parameter,
+                        value,
+                        2..=TRUTH_TABLE_MAX_INPUTS,
+                    )?;
+                }
+                (Self::TechnologyMap { k: _, l }, "l") | (Self::Rewrite { l }, "l") => {
+                    *l = Self::parse_usize_parameter(name, parameter, value, 1..=usize::MAX)?;
+                }
+                (Self::DetectGenericPatterns { classes }, "classes") => {
+                    *classes = Some(Self::parse_npn_classes_parameter(name, parameter, value)?);
+                }
+                _ => {
+                    return Err(CircuitSimplificationPipelineError::UnknownParameter(
+                        name.to_string(),
+                        parameter.to_string(),
+                    ))
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Create the simplifier that performs this pass.
+    pub fn get_simplifier(&self, verbose: bool) -> Box<dyn CircuitSimplifier> {
+        match self {
+            Self::RemoveUnused => Box::new(CircuitUnusedSignalRemover::new()),
+            Self::StructuralHash => Box::new(CircuitStructuralHashing::new()),
+            Self::DetectGenericPatterns { classes } => match classes {
+                Some(c) => Box::new(CircuitGenericPatternDetector::new_with_npn_classes(
+                    verbose,
+                    c.to_owned(),
+                )),
+                None => Box::new(CircuitGenericPatternDetector::new(verbose)),
+            },
+            Self::MergeAndGates => Box::new(CircuitAndGateMerger::new(verbose)),
+            Self::Condense => Box::new(CircuitCondenser::new()),
+            Self::TechnologyMap { k, l } => Box::new(CircuitTechnologyMapper::new(*k, *l)),
+            Self::Rewrite { l } => Box::new(CircuitRewriter::new(*l)),
+        }
+    }
+}
+
+// ************************************************************************************************
+// impl pipeline
+// ************************************************************************************************
+
+impl CircuitSimplificationPipeline {
+    // ********************************************************************************************
+    // constants
+    // ********************************************************************************************
+
+    /// The sequence of passes that is used before running a proof engine.
+    pub const DEFAULT: &'static str =
+        "remove_unused;strash;remove_unused;generic_patterns;remove_unused;merge_and_gates;condense";
+
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn run_iteration(
+        &self,
+        circuit: &mut Circuit,
+        iteration: usize,
+        verbose: bool,
+        tracker: &mut SignalTracker,
+        statistics: &mut Vec<CircuitSimplificationPassStatistics>,
+    ) -> bool {
+        for pass in self.passes.iter() {
+            if Utils::is_memory_limit_reached(self.memory_limit_mb) {
+                if verbose {
+                    println!(
+                        "Memory limit of {} MB reached, skipping the remaining passes.",
+                        self.memory_limit_mb
+                    );
+                }
+                return false;
+            }
+            let mut simplifier = pass.get_simplifier(verbose);
+            let nodes_before = circuit.get_number_of_nodes();
+            let timer = Instant::now();
+            tracker.push(simplifier.simplify(circuit));
+            let s = CircuitSimplificationPassStatistics {
+                iteration,
+                pass: pass.to_owned(),
+                title: simplifier.title(),
+                time: timer.elapsed(),
+                nodes_before,
+                nodes_after: circuit.get_number_of_nodes(),
+            };
+            if verbose {
+                println!(
+                    "Elapsed time = {:.3} sec, size = {}, {}.",
+                    s.time.as_secs_f32(),
+                    s.nodes_after,
+                    s.title
+                )
+            };
+            statistics.push(s);
+        }
+        true
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    pub fn new(passes: Vec<CircuitSimplificationPass>) -> Self {
+        Self {
+            passes,
+            memory_limit_mb: usize::MAX,
+        }
+    }
+
+    /// Sets the memory limit in MB, once the resident memory of the process reaches it no more
+    /// passes are started, and the circuit is left as simplified by the passes that completed.
+    pub fn set_memory_limit_mb(&mut self, memory_limit_mb: usize) {
+        self.memory_limit_mb = memory_limit_mb;
+    }
+
+    /// Parse a pipeline from a string of passes separated by `;`, empty entries are ignored.
+    pub fn parse(pipeline: &str) -> Result<Self, CircuitSimplificationPipelineError> {
+        let mut passes = vec![];
+        for pass in pipeline.split(';').filter(|p| !p.trim().is_empty()) {
+            passes.push(CircuitSimplificationPass::parse(pass)?);
+        }
+        Ok(Self::new(passes))
+    }
+
+    pub fn get_passes(&self) -> &[CircuitSimplificationPass] {
+        &self.passes
+    }
+
+    /// Run every pass of the pipeline once.
+    pub fn run(
+        &self,
+        circuit: &mut Circuit,
+        verbose: bool,
+    ) -> (SignalTracker, Vec<CircuitSimplificationPassStatistics>) {
+        self.run_to_fixpoint(circuit, 1, verbose)
+    }
+
+    /// Run the pipeline repeatedly until an entire iteration does not reduce the number of nodes
+    /// in the circuit, or until `max_iterations` iterations were performed.
+    pub fn run_to_fixpoint(
+        &self,
+        circuit: &mut Circuit,
+        max_iterations: usize,
+        verbose: bool,
+    ) -> (SignalTracker, Vec<CircuitSimplificationPassStatistics>) {
+        let mut tracker = SignalTracker::new();
+        let mut statistics = vec![];
+        for iteration in 0..max_iterations {
+            let nodes_before = circuit.get_number_of_nodes();
+            let completed =
+                self.run_iteration(circuit, iteration, verbose, &mut tracker, &mut statistics);
+            if !completed || circuit.get_number_of_nodes() >= nodes_before {
+                break;
+            }
+        }
+        (tracker, statistics)
+    }
+
+    /// Summarize the statistics of a run in a table.
+    pub fn get_statistics_table(statistics: &[CircuitSimplificationPassStatistics]) -> PrettyTable {
+        let header = [
+            "Iteration",
+            "Pass",
+            "Time (sec)",
+            "Nodes before",
+            "Nodes after",
+        ];
+        let mut table = PrettyTable::new(header.iter().map(|s| s.to_string()).collect());
+        for s in statistics.iter() {
+            table
+                .add_row(vec![
+                    s.iteration.to_string(),
+                    s.pass.to_string(),
+                    format!("{:.3}", s.time.as_secs_f32()),
+                    s.nodes_before.to_string(),
+                    s.nodes_after.to_string(),
+                ])
+                .unwrap();
+        }
+        table
+    }
+}
+
+impl Default for CircuitSimplificationPipeline {
+    fn default() -> Self {
+        Self::parse(Self::DEFAULT).unwrap()
+    }
+}
+
+// ************************************************************************************************
+// printing
+// ************************************************************************************************
+
+impl fmt::Display for CircuitSimplificationPass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::RemoveUnused => write!(f, "remove_unused"),
+            Self::StructuralHash => write!(f, "strash"),
+            Self::DetectGenericPatterns { classes: None } => write!(f, "generic_patterns"),
+            Self::DetectGenericPatterns { classes: Some(c) } => {
+                let names: Vec<String> = c.iter().map(|x| x.to_string()).collect();
+                write!(f, "generic_patterns:classes={}", names.join("+"))
+            }
+            Self::MergeAndGates => write!(f, "merge_and_gates"),
+            Self::Condense => write!(f, "condense"),
+            Self::TechnologyMap { k, l } => write!(f, "tech_map:k={},l={}", k, l),
+            Self::Rewrite { l } => write!(f, "rewrite:l={}", l),
+        }
+    }
+}
+
+impl fmt::Display for CircuitSimplificationPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let passes: Vec<String> = self.passes.iter().map(|p| p.to_string()).collect();
+        write!(f, "{}", passes.join(";"))
+    }
+}
+
+impl fmt::Display for CircuitSimplificationPipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CircuitSimplificationPipelineError::EmptyPassName => {
+                write!(f, "Pass with parameters but without a name.")
+            }
+            CircuitSimplificationPipelineError::UnknownPass(p) => {
+                write!(f, "Unknown simplification pass '{}'.", p)
+            }
+            CircuitSimplificationPipelineError::UnknownParameter(p, n) => {
+                write!(f, "Pass '{}' has no parameter '{}'.", p, n)
+            }
+            CircuitSimplificationPipelineError::ParameterWithoutValue(p, n) => {
+                write!(
+                    f,
+                    "Parameter '{}' of pass '{}' is not of the form name=value.",
+                    n, p
+                )
+            }
+            CircuitSimplificationPipelineError::InvalidParameterValue(p, n, v) => {
+                write!(
+                    f,
+                    "Invalid value '{}' for parameter '{}' of pass '{}'.",
+                    v, n, p
+                )
+            }
+            CircuitSimplificationPipelineError::DuplicateParameter(p, n) => {
+                write!(
+                    f,
+                    "Parameter '{}' of pass '{}' is given more than once.",
+                    n, p
+                )
+            }
+        }
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[test]
+fn test_parse_simplification_pipeline() {
+    let p = CircuitSimplificationPipeline::parse(" strash ; tech_map:k=3,l=7;;condense").unwrap();
+    assert_eq!(
+        p.get_passes(),
+        &[
+            CircuitSimplificationPass::StructuralHash,
+            CircuitSimplificationPass::TechnologyMap { k: 3, l: 7 },
+            CircuitSimplificationPass::Condense
+        ]
+    );
+    assert_eq!(p.to_string(), "strash;tech_map:k=3,l=7;condense");
+    assert_eq!(
+        CircuitSimplificationPipeline::parse("rewrite:l=4")
+            .unwrap()
+            .get_passes(),
+        &[CircuitSimplificationPass::Rewrite { l: 4 }]
+    );
+    let p = CircuitSimplificationPipeline::parse("generic_patterns:classes=xor2+ite").unwrap();
+    assert_eq!(p.to_string(), "generic_patterns:classes=xor2+ite");
+    assert_eq!(
+        CircuitSimplificationPipeline::default().to_string(),
+        CircuitSimplificationPipeline::DEFAULT
+    );
+    assert_eq!(
+        CircuitSimplificationPipeline::parse("strash;foo"),
+        Err(CircuitSimplificationPipelineError::UnknownPass(
+            "foo".to_string()
+        ))
+    );
+    assert_eq!(
+        CircuitSimplificationPipeline::parse("tech_map:k=x"),
+        Err(CircuitSimplificationPipelineError::InvalidParameterValue(
+            "tech_map".to_string(),
+            "k".to_string(),
+            "x".to_string()
+        ))
+    );
+    for (pass, parameter, value) in [
+        ("tech_map:k=5", "k", "5"),
+        ("tech_map:k=1", "k", "1"),
+        ("tech_map:l=0", "l", "0"),
+    ] {
+        assert_eq!(
+            CircuitSimplificationPipeline::parse(pass),
+            Err(CircuitSimplificationPipelineError::InvalidParameterValue(
+                "tech_map".to_string(),
+                parameter.to_string(),
+                value.to_string()
+            ))
+        );
+    }
+    assert_eq!(
+        CircuitSimplificationPipeline::parse("strash:k=4"),
+        Err(CircuitSimplificationPipelineError::UnknownParameter(
+            "strash".to_string(),
+            "k".to_string()
+        ))
+    );
+}
@@ -0,0 +1,109 @@
+//! Reduces the circuit to the sequential cone of influence of a single property.
+
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use crate::models::{
+    circuit::CircuitSimplifier, signal_tracker::SignalTransformation, Circuit, UniqueSortedVec,
+    Wire,
+};
+
+use super::CircuitUnusedSignalRemover;
+
+// ************************************************************************************************
+// simplifier
+// ************************************************************************************************
+
+pub struct CircuitPropertyConeReducer {
+    property: Wire,
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl CircuitPropertyConeReducer {
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Create a reducer that keeps only the given property, the property is a bad wire of the
+    /// circuit, or an output wire if the circuit has no bad wires.
+    pub fn new(property: Wire) -> Self {
+        Self { property }
+    }
+
+    /// Removes all properties except the given one, and then removes all the signals that are
+    /// not in the sequential cone of influence of the property and the invariant constraints.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the property is not a property of the circuit.
+    pub fn reduce_to_cone_of_property(
+        circuit: &mut Circuit,
+        property: Wire,
+    ) -> SignalTransformation {
+        let only_property = UniqueSortedVec::from_ordered_set(vec![property]);
+        if circuit.bad.is_empty() {
+            assert!(
+                circuit.outputs.contains(&property),
+                "Property is not an output of the circuit."
+            );
+            circuit.outputs = only_property;
+        } else {
+            assert!(
+                circuit.bad.contains(&property),
+                "Property is not a bad wire of the circuit."
+            );
+            circuit.bad = only_property;
+            circuit.outputs = UniqueSortedVec::new();
+        }
+
+        CircuitUnusedSignalRemover::remove_unused_signals(circuit)
+    }
+}
+
+impl CircuitSimplifier for CircuitPropertyConeReducer {
+    fn simplify(&mut self, circuit: &mut Circuit) -> SignalTransformation {
+        Self::reduce_to_cone_of_property(circuit, self.property)
+    }
+
+    fn title(&self) -> String {
+        format!("Reduce to cone of property {}", self.property)
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[test]
+fn test_reduce_to_cone_of_property() {
+    use crate::models::{AndInverterGraph, Signal, SignalTracker, TernaryValue};
+
+    // two properties, each one depends on a different latch
+    let aig = AndInverterGraph::new(
+        Signal::new(4),
+        2,
+        &[
+            (Signal::new(1).wire(false), TernaryValue::False),
+            (Signal::new(2).wire(false), TernaryValue::False),
+        ],
+        vec![],
+        vec![Signal::new(3).wire(false), Signal::new(4).wire(false)],
+        vec![],
+        &[],
+        String::new(),
+    )
+    .unwrap();
+    let mut circuit = Circuit::from_aig(&aig);
+    let mut tracker = SignalTracker::new();
+    tracker.push(circuit.reduce_to_cone_of_property(Signal::new(4).wire(false)));
+    assert_eq!(circuit.get_bad_wires().len(), 1);
+    assert_eq!(circuit.get_latch_signals().len(), 1);
+    assert_eq!(circuit.get_input_signals().len(), 1);
+    assert_eq!(tracker.get(Signal::new(1)), None);
+    assert_eq!(tracker.get(Signal::new(3)), None);
+    assert_eq!(tracker.get(Signal::new(4)), Some(Signal::new(4)));
+}
@@ -0,0 +1,765 @@
+//! DAG aware rewriting of and gates, similar to `rewrite` in ABC.
+//!
+//! Every gate is matched with its 4-feasible cuts, the function of each cut is canonized up to
+//! NPN (input negation, input permutation, output negation) and looked up in a library of small
+//! and-inverter structures that is precomputed once per process. If the structure of the class
+//! is smaller than the logic that would be freed by replacing the cone of the cut, the users of
+//! the gate are redirected to a new instance of the structure.
+//!
+//! The replaced logic is left in the circuit without users (in order to keep the signal
+//! transformation a simple re-ordering), so this pass should be followed by removing unused
+//! signals. Circuits that contain generic gates are left unchanged.
+
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::sync::OnceLock;
+
+use fxhash::{FxHashMap, FxHashSet};
+
+use crate::models::{
+    circuit::{
+        cut_enumeration::Cut,
+        node_types::{CircuitAnd, CircuitLatch, CircuitNodeType},
+        CircuitSimplifier,
+    },
+    signal_tracker::SignalTransformation,
+    truth_table::{
+        npn::{NpnCache, NpnClass, NpnTransform},
+        IDENTITY_TRUTH_TABLES,
+    },
+    Circuit, Signal, UniqueSortedHashMap, UniqueSortedVec, Wire,
+};
+
+use super::CircuitTechnologyMapper;
+
+// ************************************************************************************************
+// constants
+// ************************************************************************************************
+
+const CUT_SIZE: usize = 4;
+const NUMBER_OF_FUNCTIONS: usize = 1 << (1 << CUT_SIZE);
+
+// functions that need more 2-input and gates than this (as a tree) are not in the library,
+// covers 51118 out of the 65536 functions of 4 inputs.
+const MAX_STRUCTURE_SIZE: u8 = 9;
+
+// ************************************************************************************************
+// library
+// ************************************************************************************************
+
+/// Smallest tree of 2-input and gates for every function of 4 inputs, computed by enumerating
+/// all functions that can be built with 0, 1, 2, ... gates. The enumeration is bounded by
+/// `MAX_STRUCTURE_SIZE` and takes about 30ms in release builds (200ms in debug builds), so it is
+/// done once per process when the first circuit is rewritten.
+struct RewriteLibrary {
+    size: Vec<u8>,
+    decomposition: Vec<(u16, u16)>,
+    is_negated: Vec<bool>,
+}
+
+/// A small and-inverter structure where signal 0 is ground, signals `1..=4` are the inputs and
+/// gate `i` is signal `5 + i`.
+#[derive(Debug, Clone)]
+struct RewriteStructure {
+    gates: Vec<(Wire, Wire)>,
+    output: Wire,
+}
+
+// ************************************************************************************************
+// simplifier
+// ************************************************************************************************
+
+pub struct CircuitRewriter {
+    l: usize,
+    function_to_class: NpnCache,
+    class_to_structure: FxHashMap<u16, Option<RewriteStructure>>,
+}
+
+struct RewriteCandidate {
+    leaves: Vec<Signal>,
+    mffc: Vec<Signal>,
+    structure: RewriteStructure,
+    transform: NpnTransform,
+    // gates of the structure that already exist in the circuit
+    existing: Vec<Option<Signal>>,
+}
+
+// ************************************************************************************************
+// impl library
+// ************************************************************************************************
+
+impl RewriteLibrary {
+    fn new() -> Self {
+        let mut size = vec![u8::MAX; NUMBER_OF_FUNCTIONS];
+        let mut decomposition = vec![(0, 0); NUMBER_OF_FUNCTIONS];
+        let mut is_negated = vec![false; NUMBER_OF_FUNCTIONS];
+
+        // functions that need no gates
+        let mut functions_per_size: Vec<Vec<u16>> = vec![vec![]];
+        for f in [0].iter().chain(IDENTITY_TRUTH_TABLES.iter()) {
+            for g in [*f, !*f] {
+                size[g as usize] = 0;
+                functions_per_size[0].push(g);
+            }
+        }
+
+        // functions that are the and of two smaller functions (or its negation)
+        for s in 1..=MAX_STRUCTURE_SIZE as usize {
+            let mut new_functions = vec![];
+            for i in 0..s {
+                let j = s - 1 - i;
+                if i > j {
+                    break;
+                }
+                for (k, a) in functions_per_size[i].iter().enumerate() {
+                    let start = if i == j { k } else { 0 };
+                    for b in functions_per_size[j][start..].iter() {
+                        let f = a & b;
+                        if size[f as usize] != u8::MAX {
+                            continue;
+                        }
+                        size[f as usize] = s as u8;
+                        size[!f as usize] = s as u8;
+                        decomposition[f as usize] = (*a, *b);
+                        decomposition[!f as usize] = (*a, *b);
+                        is_negated[!f as usize] = true;
+                        new_functions.push(f);
+                        new_functions.push(!f);
+                    }
+                }
+            }
+            functions_per_size.push(new_functions);
+        }
+
+        Self {
+            size,
+            decomposition,
+            is_negated,
+        }
+    }
+
+    fn get() -> &'static Self {
+        static LIBRARY: OnceLock<RewriteLibrary> = OnceLock::new();
+        LIBRARY.get_or_init(Self::new)
+    }
+
+    fn add_function_to_structure(
+        &self,
+        f: u16,
+        structure: &mut Vec<(Wire, Wire)>,
+        cache: &mut FxHashMap<u16, Wire>,
+    ) -> Wire {
+        if let Some(w) = cache.get(&f) {
+            return *w;
+        }
+        if let Some(w) = cache.get(&!f) {
+            return !*w;
+        }
+
+        let w = if f == 0 {
+            Wire::CONSTANT_ZERO
+        } else if f == u16::MAX {
+            Wire::CONSTANT_ONE
+        } else if let Some(i) = IDENTITY_TRUTH_TABLES.iter().position(|x| *x == f) {
+            Signal::new(i as u32 + 1).wire(false)
+        } else if let Some(i) = IDENTITY_TRUTH_TABLES.iter().position(|x| *x == !f) {
+            Signal::new(i as u32 + 1).wire(true)
+        } else if self.is_negated[f as usize] {
+            !self.add_function_to_structure(!f, structure, cache)
+        } else {
+            let (a, b) = self.decomposition[f as usize];
+            let a = self.add_function_to_structure(a, structure, cache);
+            let b = self.add_function_to_structure(b, structure, cache);
+            structure.push((a, b));
+            Signal::new((CUT_SIZE + structure.len()) as u32).wire(false)
+        };
+
+        cache.insert(f, w);
+        w
+    }
+
+    /// Returns the structure of a function, or None if the function is not in the library.
+    fn get_structure(&self, f: u16) -> Option<RewriteStructure> {
+        if self.size[f as usize] > MAX_STRUCTURE_SIZE {
+            return None;
+        }
+        let mut gates = vec![];
+        let output = self.add_function_to_structure(f, &mut gates, &mut FxHashMap::default());
+        Some(RewriteStructure { gates, output })
+    }
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl CircuitRewriter {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn get_truth_table_of_cut(circuit: &Circuit, signal: &Signal, cut: &Cut) -> (u16, Vec<Signal>) {
+        let tt = CircuitTechnologyMapper::calculate_truth_table_for_cut(circuit, signal, cut);
+        let leaves = tt.get_signals().peek().to_vec();
+        let mut rows = 1 << leaves.len();
+        let mut f = tt.peek() & tt.get_mask();
+        while rows < (1 << CUT_SIZE) {
+            f |= f << rows;
+            rows <<= 1;
+        }
+        (f, leaves)
+    }
+
+    /// Returns the gates that would be removed if the signal were not used anymore, when the
+    /// logic is cut at the provided cut.
+    fn get_maximum_fanout_free_cone(
+        circuit: &Circuit,
+        signal: Signal,
+        cut: &Cut,
+        refs: &mut UniqueSortedHashMap<Signal, usize>,
+    ) -> Vec<Signal> {
+        let mut mffc = vec![signal];
+        let mut decremented = vec![];
+        let mut i = 0;
+        while i < mffc.len() {
+            let s = mffc[i];
+            i += 1;
+            let inputs = match &circuit.nodes.get(&s).unwrap().node_type {
+                CircuitNodeType::And(a) => a.inputs.iter().map(|w| w.signal()).collect(),
+                _ => vec![],
+            };
+            for input in UniqueSortedVec::from_sequence(inputs).iter() {
+                if cut.contains(input) {
+                    continue;
+                }
+                let r = refs.get_mut(input).unwrap();
+                *r -= 1;
+                decremented.push(*input);
+                if *r == 0 {
+                    mffc.push(*input);
+                }
+            }
+        }
+
+        // restore reference counts
+        for s in decremented {
+            *refs.get_mut(&s).unwrap() += 1;
+        }
+
+        mffc
+    }
+
+    fn get_number_of_2_input_and_gates(circuit: &Circuit, signals: &[Signal]) -> usize {
+        signals
+            .iter()
+            .map(|s| match &circuit.nodes.get(s).unwrap().node_type {
+                CircuitNodeType::And(a) => a.inputs.len() - 1,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    fn get_wires_of_structure_inputs(
+        leaves: &[Wire],
+        transform: &NpnTransform,
+        initial: Wire,
+    ) -> Vec<Wire> {
+        let mut structure_wires = vec![initial; CUT_SIZE + 1];
+        structure_wires[0] = Wire::CONSTANT_ZERO;
+        for (i, leaf) in leaves.iter().enumerate() {
+            let is_negated = transform.is_input_negated(i);
+            structure_wires[transform.get_permutation()[i] + 1] =
+                Self::negate_if(*leaf, is_negated);
+        }
+        structure_wires
+    }
+
+    fn get_structure_wire(w: &Wire, structure_wires: &[Option<Wire>]) -> Option<Wire> {
+        structure_wires[w.signal().number() as usize].map(|x| Self::negate_if(x, w.is_negated()))
+    }
+
+    /// Finds the gates of the structure that already exist in the circuit (before the rewritten
+    /// signal and outside of the logic that is freed), these gates are shared instead of added.
+    fn find_existing_gates(
+        signal: Signal,
+        leaves: &[Signal],
+        mffc: &[Signal],
+        structure: &RewriteStructure,
+        transform: &NpnTransform,
+        and_gates: &FxHashMap<UniqueSortedVec<Wire>, Signal>,
+    ) -> Vec<Option<Signal>> {
+        let leaves: Vec<Wire> = leaves.iter().map(|s| s.wire(false)).collect();
+        let mut structure_wires: Vec<Option<Wire>> =
+            Self::get_wires_of_structure_inputs(&leaves, transform, Wire::CONSTANT_ZERO)
+                .into_iter()
+                .map(Some)
+                .collect();
+        let mut existing = Vec::with_capacity(structure.gates.len());
+        for (a, b) in structure.gates.iter() {
+            let a = Self::get_structure_wire(a, &structure_wires);
+            let b = Self::get_structure_wire(b, &structure_wires);
+            let e = match (a, b) {
+                (Some(a), Some(b)) => and_gates
+                    .get(&UniqueSortedVec::from_sequence(vec![a, b]))
+                    .filter(|s| **s < signal && !mffc.contains(s))
+                    .copied(),
+                _ => None,
+            };
+            existing.push(e);
+            structure_wires.push(e.map(|s| s.wire(false)));
+        }
+        existing
+    }
+
+    fn get_class_of_function(&mut self, f: u16) -> (u16, NpnTransform) {
+        let (class, transform) =
+            NpnClass::canonize_with_cache(CUT_SIZE, f, &mut self.function_to_class);
+        (class.get_representative(), transform)
+    }
+
+    fn get_structure_of_class(&mut self, class: u16) -> Option<RewriteStructure> {
+        self.class_to_structure
+            .entry(class)
+            .or_insert_with(|| RewriteLibrary::get().get_structure(class))
+            .to_owned()
+    }
+
+    /// Find the cut of the gate where replacing the cone with the library structure reduces the
+    /// number of and gates the most.
+    fn get_best_candidate(
+        &mut self,
+        circuit: &Circuit,
+        signal: Signal,
+        cuts: &[Cut],
+        refs: &mut UniqueSortedHashMap<Signal, usize>,
+        and_gates: &FxHashMap<UniqueSortedVec<Wire>, Signal>,
+    ) -> Option<(usize, RewriteCandidate)> {
+        let mut best: Option<(usize, RewriteCandidate)> = None;
+        for cut in cuts.iter().filter(|c| c.peek() != &[signal]) {
+            let cone = circuit.get_cone_of_signal_bounded_by_cut(&signal, cut);
+            if cone.iter().any(|s| {
+                !cut.contains(s)
+                    && !matches!(
+                        circuit.nodes.get(s).unwrap().node_type,
+                        CircuitNodeType::And(_)
+                    )
+            }) {
+                continue;
+            }
+
+            let (f, leaves) = Self::get_truth_table_of_cut(circuit, &signal, cut);
+            let (class, transform) = self.get_class_of_function(f);
+            let structure = match self.get_structure_of_class(class) {
+                Some(s) => s,
+                None => continue,
+            };
+            let mffc = Self::get_maximum_fanout_free_cone(circuit, signal, cut, refs);
+            let freed = Self::get_number_of_2_input_and_gates(circuit, &mffc);
+            let existing = Self::find_existing_gates(
+                signal, &leaves, &mffc, &structure, &transform, and_gates,
+            );
+            let added = existing.iter().filter(|e| e.is_none()).count();
+            if freed <= added {
+                continue;
+            }
+            let gain = freed - added;
+            if best.as_ref().map(|(g, _)| gain > *g).unwrap_or(true) {
+                let candidate = RewriteCandidate {
+                    leaves,
+                    mffc,
+                    structure,
+                    transform,
+                    existing,
+                };
+                best = Some((gain, candidate));
+            }
+        }
+        best
+    }
+
+    /// Choose rewrites from the outputs towards the inputs, a rewrite is only taken if it does
+    /// not interfere with the rewrites that were already taken, so that the gains add up.
+    fn choose_rewrites(&mut self, circuit: &Circuit) -> FxHashMap<Signal, RewriteCandidate> {
+        let mut refs = circuit.get_number_of_references();
+        let refs_for_cost = refs.to_owned();
+        let cuts = circuit.enumerate_k_feasible_cuts_leaving_only_l_best(
+            CUT_SIZE,
+            self.l,
+            |cut| circuit.cut_cost_function_used_by_abc(cut, CUT_SIZE, &refs_for_cost),
+            false,
+        );
+
+        let mut and_gates: FxHashMap<UniqueSortedVec<Wire>, Signal> = Default::default();
+        for signal in circuit.gates.iter() {
+            if let CircuitNodeType::And(a) = &circuit.nodes.get(signal).unwrap().node_type {
+                and_gates.entry(a.inputs.to_owned()).or_insert(*signal);
+            }
+        }
+
+        let mut chosen: FxHashMap<Signal, RewriteCandidate> = Default::default();
+        let mut removed: FxHashSet<Signal> = Default::default();
+        let mut used: FxHashSet<Signal> = Default::default();
+        for signal in circuit.gates.iter().rev().copied() {
+            if removed.contains(&signal) {
+                continue;
+            }
+            let cuts: Vec<Cut> = cuts
+                .get(&signal)
+                .unwrap()
+                .iter()
+                .map(|c| c.cut.to_owned())
+                .collect();
+            let candidate =
+                match self.get_best_candidate(circuit, signal, &cuts, &mut refs, &and_gates) {
+                    Some((_, c)) => c,
+                    None => continue,
+                };
+            let is_mffc_free = candidate
+                .mffc
+                .iter()
+                .all(|s| !removed.contains(s) && (*s == signal || !used.contains(s)));
+            let are_leaves_alive = candidate
+                .leaves
+                .iter()
+                .chain(candidate.existing.iter().flatten())
+                .all(|s| !removed.contains(s) || chosen.contains_key(s));
+            if !is_mffc_free || !are_leaves_alive {
+                continue;
+            }
+            removed.extend(candidate.mffc.iter().copied());
+            used.extend(candidate.leaves.iter().copied());
+            used.extend(candidate.existing.iter().flatten().copied());
+            chosen.insert(signal, candidate);
+        }
+
+        chosen
+    }
+
+    fn negate_if(wire: Wire, is_negated: bool) -> Wire {
+        if is_negated {
+            !wire
+        } else {
+            wire
+        }
+    }
+
+    fn translate_wire(
+        wire: &Wire,
+        mapping: &UniqueSortedHashMap<Signal, Signal>,
+        replacements: &FxHashMap<Signal, Wire>,
+    ) -> Wire {
+        match replacements.get(&wire.signal()) {
+            Some(w) => Self::negate_if(*w, wire.is_negated()),
+            None => mapping.get(&wire.signal()).unwrap().wire(wire.is_negated()),
+        }
+    }
+
+    /// Adds the gates of the structure right before the gate that is rewritten, returns the
+    /// wire that replaces the gate.
+    fn add_structure(
+        candidate: &RewriteCandidate,
+        mapping: &UniqueSortedHashMap<Signal, Signal>,
+        replacements: &FxHashMap<Signal, Wire>,
+        next_signal: &mut Signal,
+        and_details: &mut Vec<(Signal, CircuitAnd)>,
+    ) -> Wire {
+        // inputs that the function does not depend on are connected to ground
+        let leaves: Vec<Wire> = candidate
+            .leaves
+            .iter()
+            .map(|s| Self::translate_wire(&s.wire(false), mapping, replacements))
+            .collect();
+        let mut structure_wires =
+            Self::get_wires_of_structure_inputs(&leaves, &candidate.transform, Wire::CONSTANT_ZERO);
+        let translate = |w: &Wire, structure_wires: &[Wire]| {
+            Self::negate_if(
+                structure_wires[w.signal().number() as usize],
+                w.is_negated(),
+            )
+        };
+
+        for ((a, b), e) in candidate
+            .structure
+            .gates
+            .iter()
+            .zip(candidate.existing.iter())
+        {
+            if let Some(e) = e {
+                structure_wires.push(Self::translate_wire(&e.wire(false), mapping, replacements));
+                continue;
+            }
+            let inputs = vec![
+                translate(a, &structure_wires),
+                translate(b, &structure_wires),
+            ];
+            let inputs = UniqueSortedVec::from_sequence(inputs);
+            and_details.push((*next_signal, CircuitAnd { inputs }));
+            structure_wires.push(next_signal.wire(false));
+            *next_signal = Signal::new(next_signal.number() + 1);
+        }
+
+        Self::negate_if(
+            translate(&candidate.structure.output, &structure_wires),
+            candidate.transform.is_output_negated(),
+        )
+    }
+
+    fn rewrite_circuit(
+        circuit: &mut Circuit,
+        chosen: &FxHashMap<Signal, RewriteCandidate>,
+    ) -> SignalTransformation {
+        let mut mapping = UniqueSortedHashMap::new_like(&circuit.nodes);
+        let mut replacements: FxHashMap<Signal, Wire> = Default::default();
+        let mut and_details = vec![];
+        let mut next_signal = Signal::new(1);
+
+        // give new signals, structures are placed right before the gate they replace
+        for signal in circuit.nodes.iter_sorted() {
+            if signal == Signal::GROUND {
+                mapping.insert(signal, signal);
+                continue;
+            }
+            if let Some(candidate) = chosen.get(&signal) {
+                let w = Self::add_structure(
+                    candidate,
+                    &mapping,
+                    &replacements,
+                    &mut next_signal,
+                    &mut and_details,
+                );
+                replacements.insert(signal, w);
+            }
+            mapping.insert(signal, next_signal);
+            next_signal = Signal::new(next_signal.number() + 1);
+        }
+
+        // translate the old nodes
+        let t = |w: &Wire| Self::translate_wire(w, &mapping, &replacements);
+        let mut inputs = vec![];
+        let mut latch_details = vec![];
+        for (signal, node) in circuit.nodes.iter_pairs() {
+            let new_signal = *mapping.get(&signal).unwrap();
+            match &node.node_type {
+                CircuitNodeType::ConstantZero => {}
+                CircuitNodeType::Input => inputs.push(new_signal),
+                CircuitNodeType::Latch(l) => latch_details.push((
+                    new_signal,
+                    CircuitLatch {
+                        input: t(&l.input),
+                        initial: l.initial,
+                    },
+                )),
+                CircuitNodeType::And(a) => {
+                    // users of a replaced gate use the structure, so its cone loses its users
+                    let inputs = UniqueSortedVec::from_sequence(a.inputs.iter().map(t).collect());
+                    and_details.push((new_signal, CircuitAnd { inputs }));
+                }
+                CircuitNodeType::GenericGate(_) => unreachable!(),
+            }
+        }
+        let translate_all = |wires: &UniqueSortedVec<Wire>| {
+            UniqueSortedVec::from_sequence(wires.iter().map(t).collect())
+        };
+
+        *circuit = Circuit::new(
+            UniqueSortedVec::from_sequence(inputs),
+            latch_details,
+            and_details,
+            vec![],
+            translate_all(&circuit.outputs),
+            translate_all(&circuit.bad),
+            translate_all(&circuit.constraints),
+        )
+        .unwrap();
+        debug_assert!(circuit.check().is_ok());
+
+        SignalTransformation::SignalReorder(mapping)
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Create a rewriter that keeps the `l` best 4-feasible cuts of every gate.
+    pub fn new(l: usize) -> Self {
+        Self {
+            l,
+            function_to_class: Default::default(),
+            class_to_structure: Default::default(),
+        }
+    }
+
+    /// Rewrites the circuit, returns the transformation and the number of 2-input and gates
+    /// that are saved once the unused signals are removed. Circuits that contain generic gates
+    /// are not rewritten, the transformation is then a re-ordering that keeps every signal.
+    pub fn rewrite(&mut self, circuit: &mut Circuit) -> (SignalTransformation, usize) {
+        let has_generic_gates = circuit.gates.iter().any(|s| {
+            matches!(
+                circuit.nodes.get(s).unwrap().node_type,
+                CircuitNodeType::GenericGate(_)
+            )
+        });
+        let chosen = if has_generic_gates {
+            Default::default()
+        } else {
+            self.choose_rewrites(circuit)
+        };
+
+        let saved = chosen
+            .values()
+            .map(|c| {
+                let added = c.existing.iter().filter(|e| e.is_none()).count();
+                Self::get_number_of_2_input_and_gates(circuit, &c.mffc) - added
+            })
+            .sum();
+
+        (Self::rewrite_circuit(circuit, &chosen), saved)
+    }
+}
+
+impl CircuitSimplifier for CircuitRewriter {
+    fn simplify(&mut self, circuit: &mut Circuit) -> SignalTransformation {
+        self.rewrite(circuit).0
+    }
+
+    fn title(&self) -> String {
+        "Rewrite using 4 input cuts".to_string()
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[test]
+fn test_rewrite_library() {
+    // every structure of a class implements the class representative
+    let library = RewriteLibrary::get();
+    for f in [0x8000, 0x9696, 0xE8E8, 0xCAFE] {
+        let (class, t) = NpnClass::canonize(CUT_SIZE, f);
+        let class = class.get_representative();
+        assert_eq!(t.apply(f), class);
+        let s = library.get_structure(class).unwrap();
+        let mut values = vec![0, IDENTITY_TRUTH_TABLES[0], 0xCCCC, 0xF0F0, 0xFF00];
+        let value = |w: &Wire, values: &[u16]| {
+            let v = values[w.signal().number() as usize];
+            if w.is_negated() {
+                !v
+            } else {
+                v
+            }
+        };
+        for (a, b) in s.gates.iter() {
+            values.push(value(a, &values) & value(b, &values));
+        }
+        assert_eq!(value(&s.output, &values), class);
+    }
+    // xor of 3 inputs is 9 gates as a tree, but shared sub-functions are only built once
+    assert_eq!(library.size[0x9696], 9);
+    assert!(library.get_structure(0x9696).unwrap().gates.len() < 9);
+}
+
+#[test]
+fn test_rewrite_library_enumeration_is_bounded() {
+    let library = RewriteLibrary::get();
+    let covered: Vec<u16> = (0..=u16::MAX)
+        .filter(|f| library.size[*f as usize] <= MAX_STRUCTURE_SIZE)
+        .collect();
+    assert_eq!(covered.len(), 51118);
+    // the rest of the functions were not enumerated
+    assert!(library
+        .size
+        .iter()
+        .all(|s| *s <= MAX_STRUCTURE_SIZE || *s == u8::MAX));
+    for f in covered {
+        let gates = library.get_structure(f).unwrap().gates.len();
+        assert!(gates <= library.size[f as usize] as usize);
+    }
+}
+
+#[test]
+fn test_rewrite() {
+    use crate::models::{AndInverterGraph, SignalTracker};
+
+    // 4 = a & b, 5 = a & c, 6 = !4 & !5, 7 = a & !b, 8 = !7 & !4 and the output 9 = 8 & 6,
+    // 8 is !a and so the output is !a & !(a & (b | c)) = !a
+    let aig = AndInverterGraph::new(
+        Signal::new(9),
+        3,
+        &[],
+        vec![Signal::new(9).wire(false)],
+        vec![],
+        vec![],
+        &[
+            (Signal::new(1).wire(false), Signal::new(2).wire(false)),
+            (Signal::new(1).wire(false), Signal::new(3).wire(false)),
+            (Signal::new(4).wire(true), Signal::new(5).wire(true)),
+            (Signal::new(1).wire(false), Signal::new(2).wire(true)),
+            (Signal::new(7).wire(true), Signal::new(4).wire(true)),
+            (Signal::new(8).wire(false), Signal::new(6).wire(false)),
+        ],
+        String::new(),
+    )
+    .unwrap();
+    let mut circuit = Circuit::from_aig(&aig);
+    let mut rewriter = CircuitRewriter::new(8);
+    let (t, saved) = rewriter.rewrite(&mut circuit);
+    assert_eq!(saved, 6);
+
+    let mut tracker = SignalTracker::new();
+    tracker.push(t);
+    tracker.push(circuit.remove_unused_signals());
+    assert!(circuit.get_gate_signals().is_empty());
+    assert_eq!(tracker.get(Signal::new(1)), Some(Signal::new(1)));
+    assert_eq!(tracker.get(Signal::new(9)), None);
+    assert_eq!(
+        circuit.get_output_wires().peek(),
+        &[Signal::new(1).wire(true)]
+    );
+}
+
+#[test]
+fn test_rewrite_redirects_internal_users() {
+    use crate::models::{AndInverterGraph, SignalTracker};
+
+    // the cone of 10 is the one of the output in `test_rewrite`, 10 = !a is also used by
+    // 11 = 10 & d, so both outputs must use the rewritten 10 for the old cone to be removed.
+    let w = |n: u32, is_negated: bool| Signal::new(n).wire(is_negated);
+    let aig = AndInverterGraph::new(
+        Signal::new(11),
+        4,
+        &[],
+        vec![w(10, false), w(11, false)],
+        vec![],
+        vec![],
+        &[
+            (w(1, false), w(2, false)),
+            (w(1, false), w(3, false)),
+            (w(5, true), w(6, true)),
+            (w(1, false), w(2, true)),
+            (w(8, true), w(5, true)),
+            (w(9, false), w(7, false)),
+            (w(10, false), w(4, false)),
+        ],
+        String::new(),
+    )
+    .unwrap();
+    let mut circuit = Circuit::from_aig(&aig);
+    assert_eq!(circuit.get_gate_signals().len(), 7);
+
+    let mut tracker = SignalTracker::new();
+    tracker.push(CircuitRewriter::new(8).simplify(&mut circuit));
+    tracker.push(circuit.remove_unused_signals());
+    assert_eq!(circuit.get_gate_signals().len(), 1);
+    let g = circuit.get_gate_signals().peek()[0];
+    assert_eq!(
+        circuit.get_output_wires().peek(),
+        &[w(1, true), g.wire(false)]
+    );
+    match &circuit.get_node(&g).unwrap().node_type {
+        CircuitNodeType::And(a) => assert_eq!(a.inputs.peek(), &[w(1, true), w(4, false)]),
+        _ => panic!(),
+    }
+}
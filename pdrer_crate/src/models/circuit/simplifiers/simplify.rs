@@ -2,15 +2,14 @@
 // use
 // ************************************************************************************************
 
-use std::time::Instant;
-
 use crate::models::{
     circuit::CircuitSimplifier, signal_tracker::SignalTransformation, Circuit, Signal,
-    SignalTracker, UniqueSortedVec,
+    SignalTracker, UniqueSortedVec, Wire,
 };
 
 use super::{
     detect_generic_patterns::CircuitGenericPatternDetector, CircuitAndGateMerger, CircuitCondenser,
+    CircuitPropertyConeReducer, CircuitRewriter, CircuitSimplificationPipeline,
     CircuitStructuralHashing, CircuitTechnologyMapper, CircuitUnusedSignalRemover,
 };
 
@@ -25,30 +24,6 @@ use super::{
 // ************************************************************************************************
 
 impl Circuit {
-    // ********************************************************************************************
-    // helper functions
-    // ********************************************************************************************
-
-    fn perform_simplification<F: CircuitSimplifier>(
-        &mut self,
-        verbose: bool,
-        tracker: &mut SignalTracker,
-        mut f: F,
-    ) {
-        let timer = Instant::now();
-        let message = f.title();
-        let t = f.simplify(self);
-        tracker.push(t);
-        if verbose {
-            println!(
-                "Elapsed time = {:.3} sec, size = {}, {}.",
-                timer.elapsed().as_secs_f32(),
-                self.get_number_of_nodes(),
-                message
-            )
-        };
-    }
-
     // ********************************************************************************************
     // API
     // ********************************************************************************************
@@ -70,23 +45,32 @@ impl Circuit {
     }
 
     pub fn simplify_circuit_before_using_proof_engine(&mut self, verbose: bool) -> SignalTracker {
+        self.simplify_circuit_using_pipeline(&CircuitSimplificationPipeline::default(), 1, verbose)
+    }
+
+    /// Run the given simplification pipeline on the circuit, repeating it until it stops reducing
+    /// the size of the circuit or until `max_iterations` iterations were performed.
+    pub fn simplify_circuit_using_pipeline(
+        &mut self,
+        pipeline: &CircuitSimplificationPipeline,
+        max_iterations: usize,
+        verbose: bool,
+    ) -> SignalTracker {
         if verbose {
             println!(
-                "Size = {}, Simplifying circuit before using proof engine:",
-                self.get_number_of_nodes()
+                "Size = {}, Simplifying circuit using pipeline '{}':",
+                self.get_number_of_nodes(),
+                pipeline
             )
         }
-        let mut map = SignalTracker::new();
-        self.perform_simplification(verbose, &mut map, CircuitUnusedSignalRemover::new());
-        self.perform_simplification(verbose, &mut map, CircuitStructuralHashing::new());
-        self.perform_simplification(verbose, &mut map, CircuitUnusedSignalRemover::new());
-        self.perform_simplification(verbose, &mut map, CircuitGenericPatternDetector::new(false));
-        self.perform_simplification(verbose, &mut map, CircuitUnusedSignalRemover::new());
-        self.perform_simplification(verbose, &mut map, CircuitAndGateMerger::new(false));
-        // self.perform_simplification(verbose, &mut map, CircuitTechnologyMapper::new(4, 10));
-        self.perform_simplification(verbose, &mut map, CircuitCondenser::new());
-
-        map
+        let (tracker, statistics) = pipeline.run_to_fixpoint(self, max_iterations, verbose);
+        if verbose {
+            print!(
+                "{}",
+                CircuitSimplificationPipeline::get_statistics_table(&statistics)
+            );
+        }
+        tracker
     }
 
     pub fn remove_unused_signals(&mut self) -> SignalTransformation {
@@ -114,6 +98,20 @@ impl Circuit {
         s.simplify(self)
     }
 
+    /// Reduce the circuit to the cone of a single property (bad wire, or output wire if the
+    /// circuit has no bad wires), all other properties are removed.
+    pub fn reduce_to_cone_of_property(&mut self, property: Wire) -> SignalTransformation {
+        let mut s = CircuitPropertyConeReducer::new(property);
+        s.simplify(self)
+    }
+
+    /// Rewrite the and gates using 4 input cuts, the replaced logic is left without users and
+    /// should be removed using `remove_unused_signals`.
+    pub fn rewrite(&mut self) -> SignalTransformation {
+        let mut s = CircuitRewriter::new(8);
+        s.simplify(self)
+    }
+
     pub fn default_technology_mapping(&mut self) -> SignalTransformation {
         let mut s = CircuitTechnologyMapper::new(4, 10);
         s.simplify(self)
@@ -45,8 +45,8 @@ impl CircuitTechnologyMapper {
     // common
     // ********************************************************************************************
 
-    fn get_signals_to_keep_from_chosen_cuts(
-        circuit: &mut Circuit,
+    pub(crate) fn get_signals_to_keep_from_chosen_cuts(
+        circuit: &Circuit,
         cut_for_each_signal: &UniqueSortedHashMap<Signal, Cut>,
     ) -> FxHashSet<Signal> {
         let mut signals_we_care_about =
@@ -83,11 +83,17 @@ impl CircuitTechnologyMapper {
                             &mut max_heap,
                         );
                     }
+                    CircuitNodeType::GenericGate(g) => {
+                        Self::insert_if_needed(
+                            g.truth_table.get_signals().iter().copied(),
+                            &signals_we_care_about,
+                            &mut max_heap,
+                        );
+                    }
                     // this might also be signal or input
                     CircuitNodeType::ConstantZero
                     | CircuitNodeType::Input
                     | CircuitNodeType::Latch { .. } => {}
-                    _ => unreachable!(),
                 };
             } else {
                 Self::insert_if_needed(cut.iter().copied(), &signals_we_care_about, &mut max_heap);
@@ -309,9 +315,16 @@ impl CircuitTechnologyMapper {
                         tt.as_mut().unwrap().simplify();
                         signal_to_truth_table.insert(signal.to_owned(), tt.unwrap());
                     }
-                    CircuitNodeType::GenericGate(_) => {
-                        // let truth_tables = a.inputs.iter().map(wire_to_tt);
-                        todo!("Generic gates are not supported yet.")
+                    CircuitNodeType::GenericGate(g) => {
+                        let truth_tables = g
+                            .truth_table
+                            .get_signals()
+                            .iter()
+                            .map(|s| signal_to_truth_table.get(s).unwrap().to_owned())
+                            .collect();
+                        let mut tt = g.truth_table.compose(truth_tables);
+                        tt.simplify();
+                        signal_to_truth_table.insert(signal.to_owned(), tt);
                     }
                     CircuitNodeType::ConstantZero => {
                         signal_to_truth_table
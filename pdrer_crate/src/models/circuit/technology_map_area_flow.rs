@@ -8,10 +8,16 @@ use super::{
     cut_enumeration::{CutSet, CutSetItem},
     Circuit,
 };
-use crate::models::{Signal, UniqueSortedHashMap};
+use crate::models::{Signal, TruthTable, UniqueSortedHashMap};
+use fxhash::FxHashMap;
 type AreaFlow = u32;
 type Value = i32;
 
+/// The area of every truth table is computed once. The area is not the same for all the
+/// functions in an NPN class, since some inputs get special CNF handling and the CNF is reduced
+/// heuristically, so the cache is keyed by the exact truth table.
+type AreaCache = FxHashMap<TruthTable, usize>;
+
 // ************************************************************************************************
 // impl
 // ************************************************************************************************
@@ -41,12 +47,17 @@ impl Circuit {
     //     (value, u_sign)
     // }
 
-    fn get_area_of_cut(&self, csi: &CutSetItem) -> usize {
+    fn get_area_of_cut(&self, csi: &CutSetItem, cache: &mut AreaCache) -> usize {
         let tt = csi.truth_table.as_ref().unwrap();
 
         // tt.negate();
         // let b = tt.calculate_area();
-        tt.calculate_area()
+        if let Some(area) = cache.get(tt) {
+            return *area;
+        }
+        let area = tt.calculate_area();
+        cache.insert(tt.to_owned(), area);
+        area
     }
 
     fn get_area_flow_of_gate_with_only_trivial_cut(
@@ -70,6 +81,19 @@ impl Circuit {
                 }
                 area_flow
             }
+            super::node_types::CircuitNodeType::GenericGate(g) => {
+                let mut area_flow: AreaFlow = g.truth_table.calculate_area() as AreaFlow;
+                for signal in g.truth_table.get_signals().iter() {
+                    let refs = *refs.get(signal).unwrap();
+                    if !self.gates.contains(signal) {
+                        continue;
+                    }
+                    debug_assert!(refs > 0);
+                    let den = if refs > 0 { refs } else { 1 } as AreaFlow;
+                    area_flow += area_flows.get(signal).unwrap() / den;
+                }
+                area_flow
+            }
             _ => unreachable!(),
         }
     }
@@ -79,9 +103,10 @@ impl Circuit {
         csi: &CutSetItem,
         area_flows: &UniqueSortedHashMap<Signal, AreaFlow>,
         refs: &UniqueSortedHashMap<Signal, usize>,
+        cache: &mut AreaCache,
     ) -> (AreaFlow, Value) {
         let mut value: Value = 0;
-        let mut area_flow: AreaFlow = (10 * self.get_area_of_cut(csi)) as AreaFlow;
+        let mut area_flow: AreaFlow = (10 * self.get_area_of_cut(csi, cache)) as AreaFlow;
         for signal in csi.cut.iter() {
             let refs = *refs.get(signal).unwrap();
             value += refs as Value;
@@ -105,6 +130,7 @@ impl Circuit {
         area_flows: &mut UniqueSortedHashMap<Signal, AreaFlow>,
         cut_function: &UniqueSortedHashMap<Signal, CutSet>,
         refs: &UniqueSortedHashMap<Signal, usize>,
+        cache: &mut AreaCache,
     ) -> CutSetItem {
         let possible_cuts = cut_function.get(signal).unwrap();
         debug_assert!(!possible_cuts.is_empty());
@@ -121,7 +147,7 @@ impl Circuit {
             if cut.cut.peek() == &[*signal] {
                 continue;
             }
-            let (are_flow, value) = self.get_area_flow_and_value(cut, area_flows, refs);
+            let (are_flow, value) = self.get_area_flow_and_value(cut, area_flows, refs, cache);
 
             // check if this is the first cut we see
             if best_cut.is_none()
@@ -161,10 +187,16 @@ impl Circuit {
         let mut result = UniqueSortedHashMap::new_like(cut_function);
         let mut area_flows = UniqueSortedHashMap::new_like(cut_function);
         let refs: UniqueSortedHashMap<Signal, usize> = self.get_number_of_references();
+        let mut cache = AreaCache::default();
 
         for signal in cut_function.iter_sorted() {
-            let cut =
-                self.choose_best_cut_by_area_flow(&signal, &mut area_flows, cut_function, &refs);
+            let cut = self.choose_best_cut_by_area_flow(
+                &signal,
+                &mut area_flows,
+                cut_function,
+                &refs,
+                &mut cache,
+            );
 
             // debug_assert!({
             //     let is_unit_cut = cut.cut.peek() == &[signal.to_owned()];
@@ -0,0 +1,246 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use super::{node_types::CircuitNodeType, Circuit};
+use crate::models::{Signal, TernaryValue, Utils, Wire};
+use std::fmt::Write;
+use std::path::Path;
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl Circuit {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    /// Inputs are named `i<n>`, latches `l<n>` and gates `g<n>` where `n` is the number of the
+    /// signal, so the names match the ones in the dot output.
+    fn get_verilog_signal_name(&self, signal: &Signal) -> String {
+        match self.get_node(signal).unwrap().node_type {
+            CircuitNodeType::ConstantZero => "1'b0".to_owned(),
+            CircuitNodeType::Input => format!("i{}", signal.number()),
+            CircuitNodeType::Latch(_) => format!("l{}", signal.number()),
+            CircuitNodeType::And(_) | CircuitNodeType::GenericGate(_) => {
+                format!("g{}", signal.number())
+            }
+        }
+    }
+
+    fn get_verilog_wire_expression(&self, wire: &Wire) -> String {
+        if wire.signal() == Signal::GROUND {
+            return if wire.is_negated() { "1'b1" } else { "1'b0" }.to_owned();
+        }
+        let name = self.get_verilog_signal_name(&wire.signal());
+        if wire.is_negated() {
+            format!("~{name}")
+        } else {
+            name
+        }
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Returns a structural SystemVerilog module of the circuit. The ports are the clock `clk`,
+    /// the inputs and the outputs `o<index>`. Latches are registers that start at their reset
+    /// value, latches with an unknown reset value are left uninitialized. Every gate is a wire,
+    /// and generic gates index into a constant that holds their truth table. The bad wires are
+    /// asserted to be 0 and the constraints are assumed to be 1, in every cycle.
+    pub fn get_verilog(&self, module_name: &str) -> String {
+        let mut s = String::new();
+        let mut ports = vec!["    input wire clk".to_owned()];
+        for input in self.get_input_signals().iter() {
+            ports.push(format!(
+                "    input wire {}",
+                self.get_verilog_signal_name(input)
+            ));
+        }
+        for i in 0..self.get_output_wires().len() {
+            ports.push(format!("    output wire o{i}"));
+        }
+        writeln!(
+            s,
+            "module {} (\n{}\n);",
+            Utils::get_verilog_identifier(module_name),
+            ports.join(",\n")
+        )
+        .unwrap();
+
+        // registers
+        for latch in self.get_latch_signals().iter() {
+            let name = self.get_verilog_signal_name(latch);
+            let initial = match &self.get_node(latch).unwrap().node_type {
+                CircuitNodeType::Latch(l) => l.initial,
+                _ => unreachable!(),
+            };
+            match initial {
+                TernaryValue::False => writeln!(s, "    reg {name} = 1'b0;").unwrap(),
+                TernaryValue::True => writeln!(s, "    reg {name} = 1'b1;").unwrap(),
+                TernaryValue::X => writeln!(s, "    reg {name};").unwrap(),
+            }
+        }
+
+        // gates
+        for gate in self.get_gate_signals().iter() {
+            let name = self.get_verilog_signal_name(gate);
+            let expression = match &self.get_node(gate).unwrap().node_type {
+                CircuitNodeType::And(a) => {
+                    if a.inputs.is_empty() {
+                        "1'b1".to_owned()
+                    } else {
+                        a.inputs
+                            .iter()
+                            .map(|w| self.get_verilog_wire_expression(w))
+                            .collect::<Vec<_>>()
+                            .join(" & ")
+                    }
+                }
+                CircuitNodeType::GenericGate(g) => {
+                    let inputs: Vec<String> = g
+                        .truth_table
+                        .get_signals()
+                        .iter()
+                        .map(|i| self.get_verilog_signal_name(i))
+                        .collect();
+                    let rows: Vec<bool> = (0..(1 << inputs.len()))
+                        .map(|r| g.truth_table.get_value(r))
+                        .collect();
+                    Utils::write_verilog_truth_table(
+                        &mut s,
+                        &format!("{name}_table"),
+                        &rows,
+                        &inputs,
+                    )
+                }
+                _ => unreachable!(),
+            };
+            writeln!(s, "    wire {name} = {expression};").unwrap();
+        }
+
+        // next state
+        if !self.get_latch_signals().is_empty() {
+            writeln!(s, "    always @(posedge clk) begin").unwrap();
+            for latch in self.get_latch_signals().iter() {
+                let input = match &self.get_node(latch).unwrap().node_type {
+                    CircuitNodeType::Latch(l) => l.input,
+                    _ => unreachable!(),
+                };
+                writeln!(
+                    s,
+                    "        {} <= {};",
+                    self.get_verilog_signal_name(latch),
+                    self.get_verilog_wire_expression(&input)
+                )
+                .unwrap();
+            }
+            writeln!(s, "    end").unwrap();
+        }
+
+        // outputs
+        for (i, output) in self.get_output_wires().iter().enumerate() {
+            writeln!(
+                s,
+                "    assign o{i} = {};",
+                self.get_verilog_wire_expression(output)
+            )
+            .unwrap();
+        }
+
+        // properties
+        if !self.get_bad_wires().is_empty() || !self.get_invariant_constraint_wires().is_empty() {
+            writeln!(s, "    always_comb begin").unwrap();
+            for constraint in self.get_invariant_constraint_wires().iter() {
+                writeln!(
+                    s,
+                    "        assume ({});",
+                    self.get_verilog_wire_expression(constraint)
+                )
+                .unwrap();
+            }
+            for bad in self.get_bad_wires().iter() {
+                writeln!(
+                    s,
+                    "        assert ({});",
+                    self.get_verilog_wire_expression(&!*bad)
+                )
+                .unwrap();
+            }
+            writeln!(s, "    end").unwrap();
+        }
+
+        writeln!(s, "endmodule").unwrap();
+        s
+    }
+
+    /// Writes the Verilog module of the circuit to the given path.
+    /// If the path does not exist, it will be created.
+    /// If the path exists, it will be overwritten.
+    pub fn write_verilog(&self, path: &Path, module_name: &str) -> std::io::Result<()> {
+        let data = self.get_verilog(module_name);
+        let parent = if let Some(p) = path.parent() {
+            p
+        } else {
+            return Err(std::io::Error::other("Invalid path"));
+        };
+        if !path.exists() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, data)
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::circuit::simplifiers::detect_generic_patterns::CircuitGenericPatternDetector;
+    use crate::models::circuit::CircuitSimplifier;
+    use crate::models::AndInverterGraph;
+
+    #[test]
+    fn test_circuit_to_verilog() {
+        // inputs 1, 2, 3 and latch 4, 5 = 1 & 2, 6 = !1 & !2, 7 = !5 & !6 (xor of 1 and 2),
+        // 8 = 7 & 3, 9 = 8 & 4 with 9 as bad, !3 as a constraint and the latch is 8 with reset 1
+        let w = |n: u32, negated: bool| Signal::new(n).wire(negated);
+        let aig = AndInverterGraph::new(
+            Signal::new(9),
+            3,
+            &[(w(8, false), TernaryValue::True)],
+            vec![w(7, false)],
+            vec![w(9, false)],
+            vec![w(3, true)],
+            &[
+                (w(1, false), w(2, false)),
+                (w(1, true), w(2, true)),
+                (w(5, true), w(6, true)),
+                (w(7, false), w(3, false)),
+                (w(8, false), w(4, false)),
+            ],
+            String::new(),
+        )
+        .unwrap();
+        let mut circuit = Circuit::from_aig(&aig);
+        CircuitGenericPatternDetector::new(false).simplify(&mut circuit);
+
+        let verilog = circuit.get_verilog("top");
+        assert!(verilog.starts_with("module top (\n    input wire clk,\n    input wire i1,"));
+        assert!(verilog.contains("    output wire o0\n);"));
+        assert!(verilog.contains("    reg l4 = 1'b1;"));
+        assert!(verilog.contains("    wire g9 = l4 & g8;"));
+        assert!(verilog.contains("        l4 <= g8;"));
+        assert!(verilog.contains("        assume (~i3);"));
+        assert!(verilog.contains("        assert (~g9);"));
+        assert!(verilog.ends_with("endmodule\n"));
+
+        // the xor is a generic gate over the inputs 1 and 2
+        assert!(verilog.contains("    wire [3:0] g7_table = 4'b0110;"));
+        assert!(verilog.contains("    wire g7 = g7_table[{i2, i1}];"));
+    }
+}
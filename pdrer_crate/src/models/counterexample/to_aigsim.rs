@@ -43,12 +43,25 @@ impl Counterexample {
         aig: &AndInverterGraph,
         ground_x: bool,
         s2v: F,
+    ) -> String {
+        self.get_aigsim_for_property(mapping, aig, ground_x, 0, s2v)
+    }
+
+    /// Same as `get_aigsim` but for a counterexample that violates the property with the
+    /// given index (index of the bad wire, or of the output if the AIG has no bad wires).
+    pub fn get_aigsim_for_property<F: Fn(Signal) -> Variable>(
+        &self,
+        mapping: &SignalTracker,
+        aig: &AndInverterGraph,
+        ground_x: bool,
+        property: usize,
+        s2v: F,
     ) -> String {
         let latches = aig.get_latch_information();
         let inputs = aig.get_input_signals();
         let mut rows = Vec::with_capacity(self.inputs.len() + 4);
         rows.push("1".to_string());
-        rows.push("b0".to_string());
+        rows.push(format!("b{property}"));
         let x = if ground_x { '0' } else { 'x' };
 
         let mut first_row = String::with_capacity(latches.len());
@@ -14,6 +14,29 @@ impl Definition {
     // helper functions
     // ********************************************************************************************
 
+    /// Returns the clauses over the inputs that exclude every assignment of the inputs for which
+    /// the function is not equal to `value`.
+    fn get_clauses_of_truth_table_rows(&self, value: bool) -> Vec<Clause> {
+        let n = self.inputs.len();
+        let mut values = vec![false; n];
+        let mut clauses = vec![];
+        for row in 0..(1 << n) {
+            for (j, v) in values.iter_mut().enumerate() {
+                *v = (row >> j) & 1 == 1;
+            }
+            if self.function.evaluate(&values) != value {
+                let clause = self
+                    .inputs
+                    .iter()
+                    .zip(values.iter())
+                    .map(|(l, v)| l.negate_if_true(*v))
+                    .collect();
+                clauses.push(Clause::from_sequence(clause));
+            }
+        }
+        clauses
+    }
+
     // ********************************************************************************************
     // API
     // ********************************************************************************************
@@ -49,6 +72,80 @@ impl Definition {
                 }
                 Some(if number_of_true % 2 == 0 { !x } else { x })
             }
+            DefinitionFunction::Ite(_)
+            | DefinitionFunction::Maj3
+            | DefinitionFunction::TruthTable(_) => {
+                // the result is known if it is the same for every value of the unknown inputs
+                let known: Vec<Option<bool>> = self
+                    .inputs
+                    .iter()
+                    .map(|i| {
+                        if cube.contains(i) {
+                            Some(true)
+                        } else if cube.contains(&!*i) {
+                            Some(false)
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+                let unknown: Vec<usize> =
+                    (0..known.len()).filter(|j| known[*j].is_none()).collect();
+                let mut values: Vec<bool> = known.iter().map(|v| v.unwrap_or(false)).collect();
+                let mut results = (0..(1 << unknown.len())).map(|row: usize| {
+                    for (i, j) in unknown.iter().enumerate() {
+                        values[*j] = (row >> i) & 1 == 1;
+                    }
+                    self.function.evaluate(&values)
+                });
+                let first = results.next().unwrap();
+                if results.all(|r| r == first) {
+                    Some(x.negate_if_true(!first))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Returns clauses over the inputs whose conjunction is equivalent to the literal of the
+    /// variable with the given polarity.
+    pub fn get_clauses_equivalent_to_literal(&self, is_negated: bool) -> Vec<Clause> {
+        let inputs = self.inputs.peek().peek();
+        match (self.function, is_negated) {
+            (DefinitionFunction::And, false) => inputs
+                .iter()
+                .map(|l| Clause::from_ordered_set(vec![*l]))
+                .collect(),
+            (DefinitionFunction::And, true) => {
+                vec![Clause::from_sequence(inputs.iter().map(|l| !*l).collect())]
+            }
+            (DefinitionFunction::Ite(i), _) => {
+                // x = ite(c, a, b) <=> (!c \/ a) /\ (c \/ b)
+                let c = inputs[i as usize];
+                let mut branches = inputs.iter().filter(|l| **l != c);
+                let (a, b) = (*branches.next().unwrap(), *branches.next().unwrap());
+                let (a, b) = (a.negate_if_true(is_negated), b.negate_if_true(is_negated));
+                vec![
+                    Clause::from_sequence(vec![!c, a]),
+                    Clause::from_sequence(vec![c, b]),
+                ]
+            }
+            (DefinitionFunction::Maj3, _) => {
+                // x = maj(a, b, c) <=> (a \/ b) /\ (a \/ c) /\ (b \/ c)
+                let l: Vec<Literal> = inputs
+                    .iter()
+                    .map(|l| l.negate_if_true(is_negated))
+                    .collect();
+                vec![
+                    Clause::from_sequence(vec![l[0], l[1]]),
+                    Clause::from_sequence(vec![l[0], l[2]]),
+                    Clause::from_sequence(vec![l[1], l[2]]),
+                ]
+            }
+            (DefinitionFunction::Xor, _) | (DefinitionFunction::TruthTable(_), _) => {
+                self.get_clauses_of_truth_table_rows(!is_negated)
+            }
         }
     }
 
@@ -103,6 +200,21 @@ impl Definition {
                 cnf.sort_unstable();
                 cnf
             }
+            DefinitionFunction::Ite(_)
+            | DefinitionFunction::Maj3
+            | DefinitionFunction::TruthTable(_) => {
+                let mut cnf = vec![];
+                for is_negated in [false, true] {
+                    // x -> clause and !x -> clause
+                    let x = self.variable.literal(!is_negated);
+                    for mut clause in self.get_clauses_equivalent_to_literal(is_negated) {
+                        clause.insert(x);
+                        cnf.push(clause);
+                    }
+                }
+                cnf.sort_unstable();
+                cnf
+            }
         }
     }
 
@@ -128,8 +240,11 @@ impl Definition {
                     }
                 }
             }
-            DefinitionFunction::Xor => {
-                // you can't go forward with XOR
+            DefinitionFunction::Xor
+            | DefinitionFunction::Ite(_)
+            | DefinitionFunction::Maj3
+            | DefinitionFunction::TruthTable(_) => {
+                // you can't go forward with these functions
             }
         }
         Some(clause)
@@ -146,8 +261,11 @@ impl Definition {
                     }
                 }
             }
-            DefinitionFunction::Xor => {
-                // you can't go backward with XOR
+            DefinitionFunction::Xor
+            | DefinitionFunction::Ite(_)
+            | DefinitionFunction::Maj3
+            | DefinitionFunction::TruthTable(_) => {
+                // you can't go backward with these functions
             }
         }
 
@@ -155,8 +273,12 @@ impl Definition {
     }
 
     /// Returns true if the definition is valid, false otherwise.
-    /// A definition is valid if the variable is greater than the max variable in the inputs.
+    /// A definition is valid if the variable is greater than the max variable in the inputs, and
+    /// the function supports the number of inputs.
     pub fn is_valid(&self) -> bool {
         self.inputs.max_variable() < self.variable
+            && self
+                .function
+                .is_number_of_inputs_supported(self.inputs.len())
     }
 }
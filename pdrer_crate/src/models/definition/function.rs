@@ -0,0 +1,211 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use super::DefinitionFunction;
+use crate::{
+    formulas::Literal,
+    models::truth_table::{TruthTableEntry, TRUTH_TABLE_MAX_INPUTS},
+};
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl DefinitionFunction {
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Returns true if the function can be defined over this many inputs.
+    pub fn is_number_of_inputs_supported(&self, number_of_inputs: usize) -> bool {
+        match self {
+            DefinitionFunction::And | DefinitionFunction::Xor => number_of_inputs > 0,
+            DefinitionFunction::Ite(i) => number_of_inputs == 3 && *i < 3,
+            DefinitionFunction::Maj3 => number_of_inputs == 3,
+            DefinitionFunction::TruthTable(_) => {
+                number_of_inputs > 0 && number_of_inputs <= TRUTH_TABLE_MAX_INPUTS
+            }
+        }
+    }
+
+    /// Returns the result of the function when the inputs are assigned the given values.
+    pub fn evaluate(&self, values: &[bool]) -> bool {
+        debug_assert!(self.is_number_of_inputs_supported(values.len()));
+        match self {
+            DefinitionFunction::And => values.iter().all(|v| *v),
+            DefinitionFunction::Xor => values.iter().filter(|v| **v).count() % 2 == 1,
+            DefinitionFunction::Ite(i) => {
+                let i = *i as usize;
+                let mut branches = values
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, v)| *v);
+                let (then_value, else_value) = (branches.next().unwrap(), branches.next().unwrap());
+                if values[i] {
+                    then_value
+                } else {
+                    else_value
+                }
+            }
+            DefinitionFunction::Maj3 => values.iter().filter(|v| **v).count() >= 2,
+            DefinitionFunction::TruthTable(t) => {
+                let row = values
+                    .iter()
+                    .enumerate()
+                    .fold(0, |row, (j, v)| row | ((*v as usize) << j));
+                (t >> row) & 1 == 1
+            }
+        }
+    }
+
+    /// Returns the truth table of the function over this many inputs, in the format of
+    /// `DefinitionFunction::TruthTable`.
+    pub fn get_truth_table(&self, number_of_inputs: usize) -> TruthTableEntry {
+        debug_assert!(number_of_inputs <= TRUTH_TABLE_MAX_INPUTS);
+        let mut table = 0;
+        let mut values = vec![false; number_of_inputs];
+        for row in 0..(1 << number_of_inputs) {
+            for (j, v) in values.iter_mut().enumerate() {
+                *v = (row >> j) & 1 == 1;
+            }
+            if self.evaluate(&values) {
+                table |= 1 << row;
+            }
+        }
+        table
+    }
+
+    /// Definition inputs are kept sorted, this takes the inputs in the order the function expects
+    /// them, sorts them and returns a function that is equal to the original one over the sorted
+    /// inputs. The condition of an ITE may be negated so that the branches stay in order.
+    pub fn sort_inputs(self, mut inputs: Vec<Literal>) -> (Self, Vec<Literal>) {
+        debug_assert!(self.is_number_of_inputs_supported(inputs.len()));
+        match self {
+            DefinitionFunction::And | DefinitionFunction::Xor | DefinitionFunction::Maj3 => {
+                inputs.sort_unstable();
+                (self, inputs)
+            }
+            DefinitionFunction::Ite(i) => {
+                let mut condition = inputs.remove(i as usize);
+                if inputs[1] < inputs[0] {
+                    // ite(c, a, b) = ite(!c, b, a)
+                    inputs.swap(0, 1);
+                    condition = !condition;
+                }
+                inputs.push(condition);
+                inputs.sort_unstable();
+                let i = inputs.iter().position(|l| *l == condition).unwrap();
+                (DefinitionFunction::Ite(i as u8), inputs)
+            }
+            DefinitionFunction::TruthTable(_) => {
+                // sorted input i is the original input order[i]
+                let mut order: Vec<usize> = (0..inputs.len()).collect();
+                order.sort_by_key(|j| inputs[*j]);
+                let mut table = 0;
+                let mut values = vec![false; inputs.len()];
+                for row in 0..(1 << inputs.len()) {
+                    for (i, j) in order.iter().enumerate() {
+                        values[*j] = (row >> i) & 1 == 1;
+                    }
+                    if self.evaluate(&values) {
+                        table |= 1 << row;
+                    }
+                }
+                let inputs = order.iter().map(|j| inputs[*j]).collect();
+                (DefinitionFunction::TruthTable(table), inputs)
+            }
+        }
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[test]
+fn test_sort_inputs_keeps_function() {
+    use crate::formulas::Variable;
+
+    let a = Variable::new(1).literal(false);
+    let b = Variable::new(2).literal(true);
+    let c = Variable::new(3).literal(false);
+    let functions = [
+        DefinitionFunction::Ite(0),
+        DefinitionFunction::Ite(1),
+        DefinitionFunction::Ite(2),
+        DefinitionFunction::Maj3,
+        DefinitionFunction::TruthTable(0xD8),
+        DefinitionFunction::TruthTable(0x17),
+    ];
+    let evaluate = |f: DefinitionFunction, inputs: &[Literal], assignment: usize| {
+        let values: Vec<bool> = inputs
+            .iter()
+            .map(|l| ((assignment >> (l.variable().number() - 1)) & 1 == 1) ^ l.is_negated())
+            .collect();
+        f.evaluate(&values)
+    };
+    for f in functions {
+        for inputs in [[c, a, b], [b, c, a], [a, b, c]] {
+            let (sorted_f, sorted_inputs) = f.sort_inputs(inputs.to_vec());
+            assert!(sorted_inputs.windows(2).all(|w| w[0] < w[1]));
+            for assignment in 0..8 {
+                assert_eq!(
+                    evaluate(f, &inputs, assignment),
+                    evaluate(sorted_f, &sorted_inputs, assignment)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_definition_cnf_agrees_with_function() {
+    use crate::{
+        formulas::{Cube, Variable},
+        models::{Definition, SortedVecOfLiterals},
+    };
+
+    let inputs = vec![
+        Variable::new(1).literal(true),
+        Variable::new(2).literal(false),
+        Variable::new(3).literal(false),
+    ];
+    let x = Variable::new(4);
+    for function in [
+        DefinitionFunction::Ite(1),
+        DefinitionFunction::Maj3,
+        DefinitionFunction::TruthTable(0x96),
+    ] {
+        let d = Definition {
+            variable: x,
+            function,
+            inputs: SortedVecOfLiterals::from_ordered_set(inputs.clone()),
+        };
+        assert!(d.is_valid());
+        let cnf = d.to_cnf();
+        for assignment in 0..8 {
+            let values: Vec<bool> = inputs
+                .iter()
+                .enumerate()
+                .map(|(j, l)| ((assignment >> j) & 1 == 1) ^ l.is_negated())
+                .collect();
+            let state: Vec<Literal> = (0..3)
+                .map(|j| Variable::new(j + 1).literal((assignment >> j) & 1 == 0))
+                .collect();
+            let result = function.evaluate(&values);
+            let cube = Cube::from_sequence(state.clone());
+            assert_eq!(
+                d.get_ternary_result_of_definition_on_cube(&cube),
+                Some(x.literal(!result))
+            );
+            for value in [false, true] {
+                let mut full = state.clone();
+                full.push(x.literal(!value));
+                let is_satisfied = cnf.iter().all(|c| c.iter().any(|l| full.contains(l)));
+                assert_eq!(is_satisfied, value == result);
+            }
+        }
+    }
+}
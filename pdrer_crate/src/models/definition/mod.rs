@@ -5,9 +5,9 @@
 // use
 // ************************************************************************************************
 
-use super::SortedVecOfLiterals;
+use super::{truth_table::TruthTableEntry, SortedVecOfLiterals};
 use crate::formulas::Variable;
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 // ************************************************************************************************
 // Definition function
@@ -17,6 +17,14 @@ use std::fmt::Display;
 pub enum DefinitionFunction {
     And,
     Xor,
+    /// If-then-else of 3 inputs, the input at this index is the condition, the first of the other
+    /// two inputs is the result when the condition is true and the second is the result otherwise.
+    Ite(u8),
+    /// Majority of 3 inputs.
+    Maj3,
+    /// Any function of at most `TRUTH_TABLE_MAX_INPUTS` inputs, bit `i` of the table is the result
+    /// when every input `j` is assigned bit `j` of `i`.
+    TruthTable(TruthTableEntry),
 }
 
 impl Display for DefinitionFunction {
@@ -24,6 +32,36 @@ impl Display for DefinitionFunction {
         match self {
             DefinitionFunction::And => write!(f, "AND"),
             DefinitionFunction::Xor => write!(f, "XOR"),
+            DefinitionFunction::Ite(i) => write!(f, "ITE{}", i),
+            DefinitionFunction::Maj3 => write!(f, "MAJ3"),
+            DefinitionFunction::TruthTable(t) => write!(f, "TT{:x}", t),
+        }
+    }
+}
+
+impl FromStr for DefinitionFunction {
+    type Err = ();
+
+    /// Parses the format that is written by `Display`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "AND" => Ok(DefinitionFunction::And),
+            "XOR" => Ok(DefinitionFunction::Xor),
+            "MAJ3" => Ok(DefinitionFunction::Maj3),
+            _ => {
+                if let Some(i) = s.strip_prefix("ITE") {
+                    match i.parse() {
+                        Ok(i) if i < 3 => Ok(DefinitionFunction::Ite(i)),
+                        _ => Err(()),
+                    }
+                } else if let Some(t) = s.strip_prefix("TT") {
+                    TruthTableEntry::from_str_radix(t, 16)
+                        .map(DefinitionFunction::TruthTable)
+                        .map_err(|_| ())
+                } else {
+                    Err(())
+                }
+            }
         }
     }
 }
@@ -44,6 +82,7 @@ pub struct Definition {
 // ************************************************************************************************
 
 pub mod api;
+pub mod function;
 
 // ************************************************************************************************
 // re-exports of structs in these modules to simplify paths for other imports
@@ -4,14 +4,16 @@
 
 // use std::collections::FxHashSet;
 
-use super::{FiniteStateTransitionSystem, FiniteStateTransitionSystemError};
+use super::encoding::SignalEncoding;
+use super::{
+    FiniteStateTransitionSystem, FiniteStateTransitionSystemError, TransitionRelationEncoding,
+};
 use crate::formulas::{Clause, Cube, CNF};
 use crate::formulas::{Literal, Variable};
-use crate::models::circuit::node_types::{
-    CircuitAnd, CircuitGenericGate, CircuitLatch, CircuitNode, CircuitNodeType,
-};
+use crate::models::circuit::node_types::{CircuitLatch, CircuitNode, CircuitNodeType};
 use crate::models::circuit::Circuit;
 use crate::models::truth_table::cnf::CNFCache;
+use crate::models::truth_table::TRUTH_TABLE_MAX_INPUTS;
 use crate::models::{
     CircuitSimulator, Signal, SortedVecOfLiterals, TernaryValue, TruthTable, UniqueSortedHashMap,
     UniqueSortedVec, Wire,
@@ -124,6 +126,22 @@ impl FiniteStateTransitionSystem {
         clauses
     }
 
+    fn describe_look_up_table(
+        &self,
+        signal: Signal,
+        truth_table: &TruthTable,
+        cache: &mut CNFCache,
+    ) -> Vec<Clause> {
+        let lhs = self.convert_wire_to_literal(&signal.wire(false));
+        if truth_table.is_constant_0() {
+            vec![Clause::from_ordered_set(vec![!lhs])]
+        } else if truth_table.is_constant_1() {
+            vec![Clause::from_ordered_set(vec![lhs])]
+        } else {
+            self.describe_generic_gate(signal, truth_table, cache)
+        }
+    }
+
     fn describe_generic_gate(
         &self,
         signal: Signal,
@@ -209,36 +227,36 @@ impl FiniteStateTransitionSystem {
     // handle gate
     // ********************************************************************************************
 
-    fn handle_gate(&mut self, signal: Signal, node: &CircuitNode, cache: &mut CNFCache) {
-        // get variable
-        // let variable = self.convert_signal_to_variable(*signal);
-        // update range
-
-        // call appropriate handler
-        match &node.node_type {
-            CircuitNodeType::And(a) => self.handle_and_gate(signal, a),
-            CircuitNodeType::GenericGate(g) => self.handle_generic_gate(signal, g, cache),
-            _ => unreachable!(),
-        }
-    }
-
-    fn handle_and_gate(&mut self, signal: Signal, a: &CircuitAnd) {
-        // add to description
-        let variable = self.convert_signal_to_variable(signal);
-        let mut description = self.describe_and_gate_literal(signal, a.inputs.peek());
-        description.sort_unstable();
-        self.variable_definitions.insert(variable, description);
-    }
-
-    fn handle_generic_gate(
+    fn handle_gate(
         &mut self,
         signal: Signal,
-        g: &CircuitGenericGate,
+        node: &CircuitNode,
+        encoding: &SignalEncoding,
         cache: &mut CNFCache,
     ) {
-        // add to description
+        // get variable
         let variable = self.convert_signal_to_variable(signal);
-        let mut description = self.describe_generic_gate(signal, &g.truth_table, cache);
+
+        // call appropriate describer
+        let full_description = |s: &Self, cache: &mut CNFCache| match &node.node_type {
+            CircuitNodeType::And(a) => s.describe_and_gate_literal(signal, a.inputs.peek()),
+            CircuitNodeType::GenericGate(g) => {
+                s.describe_generic_gate(signal, &g.truth_table, cache)
+            }
+            _ => unreachable!(),
+        };
+        let mut description = match encoding {
+            SignalEncoding::Full => full_description(self, cache),
+            SignalEncoding::OnlyWhen(value) => {
+                Self::keep_clauses_of_polarity(variable, *value, full_description(self, cache))
+            }
+            SignalEncoding::Lut(truth_table) => {
+                self.describe_look_up_table(signal, truth_table, cache)
+            }
+            SignalEncoding::Removed => return,
+        };
+
+        // add to description
         description.sort_unstable();
         self.variable_definitions.insert(variable, description);
     }
@@ -247,7 +265,11 @@ impl FiniteStateTransitionSystem {
     // single pass
     // ********************************************************************************************
 
-    fn perform_single_pass_on_circuit(&mut self, circuit: &Circuit, _assume_output_is_bad: bool) {
+    fn perform_single_pass_on_circuit(
+        &mut self,
+        circuit: &Circuit,
+        encoding_of_each_gate: &UniqueSortedHashMap<Signal, SignalEncoding>,
+    ) {
         // a hash table that defines each gate variable
         let mut initial_vector: Vec<Literal> =
             Vec::with_capacity(circuit.get_latch_signals().len());
@@ -265,8 +287,10 @@ impl FiniteStateTransitionSystem {
                 CircuitNodeType::Latch(l) => {
                     self.handle_latch(signal, l, &mut initial_vector, &mut transition_on_internals)
                 }
-                CircuitNodeType::And(a) => self.handle_and_gate(signal, a),
-                CircuitNodeType::GenericGate(_) => self.handle_gate(signal, node, &mut cnf_cache),
+                CircuitNodeType::And(_) | CircuitNodeType::GenericGate(_) => {
+                    let encoding = encoding_of_each_gate.get(&signal).unwrap();
+                    self.handle_gate(signal, node, encoding, &mut cnf_cache)
+                }
             }
         }
 
@@ -281,7 +305,10 @@ impl FiniteStateTransitionSystem {
     // post processing
     // ********************************************************************************************
 
-    fn get_bad_wires(circuit: &Circuit, assume_output_is_bad: bool) -> UniqueSortedVec<Wire> {
+    pub(super) fn get_bad_wires(
+        circuit: &Circuit,
+        assume_output_is_bad: bool,
+    ) -> UniqueSortedVec<Wire> {
         let mut important_wires = circuit.get_bad_wires().to_owned();
         if assume_output_is_bad {
             important_wires = important_wires.merge(circuit.get_output_wires());
@@ -344,8 +371,10 @@ impl FiniteStateTransitionSystem {
             let variable = self.convert_signal_to_variable(*signal);
             // skip latches, inputs and ground
 
-            let description_of_signal = self.variable_definitions.get(&variable).unwrap();
-            clauses.append(&mut description_of_signal.to_vec())
+            // gates that were removed by the encoding have no description
+            if let Some(description_of_signal) = self.variable_definitions.get(&variable) {
+                clauses.append(&mut description_of_signal.to_vec())
+            }
         }
         CNF::from_ordered_set(UniqueSortedVec::from_ordered_set(clauses))
     }
@@ -396,6 +425,7 @@ impl FiniteStateTransitionSystem {
         &mut self,
         circuit: &Circuit,
         assume_output_is_bad: bool,
+        encoding_of_each_gate: &UniqueSortedHashMap<Signal, SignalEncoding>,
     ) -> Result<(), FiniteStateTransitionSystemError> {
         // get bad and constraint wires
         let bad_wires = Self::get_bad_wires(circuit, assume_output_is_bad);
@@ -488,6 +518,7 @@ impl FiniteStateTransitionSystem {
             let mut a = cone_of_property.to_owned();
             a.append(&mut cone_of_invariant);
             a.append(&mut cone_of_transition);
+            a.retain(|s| !matches!(encoding_of_each_gate.get(s), Some(SignalEncoding::Removed)));
             UniqueSortedVec::from_sequence(a)
         };
 
@@ -510,6 +541,20 @@ impl FiniteStateTransitionSystem {
     pub fn new(
         circuit: &Circuit,
         assume_output_is_bad: bool,
+    ) -> Result<Self, FiniteStateTransitionSystemError> {
+        Self::new_with_encoding(
+            circuit,
+            assume_output_is_bad,
+            TransitionRelationEncoding::DEFAULT,
+        )
+    }
+
+    /// Creates the finite state transition system where the gates of the circuit are described
+    /// using the provided encoding.
+    pub fn new_with_encoding(
+        circuit: &Circuit,
+        assume_output_is_bad: bool,
+        encoding: TransitionRelationEncoding,
     ) -> Result<Self, FiniteStateTransitionSystemError> {
         // perform some checks first
         let max_wire_in_circuit = circuit.get_highest_signal().wire(false);
@@ -517,6 +562,8 @@ impl FiniteStateTransitionSystem {
             return Err(FiniteStateTransitionSystemError::MaxWireTooHigh);
         }
         Self::check_if_problem_is_trivial(circuit, assume_output_is_bad)?;
+        let encoding_of_each_gate =
+            Self::get_encoding_of_each_gate(circuit, assume_output_is_bad, encoding)?;
 
         let signal_to_variable = |s: Signal| -> Variable { Variable::new(s.number()) };
         let variable_to_signal = |v: Variable| -> Signal { Signal::new(v.number()) };
@@ -550,6 +597,7 @@ impl FiniteStateTransitionSystem {
             transition_on_internals: CNF::new(),
 
             // some meta data
+            encoding,
             max_variable,
             variable_definitions: UniqueSortedHashMap::new(max_variable),
 
@@ -574,13 +622,13 @@ impl FiniteStateTransitionSystem {
 
         // println!("Memory usage 4.2: {} MB", get_memory_usage());
 
-        result.perform_single_pass_on_circuit(circuit, assume_output_is_bad);
+        result.perform_single_pass_on_circuit(circuit, &encoding_of_each_gate);
 
         // println!("Memory usage 4.3: {} MB", get_memory_usage());
 
         // Consume circuit
 
-        result.post_processing(circuit, assume_output_is_bad)?;
+        result.post_processing(circuit, assume_output_is_bad, &encoding_of_each_gate)?;
 
         // println!("Memory usage 4.4: {} MB", get_memory_usage());
 
@@ -661,6 +709,13 @@ impl fmt::Display for FiniteStateTransitionSystemError {
                     "Bad wires include x and also !x, and thus the property is always violated (probably unsafe or sat), this model could still be safe if there are constraints that are never satisfied."
                 )
             }
+            FiniteStateTransitionSystemError::UnsupportedLutSize => {
+                write!(
+                    f,
+                    "LUT encoding requires between 2 and {} inputs per LUT and at least 1 cut per gate.",
+                    TRUTH_TABLE_MAX_INPUTS
+                )
+            }
         }
     }
 }
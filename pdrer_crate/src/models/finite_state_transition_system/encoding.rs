@@ -0,0 +1,418 @@
+//! Encodings that decide which clauses describe each gate of the circuit in the CNFs of the finite
+//! state transition system.
+
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::fmt;
+
+use super::{FiniteStateTransitionSystem, FiniteStateTransitionSystemError};
+use crate::formulas::{Clause, Variable};
+use crate::models::circuit::node_types::CircuitNodeType;
+use crate::models::circuit::simplifiers::CircuitTechnologyMapper;
+use crate::models::truth_table::TRUTH_TABLE_MAX_INPUTS;
+use crate::models::{Circuit, Signal, TruthTable, UniqueSortedHashMap, Wire};
+
+// ************************************************************************************************
+// TransitionRelationEncoding
+// ************************************************************************************************
+
+/// The encoding used to describe the gates of the circuit in the transition relation, the
+/// property and the invariant constraints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionRelationEncoding {
+    /// Each gate is described by all the clauses of its definition.
+    Tseitin,
+    /// Each gate is described only by the clauses of the polarities it is used in, gates that
+    /// are used in both polarities are described by all the clauses of their definition.
+    PlaistedGreenbaum,
+    /// The circuit is covered by look up tables of at most `k` inputs that are chosen using area
+    /// flow over the `l` best cuts of each gate, only the outputs of the look up tables get a
+    /// definition.
+    Lut { k: usize, l: usize },
+}
+
+impl TransitionRelationEncoding {
+    pub const DEFAULT: Self = Self::Tseitin;
+}
+
+impl Default for TransitionRelationEncoding {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl fmt::Display for TransitionRelationEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransitionRelationEncoding::Tseitin => write!(f, "Tseitin"),
+            TransitionRelationEncoding::PlaistedGreenbaum => write!(f, "Plaisted-Greenbaum"),
+            TransitionRelationEncoding::Lut { k, l } => write!(f, "LUT (k = {}, l = {})", k, l),
+        }
+    }
+}
+
+// ************************************************************************************************
+// SignalEncoding
+// ************************************************************************************************
+
+/// How a single gate is described.
+#[derive(Debug, Clone)]
+pub(super) enum SignalEncoding {
+    /// All the clauses of the definition of the gate.
+    Full,
+    /// Only the clauses that constrain the gate when it has the given value.
+    OnlyWhen(bool),
+    /// The definition of the truth table of the gate over the signals of its chosen cut.
+    Lut(TruthTable),
+    /// The gate is not described at all.
+    Removed,
+}
+
+// ************************************************************************************************
+// FiniteStateTransitionSystemEncodingStats
+// ************************************************************************************************
+
+/// Sizes of the CNFs that were created when constructing the finite state transition system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FiniteStateTransitionSystemEncodingStats {
+    pub encoding: TransitionRelationEncoding,
+    pub defined_variables: usize,
+    pub transition_clauses: usize,
+    pub transition_variables: usize,
+    pub property_clauses: usize,
+    pub property_variables: usize,
+    pub invariant_constraint_clauses: usize,
+    pub invariant_constraint_variables: usize,
+}
+
+impl fmt::Display for FiniteStateTransitionSystemEncodingStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Encoding = {}, defined variables = {}, Tr = {} clauses over {} variables, P = {} clauses over {} variables, C = {} clauses over {} variables",
+            self.encoding,
+            self.defined_variables,
+            self.transition_clauses,
+            self.transition_variables,
+            self.property_clauses,
+            self.property_variables,
+            self.invariant_constraint_clauses,
+            self.invariant_constraint_variables
+        )
+    }
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl FiniteStateTransitionSystem {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn add_polarity(
+        polarities: &mut UniqueSortedHashMap<Signal, (bool, bool)>,
+        wire: &Wire,
+        positive: bool,
+        negative: bool,
+    ) {
+        let (positive, negative) = if wire.is_negated() {
+            (negative, positive)
+        } else {
+            (positive, negative)
+        };
+        let p = polarities
+            .get_mut_or_add(&wire.signal(), || (false, false))
+            .unwrap();
+        p.0 |= positive;
+        p.1 |= negative;
+    }
+
+    /// Returns for each gate whether its value is required to imply its definition when it is
+    /// true and when it is false.
+    /// The latch inputs and the property are used in both polarities, while the invariant
+    /// constraints are only ever asserted.
+    fn get_polarity_of_each_gate(
+        circuit: &Circuit,
+        assume_output_is_bad: bool,
+    ) -> UniqueSortedHashMap<Signal, (bool, bool)> {
+        let mut polarities = UniqueSortedHashMap::new(circuit.get_highest_signal());
+        for latch in circuit.get_latch_signals().iter() {
+            if let CircuitNodeType::Latch(l) = &circuit.get_node(latch).unwrap().node_type {
+                Self::add_polarity(&mut polarities, &l.input, true, true);
+            }
+        }
+        for wire in Self::get_bad_wires(circuit, assume_output_is_bad).iter() {
+            Self::add_polarity(&mut polarities, wire, true, true);
+        }
+        for wire in circuit.get_invariant_constraint_wires().iter() {
+            Self::add_polarity(&mut polarities, wire, true, false);
+        }
+
+        // users always have a greater signal than the gates that feed them
+        let signals: Vec<Signal> = circuit.iter_sorted().collect();
+        for signal in signals.into_iter().rev() {
+            let (positive, negative) = match polarities.get(&signal) {
+                Some(p) => *p,
+                None => continue,
+            };
+            match &circuit.get_node(&signal).unwrap().node_type {
+                CircuitNodeType::And(a) => {
+                    for wire in a.inputs.iter() {
+                        Self::add_polarity(&mut polarities, wire, positive, negative);
+                    }
+                }
+                CircuitNodeType::GenericGate(g) => {
+                    for input in g.truth_table.get_signals().iter() {
+                        Self::add_polarity(&mut polarities, &input.wire(false), true, true);
+                    }
+                }
+                _ => {}
+            }
+        }
+        polarities
+    }
+
+    fn get_plaisted_greenbaum_encoding(
+        circuit: &Circuit,
+        assume_output_is_bad: bool,
+    ) -> UniqueSortedHashMap<Signal, SignalEncoding> {
+        let polarities = Self::get_polarity_of_each_gate(circuit, assume_output_is_bad);
+        let mut result = UniqueSortedHashMap::new(circuit.get_highest_signal());
+        for signal in circuit.get_gate_signals().iter() {
+            let encoding = match polarities.get(signal) {
+                Some((true, false)) => SignalEncoding::OnlyWhen(true),
+                Some((false, true)) => SignalEncoding::OnlyWhen(false),
+                _ => SignalEncoding::Full,
+            };
+            result.insert(*signal, encoding);
+        }
+        result
+    }
+
+    fn get_lut_encoding(
+        circuit: &Circuit,
+        k: usize,
+        l: usize,
+    ) -> UniqueSortedHashMap<Signal, SignalEncoding> {
+        // enumerate cuts and calculate their truth tables
+        let refs = circuit.get_number_of_references();
+        let mut cut_function = circuit.enumerate_k_feasible_cuts_leaving_only_l_best(
+            k,
+            l,
+            |cut| circuit.cut_cost_function_used_by_abc(cut, k, &refs),
+            false,
+        );
+        for signal in circuit.iter_sorted() {
+            for csi in cut_function.get_mut(&signal).unwrap().iter_mut() {
+                csi.truth_table = Some(CircuitTechnologyMapper::calculate_truth_table_for_cut(
+                    circuit, &signal, &csi.cut,
+                ));
+            }
+        }
+
+        // choose cut per signal
+        let cut_for_each_signal = circuit.choose_cut_for_each_signal_using_area_flow(&cut_function);
+        let mut truth_table_for_each_signal = UniqueSortedHashMap::new_like(&cut_for_each_signal);
+        let mut leaves_of_each_signal = UniqueSortedHashMap::new_like(&cut_for_each_signal);
+        for signal in cut_for_each_signal.iter_sorted() {
+            let tt = cut_for_each_signal
+                .get(&signal)
+                .unwrap()
+                .truth_table
+                .to_owned();
+            let tt = tt.unwrap();
+            leaves_of_each_signal.insert(signal, tt.get_signals().to_owned());
+            truth_table_for_each_signal.insert(signal, tt);
+        }
+        let signals_to_keep = CircuitTechnologyMapper::get_signals_to_keep_from_chosen_cuts(
+            circuit,
+            &leaves_of_each_signal,
+        );
+
+        let mut result = UniqueSortedHashMap::new(circuit.get_highest_signal());
+        for signal in circuit.get_gate_signals().iter() {
+            let encoding = if !signals_to_keep.contains(signal) {
+                SignalEncoding::Removed
+            } else {
+                let tt = truth_table_for_each_signal.get(signal).unwrap();
+                if tt.get_signals().peek() == &[*signal] {
+                    // unit cut, the gate is described as it is
+                    SignalEncoding::Full
+                } else {
+                    SignalEncoding::Lut(tt.to_owned())
+                }
+            };
+            result.insert(*signal, encoding);
+        }
+        result
+    }
+
+    /// Returns the encoding of each gate in the circuit.
+    pub(super) fn get_encoding_of_each_gate(
+        circuit: &Circuit,
+        assume_output_is_bad: bool,
+        encoding: TransitionRelationEncoding,
+    ) -> Result<UniqueSortedHashMap<Signal, SignalEncoding>, FiniteStateTransitionSystemError> {
+        match encoding {
+            TransitionRelationEncoding::Tseitin => {
+                let mut result = UniqueSortedHashMap::new(circuit.get_highest_signal());
+                for signal in circuit.get_gate_signals().iter() {
+                    result.insert(*signal, SignalEncoding::Full);
+                }
+                Ok(result)
+            }
+            TransitionRelationEncoding::PlaistedGreenbaum => Ok(
+                Self::get_plaisted_greenbaum_encoding(circuit, assume_output_is_bad),
+            ),
+            TransitionRelationEncoding::Lut { k, l } => {
+                if !(2..=TRUTH_TABLE_MAX_INPUTS).contains(&k) || l == 0 {
+                    return Err(FiniteStateTransitionSystemError::UnsupportedLutSize);
+                }
+                Ok(Self::get_lut_encoding(circuit, k, l))
+            }
+        }
+    }
+
+    /// Keeps only the clauses of the definition of `variable` that are needed when the variable
+    /// has the given value, these are the clauses that contain the opposite literal.
+    pub(super) fn keep_clauses_of_polarity(
+        variable: Variable,
+        value: bool,
+        description: Vec<Clause>,
+    ) -> Vec<Clause> {
+        let needed = variable.literal(value);
+        description
+            .into_iter()
+            .filter(|c| c.iter().all(|l| l.variable() != variable) || c.contains(&needed))
+            .collect()
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    pub fn get_encoding(&self) -> TransitionRelationEncoding {
+        self.encoding
+    }
+
+    /// Returns the sizes of the transition relation, property and invariant constraint CNFs in
+    /// the encoding chosen at construction.
+    pub fn get_encoding_stats(&self) -> FiniteStateTransitionSystemEncodingStats {
+        let transition = self.construct_transition_cnf(false, false, false, false);
+        let mut property = self.get_property_connector().to_owned();
+        property.append(self.get_property_on_internals().to_cnf());
+        let mut invariant_constraint = self.get_invariant_constraints_connector().to_owned();
+        invariant_constraint.append(self.get_invariant_constraints_on_internals().to_cnf());
+        FiniteStateTransitionSystemEncodingStats {
+            encoding: self.encoding,
+            defined_variables: self.variable_definitions.len(),
+            transition_clauses: transition.len(),
+            transition_variables: transition.get_variables().len(),
+            property_clauses: property.len(),
+            property_variables: property.get_variables().len(),
+            invariant_constraint_clauses: invariant_constraint.len(),
+            invariant_constraint_variables: invariant_constraint.get_variables().len(),
+        }
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[test]
+fn test_encodings_agree_on_result() {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        engines::pdr::{PropertyDirectedReachability, PropertyDirectedReachabilityParameters},
+        models::{AndInverterGraph, Signal, TernaryValue},
+        solvers::{dd::OxiddBdd, sat::incremental::CaDiCalSolver},
+    };
+
+    // 2 latches that are flipped together by an input, 6 = (2 == 1) and 9 = (3 == 1).
+    let aig = |bad: Wire, constraint: Wire| {
+        AndInverterGraph::new(
+            Signal::new(10),
+            1,
+            &[
+                (Signal::new(6).wire(true), TernaryValue::False),
+                (Signal::new(9).wire(true), TernaryValue::False),
+            ],
+            vec![],
+            vec![bad],
+            vec![constraint],
+            &[
+                (Signal::new(1).wire(false), Signal::new(2).wire(true)),
+                (Signal::new(1).wire(true), Signal::new(2).wire(false)),
+                (Signal::new(4).wire(true), Signal::new(5).wire(true)),
+                (Signal::new(1).wire(false), Signal::new(3).wire(true)),
+                (Signal::new(1).wire(true), Signal::new(3).wire(false)),
+                (Signal::new(7).wire(true), Signal::new(8).wire(true)),
+                (Signal::new(2).wire(false), Signal::new(3).wire(true)),
+            ],
+            String::new(),
+        )
+        .unwrap()
+    };
+    let encodings = [
+        TransitionRelationEncoding::Tseitin,
+        TransitionRelationEncoding::PlaistedGreenbaum,
+        TransitionRelationEncoding::Lut { k: 4, l: 8 },
+        TransitionRelationEncoding::Lut { k: 2, l: 4 },
+    ];
+
+    // the latches stay equal, unless the constraint does not hold, and become true after a flip.
+    let cases = [
+        (
+            Signal::new(10).wire(false),
+            Signal::new(1).wire(false),
+            true,
+        ),
+        (
+            Signal::new(2).wire(false),
+            Signal::new(10).wire(true),
+            false,
+        ),
+        (Signal::new(10).wire(false), Signal::new(6).wire(true), true),
+    ];
+    for (bad, constraint, is_safe) in cases {
+        for detect_generic_patterns in [false, true] {
+            let mut circuit = Circuit::from_aig(&aig(bad, constraint));
+            if detect_generic_patterns {
+                circuit.detect_generic_patterns();
+            }
+            let tseitin = FiniteStateTransitionSystem::new(&circuit, false).unwrap();
+            for encoding in encodings {
+                let fin_state =
+                    FiniteStateTransitionSystem::new_with_encoding(&circuit, false, encoding)
+                        .unwrap();
+                let stats = fin_state.get_encoding_stats();
+                assert_eq!(stats.encoding, encoding);
+                assert!(
+                    stats.transition_clauses <= tseitin.get_encoding_stats().transition_clauses
+                );
+
+                let fin_state = Rc::new(RefCell::new(fin_state));
+                let mut pdr = PropertyDirectedReachability::<CaDiCalSolver, OxiddBdd>::new(
+                    fin_state.to_owned(),
+                    PropertyDirectedReachabilityParameters::new(),
+                )
+                .unwrap();
+                let result = pdr.prove().unwrap();
+                assert_eq!(result.is_ok(), is_safe, "{}", encoding);
+                match result {
+                    Ok(proof) => assert!(tseitin.check_proof::<CaDiCalSolver>(&proof).is_ok()),
+                    Err(ctx) => assert!(fin_state
+                        .borrow_mut()
+                        .check_counter_example(ctx, false)
+                        .is_ok()),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,288 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use fxhash::{FxHashMap, FxHashSet};
+
+use super::FiniteStateTransitionSystem;
+use crate::{
+    formulas::{Clause, Literal, Variable},
+    models::{
+        definition::DefinitionFunction,
+        truth_table::{TruthTableEntry, TRUTH_TABLE_MAX_INPUTS},
+        Definition, SortedVecOfLiterals,
+    },
+};
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl FiniteStateTransitionSystem {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    /// Reads the function of an internal variable from the clauses that describe it.
+    /// Returns the function and its inputs, `None` if the variable is constant or if the function
+    /// is not an AND gate and has too many inputs.
+    fn get_function_of_internal_variable(
+        variable: Variable,
+        clauses: &[Clause],
+    ) -> Option<(DefinitionFunction, Vec<Literal>)> {
+        let mut inputs: Vec<Variable> = clauses
+            .iter()
+            .flat_map(|c| c.iter())
+            .map(|l| l.variable())
+            .filter(|v| *v != variable)
+            .collect();
+        inputs.sort_unstable();
+        inputs.dedup();
+        if inputs.is_empty() {
+            return None;
+        }
+
+        // x = a ^ b ^ ... <=> (!x \/ a) ^ (!x \/ b) ^ ... ^ (x \/ !a \/ !b \/ ...)
+        let x = variable.literal(false);
+        let (positive, negative): (Vec<&Clause>, Vec<&Clause>) =
+            clauses.iter().partition(|c| c.contains(&x));
+        if positive.len() == 1
+            && negative.len() == inputs.len()
+            && negative.iter().all(|c| c.len() == 2 && c.contains(&!x))
+        {
+            let and_inputs: Vec<Literal> = negative
+                .iter()
+                .map(|c| *c.iter().find(|l| **l != !x).unwrap())
+                .collect();
+            if positive[0].len() == inputs.len() + 1
+                && and_inputs.iter().all(|l| positive[0].contains(&!*l))
+            {
+                return Some((DefinitionFunction::And, and_inputs));
+            }
+        }
+
+        if inputs.len() > TRUTH_TABLE_MAX_INPUTS {
+            return None;
+        }
+
+        // the clauses must allow exactly one value of the variable for each input assignment
+        let mut table = 0;
+        let mut values: FxHashMap<Variable, bool> = FxHashMap::default();
+        for row in 0..(1 << inputs.len()) {
+            for (j, v) in inputs.iter().enumerate() {
+                values.insert(*v, (row >> j) & 1 == 1);
+            }
+            let mut allowed = [false, true].into_iter().filter(|value| {
+                values.insert(variable, *value);
+                clauses
+                    .iter()
+                    .all(|c| c.iter().any(|l| values[&l.variable()] != l.is_negated()))
+            });
+            match (allowed.next(), allowed.next()) {
+                (Some(value), None) => table |= (value as TruthTableEntry) << row,
+                _ => return None,
+            }
+        }
+        let inputs = inputs.iter().map(|v| v.literal(false)).collect();
+        Some((DefinitionFunction::TruthTable(table), inputs))
+    }
+
+    /// Returns the internal variables whose value is used by something that is not a function of
+    /// the state variables alone, these are the boundary between the state logic and the rest.
+    fn get_roots_of_state_logic(
+        &self,
+        state_logic: &FxHashMap<Variable, Literal>,
+    ) -> Vec<Variable> {
+        let mut used: Vec<Variable> = vec![];
+        for (v, clauses) in self.variable_definitions.iter_pairs() {
+            if state_logic.contains_key(&v) {
+                continue;
+            }
+            used.extend(clauses.iter().flat_map(|c| c.iter()).map(|l| l.variable()));
+        }
+        used.extend(
+            self.state_variable_to_its_internal_signal_variable
+                .iter_items()
+                .map(|(v, _)| *v),
+        );
+        used.extend(self.property_on_internals.iter().map(|l| l.variable()));
+        used.extend(
+            self.invariant_constraints_on_internals
+                .iter()
+                .map(|l| l.variable()),
+        );
+
+        let mut roots: Vec<Variable> = used
+            .into_iter()
+            .filter_map(|v| state_logic.get(&v))
+            .map(|l| l.variable())
+            .filter(|v| !self.is_state_variable(*v))
+            .collect();
+        roots.sort_unstable();
+        roots.dedup();
+        roots
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Returns definitions of the internal signals that are functions of the current state
+    /// variables alone, such as comparators and decoders over the latches.
+    ///
+    /// Only the signals on the boundary of the state logic are chosen, that is signals that are
+    /// used by the rest of the circuit, the latches or the property. Each chosen signal is
+    /// returned with all the signals in its cone, and only if its cone has at most
+    /// `max_cone_size` definitions.
+    /// The variable of each definition is the internal variable, its inputs are state literals or
+    /// variables of definitions that come before it.
+    pub fn get_definitions_of_internal_signals_over_state_variables(
+        &self,
+        max_cone_size: usize,
+    ) -> Vec<Definition> {
+        // each internal variable that is a function of the state variables is mapped to an
+        // equivalent literal, either a state literal or the variable of its own definition.
+        let mut state_logic: FxHashMap<Variable, Literal> = FxHashMap::default();
+        for v in self.state_variables.iter() {
+            state_logic.insert(*v, v.literal(false));
+        }
+        let mut definitions: FxHashMap<Variable, Definition> = FxHashMap::default();
+        for (v, clauses) in self.variable_definitions.iter_pairs() {
+            let (function, inputs) = match Self::get_function_of_internal_variable(v, clauses) {
+                Some(r) => r,
+                None => continue,
+            };
+            let inputs: Option<Vec<Literal>> = inputs
+                .iter()
+                .map(|l| {
+                    state_logic
+                        .get(&l.variable())
+                        .map(|x| x.negate_if_true(l.is_negated()))
+                })
+                .collect();
+            let inputs = match inputs {
+                Some(i) => i,
+                None => continue,
+            };
+            if inputs.len() == 1 {
+                // a buffer or an inverter
+                if function.evaluate(&[false]) == function.evaluate(&[true]) {
+                    continue;
+                }
+                let l = match function {
+                    DefinitionFunction::And => inputs[0],
+                    _ => inputs[0].negate_if_true(!function.evaluate(&[true])),
+                };
+                state_logic.insert(v, l);
+                continue;
+            }
+            let (function, inputs) = function.sort_inputs(inputs);
+            if !SortedVecOfLiterals::are_variables_sorted_and_unique(&inputs) {
+                continue;
+            }
+            if function != DefinitionFunction::And {
+                let table = function.get_truth_table(inputs.len());
+                let all_rows =
+                    TruthTableEntry::MAX >> (TruthTableEntry::BITS as usize - (1 << inputs.len()));
+                if table == 0 || table == all_rows {
+                    // the signal is constant
+                    continue;
+                }
+            }
+            state_logic.insert(v, v.literal(false));
+            definitions.insert(
+                v,
+                Definition {
+                    variable: v,
+                    function,
+                    inputs: SortedVecOfLiterals::from_ordered_set(inputs),
+                },
+            );
+        }
+
+        let mut chosen: FxHashSet<Variable> = FxHashSet::default();
+        for root in self.get_roots_of_state_logic(&state_logic) {
+            let mut cone: FxHashSet<Variable> = FxHashSet::default();
+            let mut stack = vec![root];
+            while let Some(v) = stack.pop() {
+                if cone.len() > max_cone_size {
+                    break;
+                }
+                if let Some(d) = definitions.get(&v) {
+                    if cone.insert(v) {
+                        stack.extend(d.inputs.iter().map(|l| l.variable()));
+                    }
+                }
+            }
+            if cone.len() <= max_cone_size {
+                chosen.extend(cone);
+            }
+        }
+
+        let mut result: Vec<Definition> = chosen
+            .into_iter()
+            .map(|v| definitions.remove(&v).unwrap())
+            .collect();
+        result.sort_unstable_by_key(|d| d.variable);
+        result
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[test]
+fn test_definitions_of_internal_signals() {
+    use crate::models::{AndInverterGraph, Circuit, Signal, TernaryValue};
+
+    // 1 input, 2 latches, 4 = (2 ^ 3), 5 = (!4 ^ 1), 6 = !(!2 ^ !3)
+    let aig = AndInverterGraph::new(
+        Signal::new(6),
+        1,
+        &[
+            (Signal::new(5).wire(false), TernaryValue::False),
+            (Signal::new(6).wire(true), TernaryValue::False),
+        ],
+        vec![],
+        vec![Signal::new(5).wire(false)],
+        vec![],
+        &[
+            (Signal::new(2).wire(false), Signal::new(3).wire(false)),
+            (Signal::new(4).wire(true), Signal::new(1).wire(false)),
+            (Signal::new(2).wire(true), Signal::new(3).wire(true)),
+        ],
+        String::new(),
+    )
+    .unwrap();
+    let circuit = Circuit::from_aig(&aig);
+    let fin_state = FiniteStateTransitionSystem::new(&circuit, false).unwrap();
+    let v = |i: u32| fin_state.convert_signal_to_variable(Signal::new(i));
+
+    // 4 is used by 5 which depends on the input, 6 is used by a latch
+    let definitions = fin_state.get_definitions_of_internal_signals_over_state_variables(8);
+    assert_eq!(
+        definitions,
+        vec![
+            Definition {
+                variable: v(4),
+                function: DefinitionFunction::And,
+                inputs: SortedVecOfLiterals::from_sequence(vec![
+                    v(2).literal(false),
+                    v(3).literal(false)
+                ]),
+            },
+            Definition {
+                variable: v(6),
+                function: DefinitionFunction::And,
+                inputs: SortedVecOfLiterals::from_sequence(vec![
+                    v(2).literal(true),
+                    v(3).literal(true)
+                ]),
+            },
+        ]
+    );
+    assert!(fin_state
+        .get_definitions_of_internal_signals_over_state_variables(0)
+        .is_empty());
+}
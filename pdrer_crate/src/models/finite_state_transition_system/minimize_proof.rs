@@ -0,0 +1,360 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::iter;
+
+use fxhash::FxHashSet;
+
+use super::FiniteStateTransitionSystem;
+use crate::{
+    formulas::{Clause, Literal, Variable, CNF},
+    models::{Definition, Proof},
+    solvers::sat::incremental::{IncrementalSatSolver, IncrementalSolverUtils, SatResult},
+};
+
+// ************************************************************************************************
+// struct
+// ************************************************************************************************
+
+/// A version of a clause of the invariant in the solvers of `InvariantSolvers`.
+struct ActivatedClause {
+    clause: Clause,
+    /// implies the clause in the current state
+    current: Literal,
+    /// implies the negation of the clause in the next state
+    next: Literal,
+}
+
+/// Incremental solvers that check subsets of the clauses of an invariant. Every clause gets
+/// activation literals once and a subset is then checked by assuming the activation literals of
+/// its clauses, so removing a clause or a literal does not rebuild the solvers.
+struct InvariantSolvers<T: IncrementalSatSolver> {
+    /// the transition relation with the definitions in both steps
+    inductive: T,
+    /// the violation of the property with the definitions
+    safety: T,
+    /// the initial states with the definitions
+    initial: T,
+    /// the next variable to use for activation literals
+    next_variable: u32,
+}
+
+impl<T: IncrementalSatSolver> InvariantSolvers<T> {
+    fn new(fin_state: &FiniteStateTransitionSystem, definitions: &[Definition], cnf: &CNF) -> Self {
+        let d_cnf = FiniteStateTransitionSystem::definitions_to_cnf(definitions);
+        let mut tagged_d_cnf = d_cnf.to_owned();
+        fin_state.add_tags_to_relation(&mut tagged_d_cnf, 1);
+        let mut tagged_cnf = cnf.to_owned();
+        fin_state.add_tags_to_relation(&mut tagged_cnf, 1);
+
+        let mut transition = fin_state.construct_transition_cnf(true, true, true, true);
+        transition.append(d_cnf.to_owned());
+        transition.append(tagged_d_cnf);
+        let mut bad = fin_state.construct_property_cnf(true, true);
+        bad.append(d_cnf.to_owned());
+        let mut init = fin_state.construct_initial_cnf(true);
+        init.append(d_cnf);
+
+        let max_variable = [&transition, &bad, &init, cnf, &tagged_cnf]
+            .iter()
+            .map(|c| c.get_max_variable())
+            .max()
+            .unwrap();
+        Self {
+            inductive: IncrementalSolverUtils::new_solver::<T>(&transition, 0),
+            safety: IncrementalSolverUtils::new_solver::<T>(&bad, 0),
+            initial: IncrementalSolverUtils::new_solver::<T>(&init, 0),
+            next_variable: max_variable.number() + 1,
+        }
+    }
+
+    fn new_literal(&mut self) -> Literal {
+        let v = Variable::new(self.next_variable);
+        self.next_variable += 1;
+        v.literal(false)
+    }
+
+    /// Adds a clause to the solvers, with new activation literals.
+    fn activate(
+        &mut self,
+        fin_state: &FiniteStateTransitionSystem,
+        clause: Clause,
+    ) -> ActivatedClause {
+        let current = self.new_literal();
+        let next = self.new_literal();
+        let implication: Vec<Literal> =
+            iter::once(!current).chain(clause.iter().copied()).collect();
+        self.inductive.add_clause(implication.iter().copied());
+        self.safety.add_clause(implication);
+        let mut tagged_clause = clause.to_owned();
+        fin_state.add_tags_to_clause(&mut tagged_clause, 1);
+        for l in tagged_clause.iter() {
+            self.inductive.add_clause([!next, !*l]);
+        }
+        ActivatedClause {
+            clause,
+            current,
+            next,
+        }
+    }
+
+    /// Removes a clause that is no longer used from the solvers.
+    fn deactivate(&mut self, clause: &ActivatedClause) {
+        self.inductive.add_clause([!clause.current]);
+        self.inductive.add_clause([!clause.next]);
+        self.safety.add_clause([!clause.current]);
+    }
+
+    /// Returns true if the clauses are inductive relative to themselves.
+    fn is_inductive(&mut self, clauses: &[&ActivatedClause]) -> bool {
+        clauses.is_empty()
+            || self.inductive.solve(
+                clauses.iter().map(|c| c.current),
+                clauses.iter().map(|c| c.next),
+            ) == SatResult::UnSat
+    }
+
+    /// Returns true if the clauses guarantee that the property holds.
+    fn is_safe(&mut self, clauses: &[&ActivatedClause]) -> bool {
+        self.safety
+            .solve(clauses.iter().map(|c| c.current), iter::empty())
+            == SatResult::UnSat
+    }
+
+    /// Returns true if all initial states satisfy the clause.
+    fn is_initial(&mut self, clause: &Clause) -> bool {
+        self.initial
+            .solve(clause.iter().map(|l| !*l), iter::empty())
+            == SatResult::UnSat
+    }
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl FiniteStateTransitionSystem {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    /// Removes every clause that is not needed for the invariant to stay inductive and safe.
+    /// Each clause is removed in turn and added back if the remaining clauses are no longer
+    /// an inductive invariant that guarantees safety, the result is thus minimal (but not
+    /// necessarily minimum).
+    fn remove_redundant_clauses<T: IncrementalSatSolver>(
+        solvers: &mut InvariantSolvers<T>,
+        mut clauses: Vec<ActivatedClause>,
+    ) -> Vec<ActivatedClause> {
+        // try removing the largest clauses first since they are the least valuable
+        clauses.sort_by_key(|c| std::cmp::Reverse(c.clause.len()));
+        let mut is_kept = vec![true; clauses.len()];
+        for i in 0..clauses.len() {
+            is_kept[i] = false;
+            let kept: Vec<&ActivatedClause> = clauses
+                .iter()
+                .zip(is_kept.iter())
+                .filter(|(_, k)| **k)
+                .map(|(c, _)| c)
+                .collect();
+            if !solvers.is_safe(&kept) || !solvers.is_inductive(&kept) {
+                is_kept[i] = true;
+            }
+        }
+
+        let mut result = vec![];
+        for (c, k) in clauses.into_iter().zip(is_kept) {
+            if k {
+                result.push(c);
+            } else {
+                solvers.deactivate(&c);
+            }
+        }
+        result
+    }
+
+    /// Removes literals from the clauses as long as the invariant stays inductive.
+    /// Removing a literal strengthens the invariant and so safety is kept, but the strengthened
+    /// clause must still hold in all initial states.
+    fn remove_redundant_literals<T: IncrementalSatSolver>(
+        &self,
+        solvers: &mut InvariantSolvers<T>,
+        mut clauses: Vec<ActivatedClause>,
+    ) -> Vec<ActivatedClause> {
+        for i in 0..clauses.len() {
+            let mut j = 0;
+            while j < clauses[i].clause.len() && clauses[i].clause.len() > 1 {
+                let mut candidate = clauses[i].clause.to_owned();
+                candidate.remove_index(j);
+                if !solvers.is_initial(&candidate) {
+                    j += 1;
+                    continue;
+                }
+                let candidate = solvers.activate(self, candidate);
+                let others = clauses.iter().enumerate().filter(|(k, _)| *k != i);
+                let with_candidate: Vec<&ActivatedClause> = others
+                    .map(|(_, c)| c)
+                    .chain(iter::once(&candidate))
+                    .collect();
+                if solvers.is_inductive(&with_candidate) {
+                    let original = std::mem::replace(&mut clauses[i], candidate);
+                    solvers.deactivate(&original);
+                } else {
+                    solvers.deactivate(&candidate);
+                    j += 1;
+                }
+            }
+        }
+        clauses
+    }
+
+    /// Returns the definitions that the clauses depend on, directly or through other definitions,
+    /// in the order in which they were given.
+    fn remove_unused_definitions(
+        definitions: &[Definition],
+        clauses: &[Clause],
+    ) -> Vec<Definition> {
+        let mut used: FxHashSet<Variable> = clauses
+            .iter()
+            .flat_map(|c| c.iter())
+            .map(|l| l.variable())
+            .collect();
+
+        let mut is_used = vec![false; definitions.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (i, d) in definitions.iter().enumerate() {
+                if !is_used[i] && used.contains(&d.variable) {
+                    is_used[i] = true;
+                    changed = true;
+                    used.extend(d.inputs.iter().map(|l| l.variable()));
+                }
+            }
+        }
+
+        definitions
+            .iter()
+            .zip(is_used)
+            .filter(|(_, is_used)| *is_used)
+            .map(|(d, _)| d.to_owned())
+            .collect()
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Minimizes a proof of this system by removing clauses that are not needed for the
+    /// invariant to be inductive and safe, and then removing definitions that are no longer
+    /// used. If `shrink_literals` is true, literals are also removed from the remaining clauses
+    /// as long as the invariant remains an inductive invariant.
+    ///
+    /// The proof is expected to pass `check_proof`, the result would then pass it as well.
+    pub fn minimize_proof<T: IncrementalSatSolver>(
+        &self,
+        proof: Proof,
+        shrink_literals: bool,
+    ) -> Proof {
+        if proof.all_initial_states_violate_constraints {
+            return proof;
+        }
+        debug_assert!(self.check_proof::<T>(&proof).is_ok());
+
+        let definitions = proof.definitions;
+        let mut solvers = InvariantSolvers::<T>::new(self, &definitions, &proof.invariant);
+        let mut clauses: Vec<ActivatedClause> = proof
+            .invariant
+            .unpack()
+            .unpack()
+            .into_iter()
+            .map(|c| solvers.activate(self, c))
+            .collect();
+        clauses = Self::remove_redundant_clauses(&mut solvers, clauses);
+        if shrink_literals {
+            clauses = self.remove_redundant_literals(&mut solvers, clauses);
+            // stronger clauses may make other clauses redundant
+            clauses = Self::remove_redundant_clauses(&mut solvers, clauses);
+        }
+        let clauses: Vec<Clause> = clauses.into_iter().map(|c| c.clause).collect();
+        let definitions = Self::remove_unused_definitions(&definitions, &clauses);
+
+        let result = Proof {
+            all_initial_states_violate_constraints: false,
+            invariant: CNF::from_sequence(clauses),
+            definitions,
+        };
+        debug_assert!(self.check_proof::<T>(&result).is_ok());
+        result
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[test]
+fn test_minimize_proof() {
+    use crate::{
+        models::SortedVecOfLiterals,
+        models::{definition::DefinitionFunction, AndInverterGraph, Circuit, Signal, TernaryValue},
+        solvers::sat::incremental::CaDiCalSolver,
+    };
+
+    // 1 input, latch 2 is 2 & 1 and latch 3 is 2, both start at 0 and 3 is bad
+    let aig = AndInverterGraph::new(
+        Signal::new(4),
+        1,
+        &[
+            (Signal::new(4).wire(false), TernaryValue::False),
+            (Signal::new(2).wire(false), TernaryValue::False),
+        ],
+        vec![],
+        vec![Signal::new(3).wire(false)],
+        vec![],
+        &[(Signal::new(2).wire(false), Signal::new(1).wire(false))],
+        String::new(),
+    )
+    .unwrap();
+    let circuit = Circuit::from_aig(&aig);
+    let fin_state = FiniteStateTransitionSystem::new(&circuit, false).unwrap();
+    let a = fin_state.convert_signal_to_variable(Signal::new(2));
+    let b = fin_state.convert_signal_to_variable(Signal::new(3));
+    let size = |p: &Proof| p.invariant.iter().map(|c| c.len()).sum::<usize>();
+
+    // (!a \/ b) ^ !b is inductive, !a ^ !b as well and (!a \/ !b) is redundant
+    let x = Variable::new(
+        fin_state
+            .construct_transition_cnf(true, true, true, true)
+            .get_max_variable()
+            .number()
+            + 1,
+    );
+    let proof = Proof {
+        all_initial_states_violate_constraints: false,
+        invariant: CNF::from_sequence(vec![
+            Clause::from_sequence(vec![a.literal(true), b.literal(false)]),
+            Clause::from_sequence(vec![b.literal(true)]),
+            Clause::from_sequence(vec![a.literal(true), b.literal(true)]),
+        ]),
+        definitions: vec![Definition {
+            variable: x,
+            function: DefinitionFunction::And,
+            inputs: SortedVecOfLiterals::from_sequence(vec![a.literal(false), b.literal(false)]),
+        }],
+    };
+    assert!(fin_state.check_proof::<CaDiCalSolver>(&proof).is_ok());
+
+    for shrink_literals in [false, true] {
+        let minimized =
+            fin_state.minimize_proof::<CaDiCalSolver>(proof.to_owned(), shrink_literals);
+        assert!(fin_state.check_proof::<CaDiCalSolver>(&minimized).is_ok());
+        assert_eq!(minimized.invariant.len(), 2);
+        assert!(minimized.definitions.is_empty());
+        assert!(size(&minimized) <= size(&proof));
+        if shrink_literals {
+            assert_eq!(size(&minimized), 2);
+        }
+    }
+}
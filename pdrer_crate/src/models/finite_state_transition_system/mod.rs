@@ -25,6 +25,7 @@ pub enum FiniteStateTransitionSystemError {
     ConstraintWiresIncludeWireAndItsNegation,
     BadWireIsConstantOne,
     BadWiresIncludeWireAndItsNegation,
+    UnsupportedLutSize,
 }
 
 // ************************************************************************************************
@@ -58,6 +59,7 @@ pub struct FiniteStateTransitionSystem {
     transition_on_internals: CNF,
 
     // some meta data
+    encoding: TransitionRelationEncoding,
     max_variable: Variable,
     variable_definitions: UniqueSortedHashMap<Variable, Vec<Clause>>,
 
@@ -92,13 +94,18 @@ pub mod check;
 pub mod cnf;
 pub mod construction;
 pub mod conversion;
+pub mod encoding;
 pub mod extraction;
 pub mod features;
 pub mod getting;
 pub mod inductive_subset;
+pub mod internal_definitions;
+pub mod minimize_proof;
 pub mod random;
 pub mod x_simulation;
 
 // ************************************************************************************************
 // re-exports of structs in these modules to simplify paths for other imports
 // ************************************************************************************************
+
+pub use encoding::{FiniteStateTransitionSystemEncodingStats, TransitionRelationEncoding};
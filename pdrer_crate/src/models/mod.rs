@@ -8,6 +8,7 @@ pub mod finite_state_transition_system;
 // requires folder in this directory with the name 'finite_state_transition_system'
 pub mod and_inverter_graph;
 // requires folder in this directory with the name 'and_inverter_graph'
+pub mod bit_parallel_simulator;
 pub mod btor;
 pub mod circuit;
 pub mod circuit_builder;
@@ -39,6 +40,7 @@ pub mod wire;
 // ************************************************************************************************
 
 pub use and_inverter_graph::AndInverterGraph;
+pub use bit_parallel_simulator::BitParallelSimulator;
 pub use btor::BTOR;
 pub use circuit::Circuit;
 pub use circuit_builder::CircuitBuilder;
@@ -21,6 +21,7 @@ pub struct Proof {
 // ************************************************************************************************
 
 pub mod to_certifaiger;
+pub mod to_monitor;
 
 // ************************************************************************************************
 // re-exports of structs in these modules to simplify paths for other imports
@@ -89,7 +89,7 @@ impl Proof {
         Signal::new(and_gates.last().unwrap().out.number() + 1)
     }
 
-    fn define_and_of_2_wires(a: Wire, b: Wire, and_gates: &mut Vec<AndGate>) -> Wire {
+    pub(super) fn define_and_of_2_wires(a: Wire, b: Wire, and_gates: &mut Vec<AndGate>) -> Wire {
         let out = Self::get_free_signal(and_gates);
         and_gates.push(AndGate {
             in0: a,
@@ -99,7 +99,7 @@ impl Proof {
         out.wire(false)
     }
 
-    fn define_or_of_2_wires(a: Wire, b: Wire, and_gates: &mut Vec<AndGate>) -> Wire {
+    pub(super) fn define_or_of_2_wires(a: Wire, b: Wire, and_gates: &mut Vec<AndGate>) -> Wire {
         let out = Self::get_free_signal(and_gates);
         and_gates.push(AndGate {
             in0: !a,
@@ -109,7 +109,7 @@ impl Proof {
         out.wire(true)
     }
 
-    fn define_xor_of_2_wires(a: Wire, b: Wire, and_gates: &mut Vec<AndGate>) -> Wire {
+    pub(super) fn define_xor_of_2_wires(a: Wire, b: Wire, and_gates: &mut Vec<AndGate>) -> Wire {
         let x = Self::get_free_signal(and_gates);
         and_gates.push(AndGate {
             in0: a,
@@ -131,7 +131,45 @@ impl Proof {
         out.wire(false)
     }
 
-    fn define_signal_as_bitwise_operation_on_wires<F>(
+    fn define_mux_of_wires(s: Wire, w1: Wire, w0: Wire, and_gates: &mut Vec<AndGate>) -> Wire {
+        match (w1, w0) {
+            _ if w1 == w0 => w1,
+            (Wire::CONSTANT_ONE, Wire::CONSTANT_ZERO) => s,
+            (Wire::CONSTANT_ZERO, Wire::CONSTANT_ONE) => !s,
+            _ => {
+                let a = Self::define_and_of_2_wires(s, w1, and_gates);
+                let b = Self::define_and_of_2_wires(!s, w0, and_gates);
+                Self::define_or_of_2_wires(a, b, and_gates)
+            }
+        }
+    }
+
+    /// Defines the function by a shannon expansion of its truth table over the wires.
+    pub(super) fn define_function_of_wires(
+        function: DefinitionFunction,
+        wires: Vec<Wire>,
+        and_gates: &mut Vec<AndGate>,
+    ) -> Wire {
+        let table = function.get_truth_table(wires.len());
+        let mut level: Vec<Wire> = (0..(1 << wires.len()))
+            .map(|row| {
+                if (table >> row) & 1 == 1 {
+                    Wire::CONSTANT_ONE
+                } else {
+                    Wire::CONSTANT_ZERO
+                }
+            })
+            .collect();
+        for s in wires {
+            level = level
+                .chunks(2)
+                .map(|pair| Self::define_mux_of_wires(s, pair[1], pair[0], and_gates))
+                .collect();
+        }
+        level[0]
+    }
+
+    pub(super) fn define_signal_as_bitwise_operation_on_wires<F>(
         f: F,
         wires: Vec<Wire>,
         and_gates: &mut Vec<AndGate>,
@@ -159,6 +197,29 @@ impl Proof {
         tracker: &SignalTracker,
         aig: &AndInverterGraph,
         s2v: F,
+    ) -> AndInverterGraph {
+        self.get_certifaiger_witness_custom(tracker, aig, None, s2v)
+    }
+
+    /// Same as `get_certifaiger_witness` but for a proof of a single property (index of the bad
+    /// wire, or of the output if the AIG has no bad wires), other properties are removed from
+    /// the witness.
+    pub fn get_certifaiger_witness_for_property<F: Fn(Signal) -> Variable>(
+        &self,
+        tracker: &SignalTracker,
+        aig: &AndInverterGraph,
+        property: usize,
+        s2v: F,
+    ) -> AndInverterGraph {
+        self.get_certifaiger_witness_custom(tracker, aig, Some(property), s2v)
+    }
+
+    fn get_certifaiger_witness_custom<F: Fn(Signal) -> Variable>(
+        &self,
+        tracker: &SignalTracker,
+        aig: &AndInverterGraph,
+        property: Option<usize>,
+        s2v: F,
     ) -> AndInverterGraph {
         let inputs = aig.get_input_signals();
         let latches: Vec<(Wire, TernaryValue)> = aig
@@ -178,6 +239,14 @@ impl Proof {
             UniqueSortedVec::from_sequence(constraint_wires.to_owned())
         );
 
+        if let Some(i) = property {
+            if bad_wires.is_empty() {
+                output_wires = vec![output_wires[i]];
+            } else {
+                bad_wires = vec![bad_wires[i]];
+            }
+        }
+
         let mut variable_to_wire = Self::get_variable_to_wire_map(
             tracker,
             aig.get_highest_non_negated_wire().signal(),
@@ -206,6 +275,11 @@ impl Proof {
                     inputs,
                     &mut and_gates,
                 ),
+                DefinitionFunction::Ite(_)
+                | DefinitionFunction::Maj3
+                | DefinitionFunction::TruthTable(_) => {
+                    Self::define_function_of_wires(d.function, inputs, &mut and_gates)
+                }
             };
             variable_to_wire.insert(d.variable, w);
         }
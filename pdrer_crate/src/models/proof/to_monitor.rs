@@ -0,0 +1,698 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::fmt::Write;
+
+use fxhash::{FxHashMap, FxHashSet};
+
+use crate::{
+    formulas::{Literal, Variable},
+    models::{
+        and_inverter_graph::AndGate, definition::DefinitionFunction, utils::Utils,
+        AndInverterGraph, Signal, SignalTracker, Wire,
+    },
+};
+
+use super::Proof;
+
+// ************************************************************************************************
+// types
+// ************************************************************************************************
+
+/// A literal of the monitor, either of a latch of the original AIG (by index) or of an
+/// extension variable of the proof.
+#[derive(Clone, Copy)]
+enum MonitorLiteral {
+    Latch(usize, bool),
+    Extension(Variable, bool),
+}
+
+/// The proof over the latches of the original AIG.
+struct Monitor {
+    latch_names: Vec<String>,
+    definitions: Vec<(Variable, DefinitionFunction, Vec<MonitorLiteral>)>,
+    clauses: Vec<Vec<MonitorLiteral>>,
+}
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl Proof {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    /// The name of every latch of the AIG, which is its symbol or "l<index>" when it has no
+    /// symbol or the symbol contains white space. Symbols are not unique, so names that are
+    /// already used get underscores until they are not.
+    fn get_latch_names(aig: &AndInverterGraph) -> Vec<String> {
+        let mut symbols: Vec<Option<String>> = vec![None; aig.get_latch_information().len()];
+        for (symbol_type, i, symbol) in aig.get_symbols() {
+            if symbol_type == 'l' && !symbol.is_empty() && !symbol.contains(char::is_whitespace) {
+                symbols[i as usize] = Some(symbol);
+            }
+        }
+
+        // symbols are named first so that a default name never takes the name of a symbol
+        let mut used: FxHashSet<String> = FxHashSet::default();
+        let mut names: Vec<Option<String>> = symbols
+            .iter()
+            .map(|s| s.to_owned().filter(|s| used.insert(s.to_owned())))
+            .collect();
+        for (i, name) in names.iter_mut().enumerate() {
+            if name.is_none() {
+                let mut fresh = symbols[i].to_owned().unwrap_or_else(|| format!("l{i}"));
+                while used.contains(&fresh) {
+                    fresh.insert(0, '_');
+                }
+                used.insert(fresh.to_owned());
+                *name = Some(fresh);
+            }
+        }
+        names.into_iter().map(|n| n.unwrap()).collect()
+    }
+
+    fn get_monitor_literal(
+        latches: &FxHashMap<Variable, usize>,
+        extensions: &FxHashSet<Variable>,
+        literal: Literal,
+    ) -> Result<MonitorLiteral, String> {
+        let v = literal.variable();
+        if let Some(i) = latches.get(&v) {
+            Ok(MonitorLiteral::Latch(*i, literal.is_negated()))
+        } else if extensions.contains(&v) {
+            Ok(MonitorLiteral::Extension(v, literal.is_negated()))
+        } else {
+            Err(format!("Variable {v} is not a latch of the AIG."))
+        }
+    }
+
+    /// Writes the invariant and the definitions over the latches of the original AIG. Latches
+    /// that were merged into other latches are required to be equal to them, like in the
+    /// certifaiger witness.
+    fn get_monitor<F: Fn(Signal) -> Variable>(
+        &self,
+        tracker: &SignalTracker,
+        aig: &AndInverterGraph,
+        s2v: F,
+    ) -> Result<Monitor, String> {
+        let latch_signals = aig.get_latch_signals();
+        let mut latches: FxHashMap<Variable, usize> = FxHashMap::default();
+        for (i, s) in latch_signals.iter().enumerate() {
+            if let Some(final_signal) = tracker.get(*s) {
+                if !final_signal.is_constant() {
+                    latches.entry(s2v(final_signal)).or_insert(i);
+                }
+            }
+        }
+
+        let mut definitions = self.definitions.to_owned();
+        definitions.sort_by_key(|d| d.variable);
+        let mut extensions = FxHashSet::default();
+        let mut monitor_definitions = Vec::with_capacity(definitions.len());
+        for d in definitions {
+            let inputs = d
+                .inputs
+                .iter()
+                .map(|l| Self::get_monitor_literal(&latches, &extensions, *l))
+                .collect::<Result<Vec<_>, _>>()?;
+            extensions.insert(d.variable);
+            monitor_definitions.push((d.variable, d.function, inputs));
+        }
+
+        let mut clauses = Vec::with_capacity(self.invariant.len());
+        for clause in self.invariant.iter() {
+            clauses.push(
+                clause
+                    .iter()
+                    .map(|l| Self::get_monitor_literal(&latches, &extensions, *l))
+                    .collect::<Result<Vec<_>, _>>()?,
+            );
+        }
+        for (i, s) in latch_signals.iter().enumerate() {
+            if let Some(w) = tracker.find_equivalent_if_removed(*s) {
+                if let Some(j) = latch_signals.iter().position(|x| *x == w.signal()) {
+                    for is_negated in [false, true] {
+                        clauses.push(vec![
+                            MonitorLiteral::Latch(i, is_negated),
+                            MonitorLiteral::Latch(j, is_negated == w.is_negated()),
+                        ]);
+                    }
+                }
+            }
+        }
+
+        Ok(Monitor {
+            latch_names: Self::get_latch_names(aig),
+            definitions: monitor_definitions,
+            clauses,
+        })
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Returns a combinational AIG with an input for every latch of the original AIG, named and
+    /// ordered like the latches, and a single output that is 1 when the invariant holds. The
+    /// proof should have been produced like for `get_certifaiger_witness`, and must only refer
+    /// to latches and extension variables.
+    ///
+    /// The invariant holds in every reachable state that satisfies the constraints. If all the
+    /// initial states violate the constraints the output is 0.
+    pub fn get_monitor_aig<F: Fn(Signal) -> Variable>(
+        &self,
+        tracker: &SignalTracker,
+        aig: &AndInverterGraph,
+        s2v: F,
+    ) -> Result<AndInverterGraph, String> {
+        let monitor = self.get_monitor(tracker, aig, s2v)?;
+        let number_of_inputs = monitor.latch_names.len();
+
+        // add this gate to make code easier to write
+        let mut and_gates = vec![AndGate {
+            in0: Wire::CONSTANT_ZERO,
+            in1: Wire::CONSTANT_ZERO,
+            out: Signal::new(number_of_inputs as u32 + 1),
+        }];
+        let mut extension_to_wire: FxHashMap<Variable, Wire> = FxHashMap::default();
+        let to_wire = |l: &MonitorLiteral, map: &FxHashMap<Variable, Wire>| match *l {
+            MonitorLiteral::Latch(i, is_negated) => Signal::new(i as u32 + 1).wire(is_negated),
+            MonitorLiteral::Extension(v, is_negated) => {
+                let w = map[&v];
+                if is_negated {
+                    !w
+                } else {
+                    w
+                }
+            }
+        };
+
+        for (v, function, inputs) in monitor.definitions.iter() {
+            let inputs: Vec<Wire> = inputs
+                .iter()
+                .map(|l| to_wire(l, &extension_to_wire))
+                .collect();
+            let w = match function {
+                DefinitionFunction::And => Self::define_signal_as_bitwise_operation_on_wires(
+                    Self::define_and_of_2_wires,
+                    inputs,
+                    &mut and_gates,
+                ),
+                DefinitionFunction::Xor => Self::define_signal_as_bitwise_operation_on_wires(
+                    Self::define_xor_of_2_wires,
+                    inputs,
+                    &mut and_gates,
+                ),
+                DefinitionFunction::Ite(_)
+                | DefinitionFunction::Maj3
+                | DefinitionFunction::TruthTable(_) => {
+                    Self::define_function_of_wires(*function, inputs, &mut and_gates)
+                }
+            };
+            extension_to_wire.insert(*v, w);
+        }
+
+        let mut clause_wires = Vec::with_capacity(monitor.clauses.len());
+        for clause in monitor.clauses.iter() {
+            let inputs: Vec<Wire> = clause
+                .iter()
+                .map(|l| to_wire(l, &extension_to_wire))
+                .collect();
+            let w = if inputs.is_empty() {
+                Wire::CONSTANT_ZERO
+            } else {
+                Self::define_signal_as_bitwise_operation_on_wires(
+                    Self::define_or_of_2_wires,
+                    inputs,
+                    &mut and_gates,
+                )
+            };
+            clause_wires.push(w);
+        }
+
+        let invariant_wire = if self.all_initial_states_violate_constraints {
+            Wire::CONSTANT_ZERO
+        } else if !clause_wires.is_empty() {
+            Self::define_signal_as_bitwise_operation_on_wires(
+                Self::define_and_of_2_wires,
+                clause_wires,
+                &mut and_gates,
+            )
+        } else {
+            Wire::CONSTANT_ONE
+        };
+
+        let mut monitor_aig = AndInverterGraph::new(
+            Signal::new((number_of_inputs + and_gates.len()) as u32),
+            number_of_inputs as u32,
+            &[],
+            vec![invariant_wire],
+            vec![],
+            vec![],
+            &and_gates
+                .into_iter()
+                .map(|a| (a.in0, a.in1))
+                .collect::<Vec<_>>(),
+            String::from("c\nInvariant monitor, the output is 1 when the invariant holds.\n"),
+        )?;
+        for (i, name) in monitor.latch_names.iter().enumerate() {
+            monitor_aig.add_symbol('i', i as u32, name)?;
+        }
+        monitor_aig.add_symbol('o', 0, "invariant")?;
+        Ok(monitor_aig)
+    }
+
+    /// Returns a synthesizable Verilog module that computes the same output as
+    /// `get_monitor_aig`. The ports are the latches of the original AIG, named by their symbols,
+    /// and the output `invariant`. Every extension variable is a wire that implements its
+    /// definition, and so is every clause of the invariant.
+    pub fn get_monitor_verilog<F: Fn(Signal) -> Variable>(
+        &self,
+        tracker: &SignalTracker,
+        aig: &AndInverterGraph,
+        s2v: F,
+        module_name: &str,
+    ) -> Result<String, String> {
+        let monitor = self.get_monitor(tracker, aig, s2v)?;
+        let latch_names: Vec<String> = monitor
+            .latch_names
+            .iter()
+            .map(|n| Utils::get_verilog_identifier(n))
+            .collect();
+
+        // internal names get underscores until they are not used by a latch
+        let mut used: FxHashSet<String> = latch_names.iter().cloned().collect();
+        let mut get_fresh_name = |name: String| {
+            let mut name = name;
+            while used.contains(&name) {
+                name.insert(0, '_');
+            }
+            used.insert(name.clone());
+            name
+        };
+        let output_name = get_fresh_name("invariant".to_owned());
+        let extension_names: FxHashMap<Variable, String> = monitor
+            .definitions
+            .iter()
+            .map(|(v, _, _)| (*v, get_fresh_name(format!("v{}", v.number()))))
+            .collect();
+        let clause_names: Vec<String> = (0..monitor.clauses.len())
+            .map(|i| get_fresh_name(format!("c{i}")))
+            .collect();
+        let to_expression = |l: &MonitorLiteral| {
+            let (name, is_negated) = match l {
+                MonitorLiteral::Latch(i, is_negated) => (&latch_names[*i], *is_negated),
+                MonitorLiteral::Extension(v, is_negated) => (&extension_names[v], *is_negated),
+            };
+            if is_negated {
+                format!("~{name}")
+            } else {
+                name.to_owned()
+            }
+        };
+
+        let mut s = String::new();
+        writeln!(
+            s,
+            "// Invariant monitor, '{output_name}' is 1 when the invariant holds. The invariant"
+        )
+        .unwrap();
+        writeln!(
+            s,
+            "// holds in every reachable state of the design that satisfies the constraints."
+        )
+        .unwrap();
+        let mut ports: Vec<String> = latch_names
+            .iter()
+            .map(|n| format!("    input wire {n}"))
+            .collect();
+        ports.push(format!("    output wire {output_name}"));
+        writeln!(
+            s,
+            "module {} (\n{}\n);",
+            Utils::get_verilog_identifier(module_name),
+            ports.join(",\n")
+        )
+        .unwrap();
+
+        for (v, function, inputs) in monitor.definitions.iter() {
+            let inputs: Vec<String> = inputs.iter().map(to_expression).collect();
+            let name = &extension_names[v];
+            let expression = match function {
+                DefinitionFunction::And => inputs.join(" & "),
+                DefinitionFunction::Xor => inputs.join(" ^ "),
+                DefinitionFunction::Ite(i) => {
+                    let i = *i as usize;
+                    let branches: Vec<&String> =
+                        (0..3).filter(|j| *j != i).map(|j| &inputs[j]).collect();
+                    format!("{} ? {} : {}", inputs[i], branches[0], branches[1])
+                }
+                DefinitionFunction::Maj3 => format!(
+                    "({0} & {1}) | ({0} & {2}) | ({1} & {2})",
+                    inputs[0], inputs[1], inputs[2]
+                ),
+                DefinitionFunction::TruthTable(t) => {
+                    let table = get_fresh_name(format!("{name}_table"));
+                    let rows: Vec<bool> = (0..(1 << inputs.len()))
+                        .map(|r| (t >> r) & 1 == 1)
+                        .collect();
+                    Utils::write_verilog_truth_table(&mut s, &table, &rows, &inputs)
+                }
+            };
+            writeln!(s, "    wire {name} = {expression};").unwrap();
+        }
+
+        for (clause, name) in monitor.clauses.iter().zip(clause_names.iter()) {
+            let expression = if clause.is_empty() {
+                "1'b0".to_owned()
+            } else {
+                clause
+                    .iter()
+                    .map(to_expression)
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            };
+            writeln!(s, "    wire {name} = {expression};").unwrap();
+        }
+
+        let invariant = if self.all_initial_states_violate_constraints {
+            "1'b0".to_owned()
+        } else if clause_names.is_empty() {
+            "1'b1".to_owned()
+        } else {
+            clause_names.join(" & ")
+        };
+        writeln!(s, "    assign {output_name} = {invariant};").unwrap();
+        writeln!(s, "endmodule").unwrap();
+        Ok(s)
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formulas::{Clause, CNF};
+    use crate::models::{
+        BitParallelSimulator, Circuit, Definition, SortedVecOfLiterals, TernaryValue,
+    };
+
+    /// Splits a line of the monitor into tokens, escaped identifiers lose their back slash.
+    fn get_verilog_tokens(line: &str) -> Vec<String> {
+        let is_word = |c: &char| c.is_ascii_alphanumeric() || "_$'".contains(*c);
+        let mut tokens = vec![];
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c.is_whitespace() {
+                continue;
+            }
+            let is_escaped = c == '\\';
+            if !is_escaped && !is_word(&c) {
+                tokens.push(c.to_string());
+                continue;
+            }
+            let mut token = if is_escaped {
+                String::new()
+            } else {
+                c.to_string()
+            };
+            while let Some(c) = chars.next_if(|c| {
+                if is_escaped {
+                    !c.is_whitespace()
+                } else {
+                    is_word(c)
+                }
+            }) {
+                token.push(c);
+            }
+            tokens.push(token);
+        }
+        tokens
+    }
+
+    /// Evaluates the expression that starts at token `i` and moves `i` past it. Only operators
+    /// that bind stronger than `precedence` are taken, `&` binds stronger than `^` which binds
+    /// stronger than `|`, and `? :` binds the weakest.
+    fn evaluate_verilog_expression(
+        tokens: &[String],
+        i: &mut usize,
+        values: &FxHashMap<String, bool>,
+        tables: &FxHashMap<String, Vec<bool>>,
+        precedence: usize,
+    ) -> bool {
+        let evaluate = |i: &mut usize, precedence| {
+            evaluate_verilog_expression(tokens, i, values, tables, precedence)
+        };
+        let mut value = match tokens[*i].as_str() {
+            "~" => {
+                *i += 1;
+                let value = !evaluate(i, 3);
+                *i -= 1;
+                value
+            }
+            "(" => {
+                *i += 1;
+                evaluate(i, 0)
+            }
+            "1'b0" | "1'b1" => tokens[*i] == "1'b1",
+            name if tables.contains_key(name) => {
+                // table[{a, b, ...}] where the first input is the most significant bit
+                *i += 3;
+                let mut row = 0;
+                loop {
+                    row = (row << 1) | evaluate(i, 0) as usize;
+                    *i += 1;
+                    if tokens[*i - 1] == "}" {
+                        break;
+                    }
+                }
+                tables[name][row]
+            }
+            name => values[name],
+        };
+        *i += 1;
+
+        loop {
+            let operator = match tokens.get(*i).map(|t| t.as_str()) {
+                Some("&") => 3,
+                Some("^") => 2,
+                Some("|") => 1,
+                _ => break,
+            };
+            if operator <= precedence {
+                break;
+            }
+            *i += 1;
+            let rhs = evaluate(i, operator);
+            value = match operator {
+                3 => value & rhs,
+                2 => value ^ rhs,
+                _ => value | rhs,
+            };
+        }
+        if precedence == 0 && tokens.get(*i).map(|t| t.as_str()) == Some("?") {
+            *i += 1;
+            let a = evaluate(i, 0);
+            *i += 1;
+            let b = evaluate(i, 0);
+            return if value { a } else { b };
+        }
+        value
+    }
+
+    /// Returns the output of the monitor module when its inputs are assigned the given values.
+    fn evaluate_monitor_verilog(verilog: &str, inputs: &[bool]) -> bool {
+        let mut values: FxHashMap<String, bool> = FxHashMap::default();
+        let mut tables: FxHashMap<String, Vec<bool>> = FxHashMap::default();
+        let mut inputs = inputs.iter();
+        let mut output = None;
+        for line in verilog.lines() {
+            let tokens = get_verilog_tokens(line);
+            match tokens
+                .iter()
+                .map(|t| t.as_str())
+                .collect::<Vec<_>>()
+                .as_slice()
+            {
+                ["input", "wire", name, ..] => {
+                    values.insert(name.to_string(), *inputs.next().unwrap());
+                }
+                ["wire", "[", _, ":", "0", "]", name, "=", bits, ";"] => {
+                    let bits = bits.split_once("'b").unwrap().1;
+                    tables.insert(
+                        name.to_string(),
+                        bits.chars().rev().map(|c| c == '1').collect(),
+                    );
+                }
+                ["wire" | "assign", name, "=", ..] => {
+                    let mut i = 3;
+                    let value = evaluate_verilog_expression(&tokens, &mut i, &values, &tables, 0);
+                    assert_eq!(tokens[i], ";");
+                    values.insert(name.to_string(), value);
+                    if tokens[0] == "assign" {
+                        output = Some(value);
+                    }
+                }
+                _ => {}
+            }
+        }
+        output.unwrap()
+    }
+
+    #[test]
+    fn test_monitor_computes_the_invariant() {
+        let w = |n: u32| Signal::new(n).wire(false);
+        let mut aig = AndInverterGraph::new(
+            Signal::new(3),
+            0,
+            &[
+                (w(2), TernaryValue::False),
+                (w(3), TernaryValue::False),
+                (w(1), TernaryValue::X),
+            ],
+            vec![],
+            vec![w(1)],
+            vec![],
+            &[],
+            String::new(),
+        )
+        .unwrap();
+        aig.add_symbol('l', 0, "reg").unwrap();
+        aig.add_symbol('l', 1, "a[0]").unwrap();
+
+        let l = |n: u32, is_negated: bool| Variable::new(n).literal(is_negated);
+        let definitions = [
+            (4, DefinitionFunction::And, vec![l(1, false), l(2, true)]),
+            (
+                5,
+                DefinitionFunction::Xor,
+                vec![l(2, false), l(3, false), l(4, false)],
+            ),
+            (
+                6,
+                DefinitionFunction::Ite(1),
+                vec![l(1, false), l(2, false), l(3, true)],
+            ),
+            (
+                7,
+                DefinitionFunction::Maj3,
+                vec![l(1, false), l(3, true), l(5, false)],
+            ),
+            (
+                8,
+                DefinitionFunction::TruthTable(0b0110_1001_1100_0011),
+                vec![l(1, false), l(2, false), l(5, true), l(6, false)],
+            ),
+        ];
+        let proof = Proof {
+            all_initial_states_violate_constraints: false,
+            invariant: CNF::from_sequence(vec![
+                Clause::from_sequence(vec![l(4, true), l(5, false)]),
+                Clause::from_sequence(vec![l(6, false), l(7, true), l(8, false)]),
+                Clause::from_sequence(vec![l(1, false), l(8, false)]),
+            ]),
+            definitions: definitions
+                .iter()
+                .map(|(v, function, inputs)| Definition {
+                    variable: Variable::new(*v),
+                    function: *function,
+                    inputs: SortedVecOfLiterals::from_sequence(inputs.to_owned()),
+                })
+                .collect(),
+        };
+
+        let tracker = SignalTracker::new();
+        let s2v = |s: Signal| Variable::new(s.number());
+        let monitor = proof.get_monitor_aig(&tracker, &aig, s2v).unwrap();
+        let circuit = Circuit::from_aig(&monitor);
+        let mut simulator = BitParallelSimulator::new(&circuit);
+        for (i, s) in simulator.get_input_signals().to_owned().iter().enumerate() {
+            let word = (0..8).fold(0, |r, row| r | ((row >> i) & 1) << row);
+            simulator.set_value(*s, word);
+        }
+        simulator.simulate();
+        let result = simulator.get_wire_value(circuit.get_output_wires().peek()[0]);
+
+        for row in 0..8 {
+            let mut values = vec![false; 9];
+            for (i, v) in values.iter_mut().enumerate().skip(1).take(3) {
+                *v = (row >> (i - 1)) & 1 == 1;
+            }
+            let value_of = |l: &Literal, values: &[bool]| {
+                values[l.variable().number() as usize] != l.is_negated()
+            };
+            for d in proof.definitions.iter() {
+                let inputs: Vec<bool> = d.inputs.iter().map(|l| value_of(l, &values)).collect();
+                values[d.variable.number() as usize] = d.function.evaluate(&inputs);
+            }
+            let expected = proof
+                .invariant
+                .iter()
+                .all(|c| c.iter().any(|l| value_of(l, &values)));
+            assert_eq!((result >> row) & 1 == 1, expected, "row {}", row);
+        }
+
+        let verilog = proof
+            .get_monitor_verilog(&tracker, &aig, s2v, "monitor")
+            .unwrap();
+        assert!(verilog.contains("input wire \\reg ,"));
+        assert!(verilog.contains("input wire \\a[0] ,"));
+        assert!(verilog.contains("input wire l2,"));
+        assert!(verilog.contains("wire v6 = \\a[0]  ? \\reg  : ~l2;"));
+        assert!(verilog.contains("wire v8 = v8_table[{v6, ~v5, \\a[0] , \\reg }];"));
+        assert!(verilog.ends_with("assign invariant = c0 & c1 & c2;\nendmodule\n"));
+        for row in 0..8 {
+            let inputs: Vec<bool> = (0..3).map(|i| (row >> i) & 1 == 1).collect();
+            assert_eq!(
+                evaluate_monitor_verilog(&verilog, &inputs),
+                (result >> row) & 1 == 1,
+                "row {}",
+                row
+            );
+        }
+    }
+
+    #[test]
+    fn test_monitor_latch_names_are_unique() {
+        let w = |n: u32| Signal::new(n).wire(false);
+        let mut aig = AndInverterGraph::new(
+            Signal::new(4),
+            0,
+            &[
+                (w(1), TernaryValue::False),
+                (w(2), TernaryValue::False),
+                (w(3), TernaryValue::False),
+                (w(4), TernaryValue::False),
+            ],
+            vec![],
+            vec![],
+            vec![],
+            &[],
+            String::new(),
+        )
+        .unwrap();
+        aig.add_symbol('l', 0, "l1").unwrap();
+        aig.add_symbol('l', 2, "x").unwrap();
+        aig.add_symbol('l', 3, "x").unwrap();
+        assert_eq!(Proof::get_latch_names(&aig), vec!["l1", "_l1", "x", "_x"]);
+
+        let proof = Proof {
+            all_initial_states_violate_constraints: false,
+            invariant: CNF::from_sequence(vec![]),
+            definitions: vec![],
+        };
+        let s2v = |s: Signal| Variable::new(s.number());
+        let verilog = proof
+            .get_monitor_verilog(&SignalTracker::new(), &aig, s2v, "monitor")
+            .unwrap();
+        for name in ["l1", "_l1", "x", "_x"] {
+            assert_eq!(verilog.matches(&format!("input wire {name},")).count(), 1);
+        }
+    }
+}
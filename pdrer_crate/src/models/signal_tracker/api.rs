@@ -66,6 +66,11 @@ impl SignalTracker {
         self.transformations.push(transformation);
     }
 
+    /// Appends the transformations of a tracker that was created after this one.
+    pub fn append(&mut self, mut other: SignalTracker) {
+        self.transformations.append(&mut other.transformations);
+    }
+
     pub fn len(&self) -> usize {
         self.transformations.len()
     }
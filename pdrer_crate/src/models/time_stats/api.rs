@@ -32,6 +32,24 @@ impl TimeStats {
         t.0 += duration;
         t.1 += 1;
     }
+
+    /// Returns the time since this object was created.
+    pub fn get_total_time(&self) -> Duration {
+        self.start_time.elapsed()
+    }
+
+    /// Returns the total time and number of calls of every timed function, sorted by name.
+    pub fn get_function_times(&self) -> Vec<(&'static str, Duration, usize)> {
+        let mut v: Vec<_> = self
+            .times
+            .iter()
+            .map(|(name, (time, count))| {
+                (name.0.strip_suffix("::f").unwrap_or(name.0), *time, *count)
+            })
+            .collect();
+        v.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        v
+    }
 }
 
 // ************************************************************************************************
@@ -11,6 +11,9 @@ use super::{Signal, UniqueSortedVec};
 pub type TruthTableEntry = u16;
 // pub const TRUTH_TABLE_ENTRY_IDENTITY: TruthTableEntry = 0xAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA;
 pub const TRUTH_TABLE_MAX_INPUTS: usize = 4; // 2^7 = 128
+/// The truth table of every input of a function with `TRUTH_TABLE_MAX_INPUTS` inputs.
+pub(crate) const IDENTITY_TRUTH_TABLES: [TruthTableEntry; TRUTH_TABLE_MAX_INPUTS] =
+    [0xAAAA, 0xCCCC, 0xF0F0, 0xFF00];
 
 // ************************************************************************************************
 // struct
@@ -29,6 +32,7 @@ pub struct TruthTable {
 
 pub mod cnf;
 pub mod construction;
+pub mod npn;
 pub mod operations;
 pub mod ternary_result;
 pub mod utils;
@@ -0,0 +1,392 @@
+//! NPN canonicalization of truth tables. Two functions are NPN equivalent if one can be obtained
+//! from the other by negating inputs, permuting inputs and negating the output. Every class is
+//! represented by the smallest truth table in it, this allows gates to be recognized regardless
+//! of the polarity and order of their inputs.
+
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use super::{TruthTable, TruthTableEntry, IDENTITY_TRUTH_TABLES, TRUTH_TABLE_MAX_INPUTS};
+use fxhash::FxHashMap;
+use std::fmt;
+
+// ************************************************************************************************
+// types
+// ************************************************************************************************
+
+pub type NpnCache = FxHashMap<(usize, TruthTableEntry), (NpnClass, NpnTransform)>;
+
+// ************************************************************************************************
+// constants
+// ************************************************************************************************
+
+/// Well known classes, the truth tables are not necessarily the class representatives.
+const NAMED_CLASSES: [(&str, usize, TruthTableEntry); 6] = [
+    ("and2", 2, 0x8),
+    ("and3", 3, 0x80),
+    ("xor2", 2, 0x6),
+    ("xor3", 3, 0x96),
+    ("ite", 3, 0xD8),
+    ("maj", 3, 0xE8),
+];
+
+// ************************************************************************************************
+// struct
+// ************************************************************************************************
+
+/// Transformation from a function to its NPN class representative, the representative is
+/// `output_negation ^ f(y)` where `y[i] = x[permutation[i]] ^ input_negation[i]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NpnTransform {
+    number_of_inputs: usize,
+    permutation: [usize; TRUTH_TABLE_MAX_INPUTS],
+    input_negation: u8,
+    output_negation: bool,
+}
+
+/// An NPN class of functions with a fixed number of inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NpnClass {
+    number_of_inputs: usize,
+    representative: TruthTableEntry,
+}
+
+// ************************************************************************************************
+// impl transform
+// ************************************************************************************************
+
+impl NpnTransform {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    fn get_mask(number_of_inputs: usize) -> TruthTableEntry {
+        let rows = 1 << number_of_inputs;
+        if rows == TruthTableEntry::BITS as usize {
+            TruthTableEntry::MAX
+        } else {
+            !(TruthTableEntry::MAX << rows)
+        }
+    }
+
+    /// Returns `g(x) = f(y)` where `y[i] = x[permutation[i]]`.
+    fn permute(
+        number_of_inputs: usize,
+        permutation: &[usize; TRUTH_TABLE_MAX_INPUTS],
+        f: TruthTableEntry,
+    ) -> TruthTableEntry {
+        let mut result = 0;
+        for x in 0..(1 << number_of_inputs) {
+            let mut y = 0;
+            for (i, p) in permutation.iter().take(number_of_inputs).enumerate() {
+                y |= ((x >> p) & 1) << i;
+            }
+            if (f >> y) & 1 == 1 {
+                result |= 1 << x;
+            }
+        }
+        result
+    }
+
+    /// Returns `g(x) = f(x ^ negation)`, by swapping the halves of the truth table of every
+    /// negated input.
+    fn negate_inputs(number_of_inputs: usize, negation: u8, f: TruthTableEntry) -> TruthTableEntry {
+        let mask = Self::get_mask(number_of_inputs);
+        let mut result = f;
+        for (i, identity) in IDENTITY_TRUTH_TABLES
+            .iter()
+            .enumerate()
+            .take(number_of_inputs)
+        {
+            if (negation >> i) & 1 == 1 {
+                let shift = 1 << i;
+                result = ((result & identity) >> shift) | ((result & !identity & mask) << shift);
+            }
+        }
+        result
+    }
+
+    fn get_all_permutations(number_of_inputs: usize) -> Vec<[usize; TRUTH_TABLE_MAX_INPUTS]> {
+        let mut result = vec![[0, 1, 2, 3]];
+        for n in 1..number_of_inputs {
+            // insert input n in every position of the permutations of the first n inputs
+            let mut next = vec![];
+            for p in result.iter() {
+                for position in 0..=n {
+                    let mut q = *p;
+                    q[position..=n].rotate_right(1);
+                    next.push(q);
+                }
+            }
+            result = next;
+        }
+        result
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    pub fn new_identity(number_of_inputs: usize) -> Self {
+        debug_assert!(number_of_inputs <= TRUTH_TABLE_MAX_INPUTS);
+        Self {
+            number_of_inputs,
+            permutation: [0, 1, 2, 3],
+            input_negation: 0,
+            output_negation: false,
+        }
+    }
+
+    pub fn get_number_of_inputs(&self) -> usize {
+        self.number_of_inputs
+    }
+
+    /// The position of every input in the representative.
+    pub fn get_permutation(&self) -> &[usize] {
+        &self.permutation[..self.number_of_inputs]
+    }
+
+    pub fn is_input_negated(&self, input: usize) -> bool {
+        (self.input_negation >> input) & 1 == 1
+    }
+
+    pub fn is_output_negated(&self) -> bool {
+        self.output_negation
+    }
+
+    /// Apply the transformation to a truth table entry over `number_of_inputs` inputs, only the
+    /// first `2^number_of_inputs` bits of the result are set.
+    pub fn apply(&self, f: TruthTableEntry) -> TruthTableEntry {
+        let n = self.number_of_inputs;
+        let permuted = Self::permute(n, &self.permutation, f);
+        let mut negation = 0;
+        for i in 0..n {
+            if self.is_input_negated(i) {
+                negation |= 1 << self.permutation[i];
+            }
+        }
+        let result = Self::negate_inputs(n, negation, permuted);
+        let mask = Self::get_mask(n);
+        if self.output_negation {
+            !result & mask
+        } else {
+            result & mask
+        }
+    }
+
+    /// Returns the transformation that takes the representative back to the function.
+    pub fn inverse(&self) -> Self {
+        let mut result = Self::new_identity(self.number_of_inputs);
+        for i in 0..self.number_of_inputs {
+            let j = self.permutation[i];
+            result.permutation[j] = i;
+            if self.is_input_negated(i) {
+                result.input_negation |= 1 << j;
+            }
+        }
+        result.output_negation = self.output_negation;
+        result
+    }
+}
+
+// ************************************************************************************************
+// impl class
+// ************************************************************************************************
+
+impl NpnClass {
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Returns the class of the function and the transformation that takes the function to the
+    /// class representative (the smallest truth table in the class).
+    pub fn canonize(number_of_inputs: usize, f: TruthTableEntry) -> (Self, NpnTransform) {
+        assert!(number_of_inputs <= TRUTH_TABLE_MAX_INPUTS);
+        let n = number_of_inputs;
+        let mask = NpnTransform::get_mask(n);
+        let mut best: Option<(TruthTableEntry, NpnTransform)> = None;
+        for permutation in NpnTransform::get_all_permutations(n) {
+            let permuted = NpnTransform::permute(n, &permutation, f & mask);
+            for negation in 0..(1 << n) {
+                let negated = NpnTransform::negate_inputs(n, negation, permuted);
+                for (c, output_negation) in [(negated, false), (!negated & mask, true)] {
+                    if best.as_ref().map(|(b, _)| c < *b).unwrap_or(true) {
+                        // input i of the function is input permutation[i] of the result
+                        let mut input_negation = 0;
+                        for (i, p) in permutation.iter().take(n).enumerate() {
+                            input_negation |= ((negation >> p) & 1) << i;
+                        }
+                        let t = NpnTransform {
+                            number_of_inputs: n,
+                            permutation,
+                            input_negation,
+                            output_negation,
+                        };
+                        best = Some((c, t));
+                    }
+                }
+            }
+        }
+        let (representative, transform) = best.unwrap();
+        debug_assert_eq!(transform.apply(f), representative);
+        (
+            Self {
+                number_of_inputs,
+                representative,
+            },
+            transform,
+        )
+    }
+
+    pub fn canonize_with_cache(
+        number_of_inputs: usize,
+        f: TruthTableEntry,
+        cache: &mut NpnCache,
+    ) -> (Self, NpnTransform) {
+        let mask = NpnTransform::get_mask(number_of_inputs);
+        *cache
+            .entry((number_of_inputs, f & mask))
+            .or_insert_with(|| Self::canonize(number_of_inputs, f))
+    }
+
+    /// Returns the class of the function.
+    pub fn new(number_of_inputs: usize, f: TruthTableEntry) -> Self {
+        Self::canonize(number_of_inputs, f).0
+    }
+
+    /// Returns one of the well known classes: `and2`, `and3`, `xor2`, `xor3`, `ite` or `maj`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        NAMED_CLASSES
+            .iter()
+            .find(|(n, _, _)| *n == name)
+            .map(|(_, i, f)| Self::new(*i, *f))
+    }
+
+    /// Returns the name of the class if it is one of the well known classes.
+    pub fn get_name(&self) -> Option<&'static str> {
+        NAMED_CLASSES
+            .iter()
+            .find(|(_, i, f)| Self::new(*i, *f) == *self)
+            .map(|(n, _, _)| *n)
+    }
+
+    pub fn get_number_of_inputs(&self) -> usize {
+        self.number_of_inputs
+    }
+
+    pub fn get_representative(&self) -> TruthTableEntry {
+        self.representative
+    }
+
+    /// Returns all the NPN classes of functions with the provided number of inputs (2, 4, 14
+    /// and 222 classes for 1, 2, 3 and 4 inputs), sorted by their representatives.
+    pub fn get_all_classes(number_of_inputs: usize) -> Vec<Self> {
+        assert!(number_of_inputs <= TRUTH_TABLE_MAX_INPUTS);
+        let n = number_of_inputs;
+        let number_of_functions = 1 << (1 << n);
+        let transforms: Vec<NpnTransform> = NpnTransform::get_all_permutations(n)
+            .into_iter()
+            .flat_map(|permutation| {
+                (0..(1 << n)).flat_map(move |input_negation| {
+                    [false, true].map(|output_negation| NpnTransform {
+                        number_of_inputs: n,
+                        permutation,
+                        input_negation,
+                        output_negation,
+                    })
+                })
+            })
+            .collect();
+
+        // the first function of every class that was not seen yet is the smallest in it
+        let mut is_seen = vec![false; number_of_functions];
+        let mut result = vec![];
+        for f in 0..number_of_functions {
+            if is_seen[f] {
+                continue;
+            }
+            for t in transforms.iter() {
+                is_seen[t.apply(f as TruthTableEntry) as usize] = true;
+            }
+            result.push(Self {
+                number_of_inputs,
+                representative: f as TruthTableEntry,
+            });
+        }
+        result
+    }
+}
+
+// ************************************************************************************************
+// impl truth table
+// ************************************************************************************************
+
+impl TruthTable {
+    /// Returns the NPN class of the truth table and the transformation that takes the truth table
+    /// to the class representative, input `i` of the transformation is the `i`th input signal.
+    pub fn get_npn_class(&self) -> (NpnClass, NpnTransform) {
+        NpnClass::canonize(self.input_names.len(), self.truth_table & self.mask)
+    }
+
+    pub fn get_npn_class_with_cache(&self, cache: &mut NpnCache) -> (NpnClass, NpnTransform) {
+        NpnClass::canonize_with_cache(self.input_names.len(), self.truth_table & self.mask, cache)
+    }
+
+    /// Checks if the two truth tables compute the same function up to NPN, regardless of the
+    /// names of their inputs.
+    pub fn is_npn_equivalent(&self, other: &TruthTable) -> bool {
+        self.input_names.len() == other.input_names.len()
+            && self.get_npn_class().0 == other.get_npn_class().0
+    }
+}
+
+// ************************************************************************************************
+// printing
+// ************************************************************************************************
+
+impl fmt::Display for NpnClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.get_name() {
+            Some(name) => write!(f, "{}", name),
+            None => write!(f, "npn{}_{:#x}", self.number_of_inputs, self.representative),
+        }
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[test]
+fn test_npn_classes() {
+    use rand::{rngs::ThreadRng, Rng};
+
+    let number_of_classes: Vec<usize> = (0..=TRUTH_TABLE_MAX_INPUTS)
+        .map(|n| NpnClass::get_all_classes(n).len())
+        .collect();
+    assert_eq!(number_of_classes, vec![1, 2, 4, 14, 222]);
+
+    // the class does not depend on the polarity or order of the inputs
+    let mut rng = ThreadRng::default();
+    for _ in 0..100 {
+        let n = rng.gen_range(0..=TRUTH_TABLE_MAX_INPUTS);
+        let f = rng.gen::<TruthTableEntry>() & NpnTransform::get_mask(n);
+        let (class, t) = NpnClass::canonize(n, f);
+        assert_eq!(t.apply(f), class.get_representative());
+        assert_eq!(t.inverse().apply(class.get_representative()), f);
+
+        let permutations = NpnTransform::get_all_permutations(n);
+        let r = NpnTransform {
+            number_of_inputs: n,
+            permutation: permutations[rng.gen_range(0..permutations.len())],
+            input_negation: rng.gen_range(0..(1 << n)),
+            output_negation: rng.gen(),
+        };
+        assert_eq!(NpnClass::new(n, r.apply(f)), class);
+    }
+
+    // xnor with a negated input is xor
+    assert_eq!(NpnClass::new(2, 0x9).get_name(), Some("xor2"));
+    assert_eq!(NpnClass::new(3, 0xE4).to_string(), "ite");
+}
@@ -78,6 +78,49 @@ impl TruthTable {
         result
     }
 
+    /// returns a new truth table where the j-th input of this truth table is replaced by the
+    /// function described by the j-th truth table in `inputs`.
+    /// The inputs of the new truth table are the merged inputs of the provided truth tables.
+    /// If the new inputs exceed the maximum number of inputs, this function will panic.
+    pub fn compose(&self, mut inputs: Vec<TruthTable>) -> TruthTable {
+        debug_assert_eq!(self.input_names.len(), inputs.len());
+        let signals = UniqueSortedVec::k_merge(inputs.iter().map(|t| &t.input_names), inputs.len());
+        assert!(
+            signals.len() <= TRUTH_TABLE_MAX_INPUTS,
+            "Truth table has too many inputs."
+        );
+        for t in inputs.iter_mut() {
+            for input in signals.iter() {
+                t.add_truth_table_input(input);
+            }
+        }
+
+        let mut result = Self {
+            truth_table: TruthTableEntry::MIN,
+            input_names: signals,
+            mask: 0,
+        };
+        result.mask = result.calculate_mask();
+
+        // each row of this truth table that is true adds the rows where the inputs match it
+        for row in 0..self.calculate_number_of_rows() {
+            if !self.get_value(row) {
+                continue;
+            }
+            let mut rows = result.mask;
+            for (j, t) in inputs.iter().enumerate() {
+                if (row >> j) & 1 == 1 {
+                    rows &= t.truth_table;
+                } else {
+                    rows &= !t.truth_table;
+                }
+            }
+            result.truth_table |= rows;
+        }
+        debug_assert!(result.check().is_ok());
+        result
+    }
+
     /// Remove signal from the truth table.
     /// If assumed_value is true, the rows where the signal is true will be removed.
     /// If assumed_value is false, the rows where the signal is false will be removed.
@@ -252,6 +295,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_compose() {
+        let max_signal = Signal::new(TRUTH_TABLE_MAX_INPUTS as u32);
+        let iterations = 10000;
+        for _ in 0..iterations {
+            let seed: u64 = ThreadRng::default().gen();
+            println!("seed = {}", seed);
+            let mut rng = StdRng::seed_from_u64(seed);
+            let i_f = rng.gen_range(1..TRUTH_TABLE_MAX_INPUTS);
+            let f = TruthTable::new_random_truth_table(&mut rng, i_f, max_signal);
+            let inputs: Vec<TruthTable> = (0..f.len())
+                .map(|_| {
+                    let i = rng.gen_range(0..TRUTH_TABLE_MAX_INPUTS);
+                    TruthTable::new_random_truth_table(&mut rng, i, max_signal)
+                })
+                .collect();
+
+            let c = f.compose(inputs.clone());
+            // check truth table for correctness
+            for row in 0..c.calculate_number_of_rows() {
+                let value_of = |t: &TruthTable| {
+                    let mut index = 0;
+                    for (j, s) in t.get_signals().iter().enumerate() {
+                        let k = c.get_signals().peek().binary_search(s).unwrap();
+                        index |= ((row >> k) & 1) << j;
+                    }
+                    t.get_value(index)
+                };
+                let mut index = 0;
+                for (j, t) in inputs.iter().enumerate() {
+                    index |= (value_of(t) as usize) << j;
+                }
+                assert_eq!(c.get_value(row), f.get_value(index));
+            }
+        }
+    }
+
     #[test]
     fn test_simplify() {
         let max_signal = Signal::new(TRUTH_TABLE_MAX_INPUTS as u32);
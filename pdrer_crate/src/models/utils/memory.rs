@@ -0,0 +1,130 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::{
+    fs,
+    process::Command,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use super::Utils;
+
+// ************************************************************************************************
+// constants
+// ************************************************************************************************
+
+/// Spawning `ps` is expensive, so its result is reused for this long.
+const PS_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The last result of `ps` and when it was taken.
+static LAST_PS_RESULT: Mutex<Option<(Instant, Option<usize>)>> = Mutex::new(None);
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl Utils {
+    // ********************************************************************************************
+    // helper functions
+    // ********************************************************************************************
+
+    /// Returns the value of a field of `/proc/<pid>/status`, such as `VmRSS:   1234 kB`, in KB.
+    fn parse_proc_status_field_in_kb(status: &str, field: &str) -> Option<usize> {
+        status
+            .lines()
+            .find_map(|l| l.strip_prefix(field)?.strip_prefix(':'))
+            .and_then(|v| v.trim().strip_suffix("kB"))
+            .and_then(|v| v.trim().parse::<usize>().ok())
+    }
+
+    /// Returns a field of `/proc/self/status` in MB, this is only available on Linux.
+    fn get_proc_status_field_in_mb(field: &str) -> Option<usize> {
+        let status = fs::read_to_string("/proc/self/status").ok()?;
+        Self::parse_proc_status_field_in_kb(&status, field).map(|kb| kb / 1024)
+    }
+
+    /// Returns the resident memory of the process in MB as reported by `ps`.
+    fn get_memory_usage_from_ps_in_mb() -> Option<usize> {
+        // This command will work on both Linux and macOS.
+        let output = Command::new("ps")
+            .args(["-o", "rss=", "-p", &std::process::id().to_string()])
+            .output()
+            .ok()?;
+
+        let memory_kb = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse::<usize>()
+            .ok()?;
+        Some(memory_kb / 1024) // Convert from KB to MB
+    }
+
+    // ********************************************************************************************
+    // API
+    // ********************************************************************************************
+
+    /// Returns the resident memory of the process in MB.
+    /// On Linux this is read from `/proc` which is cheap enough to be done often, on other systems
+    /// `ps` is called instead, at most once every `PS_REFRESH_INTERVAL`.
+    pub fn get_memory_usage_in_mb() -> Option<usize> {
+        if let Some(memory_mb) = Self::get_proc_status_field_in_mb("VmRSS") {
+            return Some(memory_mb);
+        }
+
+        let mut last = LAST_PS_RESULT.lock().unwrap();
+        match *last {
+            Some((time, memory_mb)) if time.elapsed() < PS_REFRESH_INTERVAL => memory_mb,
+            _ => {
+                let memory_mb = Self::get_memory_usage_from_ps_in_mb();
+                *last = Some((Instant::now(), memory_mb));
+                memory_mb
+            }
+        }
+    }
+
+    /// Returns true if the resident memory of the process is above the limit.
+    /// If the memory usage cannot be read then the limit is considered not to be reached.
+    pub fn is_memory_limit_reached(memory_limit_in_mb: usize) -> bool {
+        if memory_limit_in_mb == usize::MAX {
+            return false;
+        }
+        Self::get_memory_usage_in_mb().is_some_and(|m| m >= memory_limit_in_mb)
+    }
+
+    /// Returns the peak resident memory of the process in MB, this is only available on Linux,
+    /// on other systems the current memory usage is returned instead.
+    pub fn get_peak_memory_usage_in_mb() -> Option<usize> {
+        Self::get_proc_status_field_in_mb("VmHWM").or_else(Self::get_memory_usage_in_mb)
+    }
+}
+
+// ************************************************************************************************
+// tests
+// ************************************************************************************************
+
+#[cfg(test)]
+mod tests {
+    use crate::models::Utils;
+
+    #[test]
+    fn test_parse_proc_status() {
+        let status = "Name:\tcargo\nVmHWM:\t  204800 kB\nVmRSSx:\t1 kB\nVmRSS:\t   10240 kB\n";
+        assert_eq!(
+            Utils::parse_proc_status_field_in_kb(status, "VmRSS"),
+            Some(10240)
+        );
+        assert_eq!(
+            Utils::parse_proc_status_field_in_kb(status, "VmHWM"),
+            Some(204800)
+        );
+        assert_eq!(Utils::parse_proc_status_field_in_kb(status, "VmSwap"), None);
+        assert_eq!(Utils::parse_proc_status_field_in_kb(status, "Name"), None);
+
+        if cfg!(target_os = "linux") {
+            let current = Utils::get_memory_usage_in_mb().unwrap();
+            let peak = Utils::get_peak_memory_usage_in_mb().unwrap();
+            assert!(0 < current && current <= peak);
+        }
+    }
+}
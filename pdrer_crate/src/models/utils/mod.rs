@@ -7,7 +7,9 @@
 pub mod ensure;
 pub mod formula_logic;
 pub mod is_sorted;
+pub mod memory;
 pub mod remove_indexes;
+pub mod verilog;
 
 // ************************************************************************************************
 // struct
@@ -0,0 +1,86 @@
+// ************************************************************************************************
+// use
+// ************************************************************************************************
+
+use std::fmt::Write;
+
+use super::Utils;
+
+// ************************************************************************************************
+// constants
+// ************************************************************************************************
+
+/// The keywords of SystemVerilog (IEEE 1800-2017), which include the keywords of Verilog,
+/// separated by white space.
+const VERILOG_KEYWORDS: &str = "\
+    accept_on alias always always_comb always_ff always_latch and assert assign assume automatic \
+    before begin bind bins binsof bit break buf bufif0 bufif1 byte case casex casez cell chandle \
+    checker class clocking cmos config const constraint context continue cover covergroup \
+    coverpoint cross deassign default defparam design disable dist do edge else end endcase \
+    endchecker endclass endclocking endconfig endfunction endgenerate endgroup endinterface \
+    endmodule endpackage endprimitive endprogram endproperty endspecify endsequence endtable \
+    endtask enum event eventually expect export extends extern final first_match for force \
+    foreach forever fork forkjoin function generate genvar global highz0 highz1 if iff ifnone \
+    ignore_bins illegal_bins implements implies import incdir include initial inout input inside \
+    instance int integer interconnect interface intersect join join_any join_none large let \
+    liblist library local localparam logic longint macromodule matches medium modport module \
+    nand negedge nettype new nexttime nmos nor noshowcancelled not notif0 notif1 null or output \
+    package packed parameter pmos posedge primitive priority program property protected pull0 \
+    pull1 pulldown pullup pulsestyle_ondetect pulsestyle_onevent pure rand randc randcase \
+    randsequence rcmos real realtime ref reg reject_on release repeat restrict return rnmos \
+    rpmos rtran rtranif0 rtranif1 s_always s_eventually s_nexttime s_until s_until_with scalared \
+    sequence shortint shortreal showcancelled signed small soft solve specify specparam static \
+    string strong strong0 strong1 struct super supply0 supply1 sync_accept_on sync_reject_on \
+    table tagged task this throughout time timeprecision timeunit tran tranif0 tranif1 tri tri0 \
+    tri1 triand trior trireg type typedef union unique unique0 unsigned until until_with untyped \
+    use uwire var vectored virtual void wait wait_order wand weak weak0 weak1 while wildcard \
+    wire with within wor xnor xor";
+
+// ************************************************************************************************
+// impl
+// ************************************************************************************************
+
+impl Utils {
+    /// Returns the name as a Verilog identifier. Names that are not simple identifiers, or that
+    /// are keywords, are written as escaped identifiers. The name must not contain white space.
+    pub fn get_verilog_identifier(name: &str) -> String {
+        debug_assert!(!name.is_empty() && !name.contains(char::is_whitespace));
+        let mut chars = name.chars();
+        let is_simple = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+        if is_simple && !VERILOG_KEYWORDS.split_whitespace().any(|k| k == name) {
+            name.to_owned()
+        } else {
+            // escaped identifiers end with white space
+            format!("\\{name} ")
+        }
+    }
+
+    /// Writes a constant wire named `table_name` that holds the truth table, where `rows[r]` is
+    /// the value of row `r`, and returns the expression that indexes it with the inputs. The
+    /// first input is the least significant bit of the row.
+    pub fn write_verilog_truth_table(
+        s: &mut String,
+        table_name: &str,
+        rows: &[bool],
+        inputs: &[String],
+    ) -> String {
+        debug_assert_eq!(rows.len(), 1 << inputs.len());
+        let bits: String = rows
+            .iter()
+            .rev()
+            .map(|b| if *b { '1' } else { '0' })
+            .collect();
+        writeln!(
+            s,
+            "    wire [{}:0] {table_name} = {}'b{bits};",
+            rows.len() - 1,
+            rows.len()
+        )
+        .unwrap();
+        let index: Vec<&str> = inputs.iter().rev().map(|x| x.as_str()).collect();
+        format!("{table_name}[{{{}}}]", index.join(", "))
+    }
+}
@@ -125,6 +125,7 @@ impl<const T: char> Drop for CuddBase<T> {
 #[allow(unsafe_code)]
 impl<const T: char> DecisionDiagramManager for CuddBase<T> {
     type DecisionDiagram = CuddBaseNode<T>;
+    const SUPPORTS_SEVERAL_MANAGERS_PER_THREAD: bool = true;
 
     fn new(number_of_vars: usize, number_of_threads: usize, max_memory_in_mb: usize) -> Self {
         assert!(
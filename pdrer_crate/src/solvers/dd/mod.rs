@@ -37,6 +37,10 @@ pub trait DecisionDiagramManager {
     /// be able to clone.
     type DecisionDiagram: Clone;
 
+    /// True if several managers may be used in turns on the same thread. Managers that share
+    /// thread local state (like the oxidd ones) may corrupt each other when this is done.
+    const SUPPORTS_SEVERAL_MANAGERS_PER_THREAD: bool;
+
     /// Initialize a decision diagram manager with the provided number of variables
     fn new(number_of_vars: usize, number_of_threads: usize, max_memory_in_mb: usize) -> Self;
 
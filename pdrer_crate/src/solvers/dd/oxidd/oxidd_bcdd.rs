@@ -15,6 +15,7 @@ pub struct OxiddBcdd {
 
 impl DecisionDiagramManager for OxiddBcdd {
     type DecisionDiagram = BCDDFunction;
+    const SUPPORTS_SEVERAL_MANAGERS_PER_THREAD: bool = false;
 
     fn new(number_of_vars: usize, number_of_threads: usize, max_memory_in_mb: usize) -> Self {
         let (x, y) = get_oxidd_capacities(max_memory_in_mb);
@@ -27,6 +27,7 @@ pub fn get_oxidd_capacities(max_memory_in_mb: usize) -> (usize, usize) {
 
 impl DecisionDiagramManager for OxiddBdd {
     type DecisionDiagram = BDDFunction;
+    const SUPPORTS_SEVERAL_MANAGERS_PER_THREAD: bool = false;
 
     fn new(number_of_vars: usize, number_of_threads: usize, max_memory_in_mb: usize) -> Self {
         let (x, y) = get_oxidd_capacities(max_memory_in_mb);